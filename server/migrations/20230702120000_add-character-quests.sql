@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS character_quests
+(
+    character_id BIGINT NOT NULL REFERENCES characters (id) ON DELETE CASCADE,
+    quest        TEXT NOT NULL,
+    stage        INTEGER NOT NULL DEFAULT 0,
+    progress     INTEGER[] NOT NULL DEFAULT '{}',
+    completed    BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at   TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (character_id, quest)
+);
@@ -0,0 +1 @@
+ALTER TYPE character_role ADD VALUE 'builder';
@@ -1,12 +1,14 @@
 use bevy::prelude::*;
 
+use crate::db::pool::DatabasePool;
+
 use super::systems::*;
 
 pub struct AuthPlugin;
 
 impl Plugin for AuthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(authenticate);
+        app.add_system(authenticate.run_if(resource_exists::<DatabasePool>()));
         app.add_system(handle_user_exists_task);
         app.add_system(handle_authenticate_task);
     }
@@ -17,7 +17,7 @@ use crate::{
         pool::DatabasePool,
     },
     input::events::{Command, ParsedCommand, ProxyCommand},
-    items::components::Inventory,
+    items::components::{Inventory, WrittenPages},
     keycard::Keycard,
     paint,
     player::{
@@ -25,7 +25,8 @@ use crate::{
         components::{Character, CharacterState, Client, Online},
         config::CharacterConfig,
     },
-    spatial::components::{Spawn, Tile},
+    spatial::components::{Instance, Spawn, Tile},
+    utils::prototype_id,
     value_or_continue,
     world::resources::WorldState,
 };
@@ -182,14 +183,15 @@ fn spawn_authenticate_task(
 pub fn handle_authenticate_task(
     mut bevy: Commands,
     mut clients: Query<(Entity, &Client, &mut Authenticating), Without<Online>>,
+    instances: Query<&Instance>,
     mut outbox: EventWriter<Outbox>,
     mut proto: ProtoCommands,
     mut proxy: EventWriter<ProxyCommand>,
     mut tasks: Query<(Entity, &mut AuthenticateTask)>,
     online_characters: Query<(&Client, &Character), With<Online>>,
     server: Res<Server>,
-    spawn_tiles: Query<Entity, (With<Tile>, With<Spawn>)>,
-    tiles: Query<(Entity, &Name), With<Tile>>,
+    spawn_tiles: Query<(Entity, &Parent), (With<Tile>, With<Spawn>)>,
+    tiles: Query<(Entity, &Name, &Parent), With<Tile>>,
     world_state: Res<WorldState>,
 ) {
     for (task_entity, mut task) in &mut tasks {
@@ -221,6 +223,7 @@ pub fn handle_authenticate_task(
                         PlayerBundle {
                             keycard: match character.role {
                                 Role::Admin => Keycard::admin(),
+                                Role::Builder => Keycard::builder(),
                                 Role::Player => Keycard::player(),
                             },
                             character: Character {
@@ -233,7 +236,10 @@ pub fn handle_authenticate_task(
                         },
                     ));
 
-                let spawn = value_or_continue!(spawn_tiles.iter().next());
+                let spawn = value_or_continue!(spawn_tiles
+                    .iter()
+                    .find(|(_, zone)| !instances.contains(zone.get()))
+                    .map(|(tile, _)| tile));
 
                 let character_in_state =
                     world_state.characters.iter().find(|c| c.id == character.id);
@@ -241,11 +247,17 @@ pub fn handle_authenticate_task(
                 if let Some(character_in_state) = character_in_state {
                     let tile = tiles
                         .iter()
-                        .find(|(_, name)| {
-                            name.trim_end_matches(" (Prototype)")
-                                == character_in_state.tile.trim_end_matches(" (Prototype)")
+                        .find(|(_, name, zone)| {
+                            prototype_id(name)
+                                == prototype_id(&character_in_state.tile)
+                                // Only back into an instance that's still theirs.
+                                && instances
+                                    .get(zone.get())
+                                    .map_or(true, |instance| {
+                                        instance.members.contains(&character.id)
+                                    })
                         })
-                        .map(|(e, _)| e)
+                        .map(|(e, _, _)| e)
                         .unwrap_or(spawn);
 
                     bevy.entity(player_entity)
@@ -253,10 +265,17 @@ pub fn handle_authenticate_task(
                         .with_children(|parent| {
                             let mut inventory = parent.spawn(Inventory);
 
-                            for item_name in character_in_state.inventory.iter() {
-                                inventory.add_child(
-                                    proto.spawn(item_name.trim_end_matches(" (Prototype)")).id(),
-                                );
+                            for item in character_in_state.inventory.iter() {
+                                let item_entity = proto.spawn(prototype_id(&item.name)).id();
+
+                                if !item.written_pages.is_empty() {
+                                    inventory
+                                        .commands()
+                                        .entity(item_entity)
+                                        .insert(WrittenPages(item.written_pages.clone()));
+                                }
+
+                                inventory.add_child(item_entity);
                             }
                         });
                 } else {
@@ -5,7 +5,10 @@ use bevy_nest::prelude::*;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
     interact::components::{Interaction, Interactions},
     npc::components::Npc,
     player::{
@@ -30,7 +33,7 @@ pub fn handle_attack(content: &str) -> Result<Command, ParseError> {
                 .map(|m| m.as_str().trim())
                 .ok_or(ParseError::InvalidArguments("Attack what?".into()))?;
 
-            Ok(Command::Attack(target.into()))
+            Ok(Command::Attack(Target::parse(target)))
         }
     }
 }
@@ -49,15 +52,28 @@ pub fn attack(
                 value_or_continue!(players.iter_mut().find(|(_, c, _)| c.id == command.from));
             let siblings = value_or_continue!(tiles.get(tile.get()).ok());
 
-            let Some((entity, _, interactions)) = siblings
+            let candidates = siblings
                 .iter()
                 .filter_map(|sibling| npcs.get(*sibling).ok())
-                .find(|(entity, depiction, _)| depiction.matches_query(entity, target)) else {
-                outbox.send_text(client.id, format!("You don't see a {target} here."));
+                .map(|(entity, depiction, _)| (entity, depiction))
+                .collect::<Vec<_>>();
 
-                continue;
+            let entity = match target.resolve(&candidates) {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
             };
 
+            let (entity, _, interactions) = value_or_continue!(npcs.get(entity).ok());
+
             if interactions.map_or(true, |i| !i.0.contains(&Interaction::Attack)) {
                 outbox.send_text(client.id, format!("You can't attack the {target}."));
 
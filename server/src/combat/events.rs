@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Sent when a player kills an NPC. The NPC may be despawned by the time this is read, so it's
+/// named by its prototype.
+// Nothing deals damage yet; combat sends this once NPCs can die.
+#[allow(dead_code)]
+pub struct Killed {
+    pub killer: Entity,
+    pub npc: String,
+}
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 
-use super::commands::attack::*;
+use super::{commands::attack::*, events::Killed};
 
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
+        app.add_event::<Killed>();
+
         app.add_system(attack);
     }
 }
@@ -0,0 +1,349 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    interact::components::Openable,
+    spatial::{
+        components::{Doorway, Exits, Position, Tile, Transition, Zone},
+        utils::{offset_for_direction, opposite_direction, passage, Passage},
+    },
+    visual::components::{Depiction, Sprite},
+};
+
+/// One of each pair of opposite directions, so each way between two tiles is drawn once.
+const EDGE_DIRECTIONS: [&str; 5] = ["north", "northeast", "east", "southeast", "up"];
+
+/// Draws the spawned world as a Graphviz graph and as plain text maps.
+#[derive(SystemParam)]
+pub struct MapExporter<'w, 's> {
+    doorways: Query<
+        'w,
+        's,
+        (
+            &'static Doorway,
+            &'static Depiction,
+            Option<&'static Openable>,
+        ),
+    >,
+    exits: Query<'w, 's, &'static Exits>,
+    tiles: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Tile,
+            &'static Position,
+            &'static Sprite,
+            &'static Parent,
+        ),
+    >,
+    transitions: Query<'w, 's, (&'static Transition, &'static Depiction, &'static Parent)>,
+    zones: Query<'w, 's, (Entity, &'static Zone)>,
+}
+
+impl<'w, 's> MapExporter<'w, 's> {
+    /// A DOT graph with a cluster of tiles for each zone. Ways that can be walked both
+    /// ways are drawn as one edge with arrows at both ends, and transitions are dashed.
+    pub fn graph(&self) -> String {
+        let mut lines = vec!["digraph world {".to_string()];
+
+        for (zone, Zone { name }) in self.sorted_zones() {
+            lines.push(format!(
+                "    subgraph {} {{",
+                quote(&format!("cluster_{name}"))
+            ));
+            lines.push(format!("        label={};", quote(name)));
+
+            for (_, tile, position, _, _) in self.sorted_tiles(zone) {
+                lines.push(format!(
+                    "        {} [label={}];",
+                    quote(&node(name, position.0)),
+                    quote(&tile.name),
+                ));
+            }
+
+            lines.push("    }".into());
+        }
+
+        let by_position = self
+            .tiles
+            .iter()
+            .map(|(entity, _, position, _, zone)| ((zone.get(), position.0), entity))
+            .collect::<HashMap<_, _>>();
+
+        for (zone, _) in self.sorted_zones() {
+            for (tile, _, position, _, _) in self.sorted_tiles(zone) {
+                for direction in EDGE_DIRECTIONS {
+                    let Some(offset) = offset_for_direction(direction) else {
+                        continue;
+                    };
+
+                    let Some(neighbour) = by_position.get(&(zone, position.0 + offset)) else {
+                        continue;
+                    };
+
+                    let there = !matches!(
+                        passage(tile, *neighbour, offset, &self.exits, &self.doorways),
+                        Passage::Blocked
+                    );
+                    let back = !matches!(
+                        passage(*neighbour, tile, -offset, &self.exits, &self.doorways),
+                        Passage::Blocked
+                    );
+
+                    let (from, to, direction) = match (there, back) {
+                        (false, false) => continue,
+                        (false, true) => (
+                            *neighbour,
+                            tile,
+                            opposite_direction(direction).unwrap_or(direction),
+                        ),
+                        _ => (tile, *neighbour, direction),
+                    };
+
+                    let mut label = direction.to_string();
+
+                    if let Some((_, door, _)) = self.doorways.iter().find(|(doorway, _, _)| {
+                        (doorway.from == tile && doorway.to == *neighbour)
+                            || (doorway.from == *neighbour && doorway.to == tile)
+                    }) {
+                        label = format!("{label}, {}", door.name);
+                    }
+
+                    lines.push(format!(
+                        "    {} -> {} [label={}{}];",
+                        quote(&self.node_of(from)),
+                        quote(&self.node_of(to)),
+                        quote(&label),
+                        if there && back { ", dir=both" } else { "" },
+                    ));
+                }
+            }
+        }
+
+        let mut transitions = self
+            .transitions
+            .iter()
+            .filter(|(_, _, tile)| self.tiles.contains(tile.get()))
+            .map(|(transition, depiction, tile)| {
+                let label = match depiction.tags.first() {
+                    Some(tag) => format!("enter {tag}"),
+                    None => "enter".into(),
+                };
+
+                format!(
+                    "    {} -> {} [label={}, style=dashed];",
+                    quote(&self.node_of(tile.get())),
+                    quote(&node(&transition.zone, transition.position)),
+                    quote(&label),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        transitions.sort();
+
+        lines.extend(transitions);
+        lines.push("}".into());
+
+        lines.join("\n") + "\n"
+    }
+
+    /// A map for each zone, keyed by zone name, drawn with each tile's sprite. Every level
+    /// of the zone gets its own grid, from the top down, with north at the top.
+    pub fn maps(&self) -> Vec<(String, String)> {
+        self.sorted_zones()
+            .into_iter()
+            .map(|(zone, Zone { name })| {
+                let mut levels: BTreeMap<i32, Vec<(IVec3, &Sprite)>> = BTreeMap::new();
+
+                for (_, _, position, sprite, _) in self.sorted_tiles(zone) {
+                    levels
+                        .entry(position.0.z)
+                        .or_default()
+                        .push((position.0, sprite));
+                }
+
+                let drawn = levels
+                    .iter()
+                    .rev()
+                    .map(|(z, tiles)| {
+                        let min = tiles
+                            .iter()
+                            .fold(IVec3::splat(i32::MAX), |min, (p, _)| min.min(*p));
+                        let max = tiles
+                            .iter()
+                            .fold(IVec3::splat(i32::MIN), |max, (p, _)| max.max(*p));
+
+                        let width = (max.x - min.x + 1) as usize;
+                        let height = (max.y - min.y + 1) as usize;
+
+                        let mut grid = vec![vec![' '; width]; height];
+
+                        for (position, sprite) in tiles {
+                            grid[(position.y - min.y) as usize][(position.x - min.x) as usize] =
+                                sprite.character.chars().next().unwrap_or(' ');
+                        }
+
+                        let rows = grid
+                            .iter()
+                            .map(|row| row.iter().collect::<String>().trim_end().to_string())
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        format!("Level {z}, from ({}, {}):\n{rows}", min.x, min.y)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+
+                (name.clone(), format!("{name}\n\n{drawn}\n"))
+            })
+            .collect()
+    }
+
+    fn sorted_zones(&self) -> Vec<(Entity, &Zone)> {
+        let mut zones = self.zones.iter().collect::<Vec<_>>();
+
+        zones.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+
+        zones
+    }
+
+    fn sorted_tiles(&self, zone: Entity) -> Vec<(Entity, &Tile, &Position, &Sprite, &Parent)> {
+        let mut tiles = self
+            .tiles
+            .iter()
+            .filter(|(_, _, _, _, parent)| parent.get() == zone)
+            .collect::<Vec<_>>();
+
+        tiles.sort_by_key(|(_, _, position, _, _)| (position.0.z, position.0.y, position.0.x));
+
+        tiles
+    }
+
+    fn node_of(&self, tile: Entity) -> String {
+        let Ok((_, _, position, _, zone)) = self.tiles.get(tile) else {
+            return format!("{tile:?}");
+        };
+
+        let zone = self.zones.get(zone.get()).map_or_else(
+            |_| format!("{:?}", zone.get()),
+            |(_, zone)| zone.name.clone(),
+        );
+
+        node(&zone, position.0)
+    }
+}
+
+/// Tiles are named by where they are, so transitions can point at them by zone name and
+/// position alone.
+fn node(zone: &str, position: IVec3) -> String {
+    format!("{zone} ({}, {}, {})", position.x, position.y, position.z)
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use crate::test::{
+        app_builder::AppBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        transition_builder::TransitionBuilder,
+    };
+
+    use super::*;
+
+    fn exporter(app: &mut App) -> SystemState<MapExporter<'static, 'static>> {
+        SystemState::new(&mut app.world)
+    }
+
+    #[test]
+    fn graphs_tiles_and_transitions() {
+        let mut app = AppBuilder::new().build();
+
+        let trinus = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .name("Gate")
+            .position(IVec3::ZERO)
+            .build(&mut app, trinus);
+        TileBuilder::new()
+            .name("Road")
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, trinus);
+        TileBuilder::new()
+            .name("Tower")
+            .position(IVec3::new(0, -1, 0))
+            .one_way("south")
+            .build(&mut app, trinus);
+
+        let cave = ZoneBuilder::new().name("Cave").build(&mut app);
+        let mouth = TileBuilder::new()
+            .name("Mouth")
+            .position(IVec3::ZERO)
+            .build(&mut app, cave);
+
+        TransitionBuilder::new()
+            .tags(&vec!["cave"])
+            .build(&mut app, gate, mouth);
+
+        let mut state = exporter(&mut app);
+
+        assert_eq!(
+            state.get(&app.world).graph(),
+            [
+                "digraph world {",
+                "    subgraph \"cluster_Cave\" {",
+                "        label=\"Cave\";",
+                "        \"Cave (0, 0, 0)\" [label=\"Mouth\"];",
+                "    }",
+                "    subgraph \"cluster_Trinus\" {",
+                "        label=\"Trinus\";",
+                "        \"Trinus (0, -1, 0)\" [label=\"Tower\"];",
+                "        \"Trinus (0, 0, 0)\" [label=\"Gate\"];",
+                "        \"Trinus (1, 0, 0)\" [label=\"Road\"];",
+                "    }",
+                "    \"Trinus (0, -1, 0)\" -> \"Trinus (1, 0, 0)\" [label=\"southeast\", dir=both];",
+                "    \"Trinus (0, -1, 0)\" -> \"Trinus (0, 0, 0)\" [label=\"south\"];",
+                "    \"Trinus (0, 0, 0)\" -> \"Trinus (1, 0, 0)\" [label=\"east\", dir=both];",
+                "    \"Trinus (0, 0, 0)\" -> \"Cave (0, 0, 0)\" [label=\"enter cave\", style=dashed];",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn draws_each_level() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+
+        for (x, y, z, sprite) in [
+            (0, 0, 0, "."),
+            (2, 0, 0, "#"),
+            (2, 1, 0, "+"),
+            (5, 5, 1, "^"),
+        ] {
+            TileBuilder::new()
+                .position(IVec3::new(x, y, z))
+                .sprite(sprite)
+                .build(&mut app, zone);
+        }
+
+        let mut state = exporter(&mut app);
+
+        assert_eq!(
+            state.get(&app.world).maps(),
+            vec![(
+                "Trinus".to_string(),
+                "Trinus\n\nLevel 1, from (5, 5):\n^\n\nLevel 0, from (0, 0):\n. #\n  +\n"
+                    .to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,4 @@
+mod export;
+pub mod plugin;
+pub mod resources;
+mod systems;
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use super::{resources::ExportDir, systems::*};
+
+/// Checks the prototypes in `world/` for mistakes instead of running the game, for
+/// `server --check-content`.
+pub struct ContentCheckPlugin;
+
+impl Plugin for ContentCheckPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(check_content.run_if(world_settled));
+    }
+}
+
+/// Writes a graph and maps of the world to `dir` instead of running the game, for
+/// `server --export-maps [dir]`.
+pub struct MapExportPlugin {
+    pub dir: PathBuf,
+}
+
+impl Plugin for MapExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ExportDir(self.dir.clone()))
+            .add_system(export_maps.run_if(world_settled));
+    }
+}
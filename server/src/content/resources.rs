@@ -0,0 +1,7 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+/// Where `server --export-maps` writes its files.
+#[derive(Resource)]
+pub struct ExportDir(pub PathBuf);
@@ -0,0 +1,491 @@
+use std::{collections::HashMap, fs};
+
+use bevy::{app::AppExit, ecs::system::SystemParam, prelude::*};
+use bevy_proto::prelude::*;
+
+use crate::{
+    items::components::{Item, Surface},
+    npc::components::EnemySpawner,
+    spatial::{
+        components::{Position, Spawn, Tile, Transition, Zone},
+        pathfinding::Pathfinder,
+    },
+    visual::components::Depiction,
+    wilderness::{components::Wilderness, utils::in_bounds},
+    world::resources::{InstancedZones, PendingZones, WorldFiles},
+};
+
+use super::{export::MapExporter, resources::ExportDir};
+
+/// Frames to wait once nothing is loading or waiting to spawn, so prototypes loaded
+/// outside of `world/` and zones found on the last frame have caught up.
+const SETTLE_FRAMES: u32 = 60;
+
+/// Looks over the spawned world for mistakes in its prototypes.
+#[derive(SystemParam)]
+pub struct ContentChecker<'w, 's> {
+    instanced: Res<'w, InstancedZones>,
+    items: Query<'w, 's, &'static Item>,
+    pathfinder: Pathfinder<'w, 's>,
+    spawners: Query<'w, 's, (&'static EnemySpawner, &'static Parent)>,
+    spawn_tiles: Query<'w, 's, Entity, (With<Tile>, With<Spawn>)>,
+    surfaces: Query<
+        'w,
+        's,
+        (
+            &'static Surface,
+            &'static Depiction,
+            Option<&'static Parent>,
+            Option<&'static Children>,
+        ),
+    >,
+    tiles: Query<'w, 's, (Entity, &'static Tile, &'static Position, &'static Parent)>,
+    transitions: Query<'w, 's, (&'static Transition, &'static Parent)>,
+    wilderness: Query<'w, 's, &'static Wilderness>,
+    zones: Query<'w, 's, (Entity, &'static Zone)>,
+}
+
+impl<'w, 's> ContentChecker<'w, 's> {
+    /// Describes every problem found, with `is_prototype` telling which prototype names
+    /// exist.
+    pub fn problems(&self, is_prototype: impl Fn(&str) -> bool) -> Vec<String> {
+        let mut problems = vec![];
+
+        if self.zones.is_empty() {
+            problems.push("No zones were spawned.".into());
+        }
+
+        let mut by_position: HashMap<(Entity, IVec3), &Tile> = HashMap::new();
+
+        for (_, tile, position, zone) in self.tiles.iter() {
+            if let Some(other) = by_position.insert((zone.get(), position.0), tile) {
+                problems.push(format!(
+                    "{} and {} are both at {} in {}.",
+                    other.name,
+                    tile.name,
+                    describe_position(position.0),
+                    self.zone_name(zone.get()),
+                ));
+            }
+        }
+
+        // Players walk into a wilderness from elsewhere, so it doesn't need a spawn tile.
+        for (zone, Zone { name }) in self.zones.iter() {
+            if !self.wilderness.contains(zone)
+                && !self.spawn_tiles.iter().any(|tile| {
+                    self.tiles
+                        .get(tile)
+                        .is_ok_and(|(.., parent)| parent.get() == zone)
+                })
+            {
+                problems.push(format!("{name} has no spawn tile."));
+            }
+        }
+
+        // Instanced zones aren't spawned until someone enters one, so there's nothing to
+        // check them against.
+        for (transition, tile) in self
+            .transitions
+            .iter()
+            .filter(|(transition, _)| !self.instanced.0.contains_key(&transition.zone))
+        {
+            let Some((zone, _)) = self
+                .zones
+                .iter()
+                .find(|(_, zone)| zone.name == transition.zone)
+            else {
+                problems.push(format!(
+                    "The transition on {} leads to {}, which doesn't exist.",
+                    self.describe_tile(tile.get()),
+                    transition.zone,
+                ));
+
+                continue;
+            };
+
+            let generated = self
+                .wilderness
+                .get(zone)
+                .is_ok_and(|wilderness| in_bounds(wilderness, transition.position));
+
+            if !generated && !by_position.contains_key(&(zone, transition.position)) {
+                problems.push(format!(
+                    "The transition on {} leads to {} in {}, where there is no tile.",
+                    self.describe_tile(tile.get()),
+                    describe_position(transition.position),
+                    transition.zone,
+                ));
+            }
+        }
+
+        // Without any spawn tiles every zone has already been reported, and nothing is
+        // reachable.
+        if !self.spawn_tiles.is_empty() {
+            let reachable = self.pathfinder.reachable(self.spawn_tiles.iter());
+
+            // Tiles in a wilderness are joined up by ground that's only generated in play.
+            for (tile, .., zone) in self.tiles.iter() {
+                if !self.wilderness.contains(zone.get()) && !reachable.contains(&tile) {
+                    problems.push(format!(
+                        "{} can't be reached from any spawn tile.",
+                        self.describe_tile(tile),
+                    ));
+                }
+            }
+        }
+
+        for (spawner, tile) in self.spawners.iter() {
+            if !is_prototype(&spawner.enemies.0) {
+                problems.push(format!(
+                    "The enemy spawner on {} uses {}, which doesn't exist.",
+                    self.describe_tile(tile.get()),
+                    spawner.enemies.0,
+                ));
+            }
+        }
+
+        for (surface, depiction, parent, children) in self.surfaces.iter() {
+            let held = children
+                .iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|child| self.items.get(*child).ok())
+                .map(|item| u32::from(item.size.value()))
+                .sum::<u32>();
+
+            if held > u32::from(surface.capacity) {
+                let place = parent
+                    .filter(|parent| self.tiles.contains(parent.get()))
+                    .map(|tile| format!(" on {}", self.describe_tile(tile.get())))
+                    .unwrap_or_default();
+
+                problems.push(format!(
+                    "The {}{} holds {} worth of items, over its capacity of {}.",
+                    depiction.name, place, held, surface.capacity,
+                ));
+            }
+        }
+
+        problems
+    }
+
+    fn zone_name(&self, zone: Entity) -> String {
+        self.zones
+            .get(zone)
+            .map_or_else(|_| format!("{zone:?}"), |(_, zone)| zone.name.clone())
+    }
+
+    fn describe_tile(&self, tile: Entity) -> String {
+        match self.tiles.get(tile) {
+            Ok((_, tile, _, zone)) => format!("{} in {}", tile.name, self.zone_name(zone.get())),
+            Err(_) => format!("{tile:?}"),
+        }
+    }
+}
+
+fn describe_position(position: IVec3) -> String {
+    format!("({}, {}, {})", position.x, position.y, position.z)
+}
+
+/// Whether the world has finished loading and spawning, and stayed that way for a little
+/// while.
+pub fn world_settled(
+    files: Res<WorldFiles>,
+    pending: Res<PendingZones>,
+    mut settled: Local<u32>,
+) -> bool {
+    if !files.loading.is_empty() || !pending.0.is_empty() {
+        *settled = 0;
+
+        return false;
+    }
+
+    *settled += 1;
+
+    *settled >= SETTLE_FRAMES
+}
+
+/// Reports any problems in the world, then exits, with a failure code if there were any.
+pub fn check_content(
+    checker: ContentChecker,
+    mut exit: EventWriter<AppExit>,
+    files: Res<WorldFiles>,
+    prototypes: Prototypes,
+) {
+    let problems = files
+        .failed
+        .iter()
+        .map(|failure| format!("Failed to load {failure}."))
+        .chain(checker.problems(|prototype| prototypes.is_ready(prototype)))
+        .collect::<Vec<_>>();
+
+    if problems.is_empty() {
+        info!("No problems found in the world's content");
+
+        exit.send(AppExit);
+
+        return;
+    }
+
+    for problem in &problems {
+        error!("{}", problem);
+    }
+
+    error!("Found {} problems in the world's content", problems.len());
+
+    std::process::exit(1);
+}
+
+/// Writes `world.dot` and a text map of each zone to the export directory, then exits.
+pub fn export_maps(dir: Res<ExportDir>, mut exit: EventWriter<AppExit>, exporter: MapExporter) {
+    let files = std::iter::once(("world.dot".to_string(), exporter.graph())).chain(
+        exporter.maps().into_iter().map(|(zone, map)| {
+            (
+                format!("{}.txt", zone.to_lowercase().replace(' ', "-")),
+                map,
+            )
+        }),
+    );
+
+    if let Err(err) = fs::create_dir_all(&dir.0) {
+        error!("Failed to create {}: {}", dir.0.display(), err);
+
+        std::process::exit(1);
+    }
+
+    for (name, content) in files {
+        let path = dir.0.join(name);
+
+        if let Err(err) = fs::write(&path, content) {
+            error!("Failed to write {}: {}", path.display(), err);
+
+            std::process::exit(1);
+        }
+
+        info!("Wrote {}", path.display());
+    }
+
+    exit.send(AppExit);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use crate::{
+        items::components::{Size, SurfaceKind},
+        test::{
+            app_builder::AppBuilder,
+            item_builder::ItemBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+        },
+    };
+
+    use super::*;
+
+    fn problems(app: &mut App) -> Vec<String> {
+        let mut state = SystemState::<ContentChecker>::new(&mut app.world);
+
+        state
+            .get_mut(&mut app.world)
+            .problems(|prototype| prototype == "goblin")
+    }
+
+    #[test]
+    fn passes_valid_content() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+
+        app.world
+            .spawn(EnemySpawner {
+                delay: 10.0,
+                enemies: ("goblin".into(), 1, 2),
+                spawned: vec![],
+            })
+            .set_parent(start);
+
+        assert!(problems(&mut app).is_empty());
+    }
+
+    #[test]
+    fn reports_zone_problems() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        TileBuilder::new()
+            .name("Gate")
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .name("Well")
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        assert_eq!(
+            problems(&mut app),
+            vec![
+                "Gate and Well are both at (0, 0, 0) in Trinus.",
+                "Trinus has no spawn tile.",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unreachable_tiles() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .name("Tower")
+            .position(IVec3::new(5, 0, 0))
+            .build(&mut app, zone);
+
+        let cave = ZoneBuilder::new().name("Cave").build(&mut app);
+        let mouth = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, cave);
+        let hollow = TileBuilder::new()
+            .position(IVec3::new(0, 0, -5))
+            .build(&mut app, cave);
+
+        // Locked transitions can still be unlocked.
+        TransitionBuilder::new()
+            .lock("rusty key", true)
+            .build(&mut app, gate, hollow);
+
+        assert_eq!(
+            problems(&mut app),
+            vec!["Tower in Trinus can't be reached from any spawn tile."]
+        );
+
+        app.world.entity_mut(mouth).remove::<Spawn>();
+
+        assert!(problems(&mut app).contains(&"Cave has no spawn tile.".to_string()));
+    }
+
+    #[test]
+    fn reports_broken_transitions() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .name("Gate")
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+
+        let missing_zone = TransitionBuilder::new().build(&mut app, gate, gate);
+        let missing_tile = TransitionBuilder::new().build(&mut app, gate, gate);
+
+        app.world.get_mut::<Transition>(missing_zone).unwrap().zone = "Uruk".into();
+        app.world
+            .get_mut::<Transition>(missing_tile)
+            .unwrap()
+            .position = IVec3::new(0, 3, 0);
+
+        assert_eq!(
+            problems(&mut app),
+            vec![
+                "The transition on Gate in Trinus leads to Uruk, which doesn't exist.",
+                "The transition on Gate in Trinus leads to (0, 3, 0) in Trinus, where there is no tile.",
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_wilderness() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+
+        let heath = ZoneBuilder::new().name("The Heath").build(&mut app);
+        app.world.entity_mut(heath).insert(Wilderness {
+            seed: 1,
+            width: 50,
+            height: 50,
+            biomes: vec![],
+        });
+        let ruin = TileBuilder::new()
+            .position(IVec3::new(30, 30, 0))
+            .build(&mut app, heath);
+
+        let road = TransitionBuilder::new().build(&mut app, gate, ruin);
+        app.world.get_mut::<Transition>(road).unwrap().position = IVec3::new(10, 10, 0);
+
+        assert!(problems(&mut app).is_empty());
+    }
+
+    #[test]
+    fn reports_missing_spawner_prototypes() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .name("Gate")
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+
+        app.world
+            .spawn(EnemySpawner {
+                delay: 10.0,
+                enemies: ("gobbo".into(), 1, 2),
+                spawned: vec![],
+            })
+            .set_parent(gate);
+
+        assert_eq!(
+            problems(&mut app),
+            vec!["The enemy spawner on Gate in Trinus uses gobbo, which doesn't exist."]
+        );
+    }
+
+    #[test]
+    fn reports_overfull_surfaces() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().name("Trinus").build(&mut app);
+        let gate = TileBuilder::new()
+            .name("Gate")
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+
+        let table = ItemBuilder::new()
+            .name("table")
+            .is_surface(SurfaceKind::Floor, 4)
+            .tile(gate)
+            .build(&mut app);
+
+        for size in [Size::Medium, Size::Small, Size::Small] {
+            let item = ItemBuilder::new().size(size).build(&mut app);
+
+            app.world.entity_mut(table).add_child(item);
+        }
+
+        assert_eq!(
+            problems(&mut app),
+            vec!["The table on Gate in Trinus holds 5 worth of items, over its capacity of 4."]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use crate::{player::config::CharacterConfig, world::resources::WorldState};
 #[sqlx(type_name = "character_role", rename_all = "lowercase")]
 pub enum Role {
     Admin,
+    Builder,
     Player,
 }
 
@@ -30,3 +31,11 @@ pub struct WorldSaveModel {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
+
+#[derive(FromRow)]
+pub struct CharacterQuestModel {
+    pub quest: String,
+    pub stage: i32,
+    pub progress: Vec<i32>,
+    pub completed: bool,
+}
@@ -0,0 +1,32 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::events::Command;
+
+/// While present, every line a player sends is collected here instead of being parsed as a
+/// command. A line with only `.` finishes editing and sends the lines as `EditorDone`.
+#[derive(Component, Default)]
+pub struct Editor {
+    pub lines: Vec<String>,
+}
+
+/// Seconds between each queued command being run.
+pub const QUEUE_STEP_SECONDS: f32 = 0.5;
+
+/// Movement steps waiting their turn, from a speedwalk, a travel or several moves sent at once.
+/// They run one at a time until the queue is empty, the player types `stop` or combat starts.
+#[derive(Component)]
+pub struct CommandQueue {
+    pub commands: VecDeque<Command>,
+    pub timer: Timer,
+}
+
+impl CommandQueue {
+    pub fn new(commands: VecDeque<Command>) -> Self {
+        Self {
+            commands,
+            timer: Timer::from_seconds(QUEUE_STEP_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::fmt::{self, Display, Formatter};
 
 use bevy_nest::server::ClientId;
 
+use super::target::Target;
+
 #[derive(Clone, Debug)]
 pub enum ChatChannel {
     Chat,
@@ -29,26 +31,50 @@ impl ChatChannel {
 #[derive(Clone, Debug)]
 pub enum Command {
     Announce(String),
-    Attack(String),
+    Ask((Target, String)),
+    Attack(Target),
     Chat((ChatChannel, String)),
+    Close(Target),
     Config((Option<String>, Option<String>)),
     Describe(Option<String>),
-    Drop((String, bool)),
+    Dig(String),
+    Drop(Target),
+    EditorDone(Vec<String>),
     Emote(String),
     Enter(Option<String>),
-    Examine((Option<String>, Option<usize>)),
+    /// Lists world events, or triggers the one named.
+    Events(Option<String>),
+    Examine((Option<Target>, Option<usize>)),
     Inventory,
-    Look(Option<String>),
+    Lock(Target),
+    Look(Option<Target>),
     Map,
     Movement(String),
-    Place((String, String)),
+    Oedit((Target, String, String)),
+    Open(Target),
+    Path(String),
+    Place((Target, Target)),
+    Quest(String),
+    Quests,
+    Read((Target, Option<usize>)),
+    Redit((String, String)),
+    Reload(String),
     Say(String),
-    Scan((bool, Option<String>)),
-    Take((String, bool, Option<String>)),
+    Scan((bool, Option<Target>)),
+    /// Expanded into its steps and queued as soon as it's parsed.
+    Speedwalk(Vec<Command>),
+    Stop,
+    Take((Target, Option<Target>)),
+    Talk((Option<Target>, Option<usize>)),
     Teleport((String, (i32, i32, i32))),
     Time,
+    Travel(String),
+    Unlock(Target),
+    Weather,
     Who,
+    Write(Target),
     Yell(String),
+    Zsave,
 }
 
 pub enum ParseError {
@@ -1,3 +1,5 @@
+pub mod components;
 pub mod events;
 pub mod plugin;
 pub mod systems;
+pub mod target;
@@ -10,6 +10,8 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ParsedCommand>().add_event::<ProxyCommand>();
 
-        app.add_systems((parse_command, handle_proxy_command).in_base_set(Set::Input));
+        app.add_systems(
+            (parse_command, run_command_queues, handle_proxy_command).in_base_set(Set::Input),
+        );
     }
 }
@@ -1,34 +1,61 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 use bevy_nest::prelude::*;
 
 use crate::{
     combat::commands::attack::handle_attack,
-    interact::commands::{examine::handle_examine, place::handle_place, take::handle_take},
-    items::commands::{drop::handle_drop, inventory::handle_inventory},
+    interact::commands::{
+        ask::handle_ask, close::handle_close, examine::handle_examine, lock::handle_lock,
+        open::handle_open, place::handle_place, take::handle_take, talk::handle_talk,
+        unlock::handle_unlock,
+    },
+    items::commands::{
+        drop::handle_drop, inventory::handle_inventory, read::handle_read, write::handle_write,
+    },
+    olc::commands::{
+        dig::handle_dig, oedit::handle_oedit, redit::handle_redit, zsave::handle_zsave,
+    },
     player::{
         commands::{config::handle_config, describe::handle_describe},
-        components::{Client, Online},
+        components::{Character, Client, Online},
     },
+    quest::commands::{quest::handle_quest, quests::handle_quests},
+    scheduler::commands::events::handle_events,
     social::commands::{
         announce::handle_announce, chat::handle_chat, emote::handle_emote, say::handle_say,
         who::handle_who, yell::handle_yell,
     },
     spatial::commands::{
         enter::handle_enter, look::handle_look, map::handle_map, movement::handle_movement,
-        scan::handle_scan, teleport::handle_teleport,
+        path::handle_path, scan::handle_scan, speedwalk::handle_speedwalk, stop::handle_stop,
+        teleport::handle_teleport, travel::handle_travel,
     },
     value_or_continue,
     visual::paint,
-    world::commands::time::handle_time,
+    weather::commands::weather::handle_weather,
+    world::commands::{reload::handle_reload, time::handle_time},
 };
 
-use super::events::{Command, ParseError, ParsedCommand, ProxyCommand};
+use super::{
+    components::{CommandQueue, Editor},
+    events::{Command, ParseError, ParsedCommand, ProxyCommand},
+};
 
 pub fn parse_command(
+    mut bevy: Commands,
     mut inbox: EventReader<Inbox>,
     mut outbox: EventWriter<Outbox>,
     mut commands: EventWriter<ParsedCommand>,
-    players: Query<&Client, With<Online>>,
+    mut players: Query<
+        (
+            Entity,
+            &Client,
+            Option<&mut Editor>,
+            Option<&mut CommandQueue>,
+        ),
+        With<Online>,
+    >,
 ) {
     for (message, content) in inbox.iter().filter_map(|m| {
         if let Message::Text(content) = &m.content {
@@ -37,43 +64,161 @@ pub fn parse_command(
             None
         }
     }) {
-        let client = value_or_continue!(players.iter().find(|c| c.id == message.from));
-
-        let handlers: Vec<Box<dyn Fn(&str) -> Result<Command, ParseError>>> = vec![
-            Box::new(handle_announce),
-            Box::new(handle_attack),
-            Box::new(handle_chat),
-            Box::new(handle_config),
-            Box::new(handle_describe),
-            Box::new(handle_drop),
-            Box::new(handle_emote),
-            Box::new(handle_enter),
-            Box::new(handle_examine),
-            Box::new(handle_inventory),
-            Box::new(handle_look),
-            Box::new(handle_map),
-            Box::new(handle_movement),
-            Box::new(handle_place),
-            Box::new(handle_say),
-            Box::new(handle_scan),
-            Box::new(handle_take),
-            Box::new(handle_teleport),
-            Box::new(handle_time),
-            Box::new(handle_who),
-            Box::new(handle_yell),
-        ];
-
-        match handlers.iter().find_map(|handler| match handler(&content) {
+        let (entity, client, editor, mut queue) =
+            value_or_continue!(players.iter_mut().find(|(_, c, _, _)| c.id == message.from));
+
+        if let Some(mut editor) = editor {
+            if content.trim() == "." {
+                bevy.entity(entity).remove::<Editor>();
+
+                commands.send(ParsedCommand {
+                    from: client.id,
+                    command: Command::EditorDone(std::mem::take(&mut editor.lines)),
+                });
+            } else {
+                editor.lines.push(content);
+            }
+
+            continue;
+        }
+
+        // Several moves can arrive in one packet and a speedwalk expands into many, so all
+        // but the first step wait their turn in a queue. Anything else runs right away.
+        let mut steps = VecDeque::new();
+
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match parse_line(line) {
+                Ok(Command::Stop) => {
+                    // Stopping skips the queue and drops everything waiting in it, but
+                    // anything sent after it still runs.
+                    steps.clear();
+
+                    if let Some(queue) = queue.as_mut() {
+                        queue.commands.clear();
+                    }
+
+                    commands.send(ParsedCommand {
+                        from: client.id,
+                        command: Command::Stop,
+                    });
+                }
+                Ok(Command::Speedwalk(speedwalk)) => steps.extend(speedwalk),
+                Ok(command @ (Command::Movement(_) | Command::Enter(_))) => {
+                    steps.push_back(command);
+                }
+                Ok(command) => commands.send(ParsedCommand {
+                    from: client.id,
+                    command,
+                }),
+                Err(error) => outbox.send_text(client.id, error.to_string()),
+            }
+        }
+
+        match queue {
+            Some(mut queue) => queue.commands.extend(steps),
+            None => {
+                let Some(command) = steps.pop_front() else {
+                    continue;
+                };
+
+                commands.send(ParsedCommand {
+                    from: client.id,
+                    command,
+                });
+
+                if !steps.is_empty() {
+                    bevy.entity(entity).insert(CommandQueue::new(steps));
+                }
+            }
+        }
+    }
+}
+
+fn parse_line(content: &str) -> Result<Command, ParseError> {
+    let handlers: Vec<Box<dyn Fn(&str) -> Result<Command, ParseError>>> = vec![
+        Box::new(handle_announce),
+        Box::new(handle_ask),
+        Box::new(handle_attack),
+        Box::new(handle_chat),
+        Box::new(handle_close),
+        Box::new(handle_config),
+        Box::new(handle_describe),
+        Box::new(handle_dig),
+        Box::new(handle_drop),
+        Box::new(handle_emote),
+        Box::new(handle_enter),
+        Box::new(handle_events),
+        Box::new(handle_examine),
+        Box::new(handle_inventory),
+        Box::new(handle_lock),
+        Box::new(handle_look),
+        Box::new(handle_map),
+        Box::new(handle_movement),
+        Box::new(handle_oedit),
+        Box::new(handle_open),
+        Box::new(handle_path),
+        Box::new(handle_place),
+        Box::new(handle_quest),
+        Box::new(handle_quests),
+        Box::new(handle_read),
+        Box::new(handle_redit),
+        Box::new(handle_reload),
+        Box::new(handle_say),
+        Box::new(handle_scan),
+        Box::new(handle_speedwalk),
+        Box::new(handle_stop),
+        Box::new(handle_take),
+        Box::new(handle_talk),
+        Box::new(handle_teleport),
+        Box::new(handle_time),
+        Box::new(handle_travel),
+        Box::new(handle_unlock),
+        Box::new(handle_weather),
+        Box::new(handle_who),
+        Box::new(handle_write),
+        Box::new(handle_yell),
+        Box::new(handle_zsave),
+    ];
+
+    handlers
+        .iter()
+        .find_map(|handler| match handler(content) {
             Err(ParseError::WrongCommand) => None,
-            Ok(command) => Some(Ok(command)),
-            Err(err) => Some(Err(err)),
-        }) {
-            Some(Ok(command)) => commands.send(ParsedCommand {
+            result => Some(result),
+        })
+        .unwrap_or(Err(ParseError::UnknownCommand))
+}
+
+/// Sends the next queued command each step, until the queue runs dry or combat starts.
+pub fn run_command_queues(
+    mut bevy: Commands,
+    mut commands: EventWriter<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    mut players: Query<(Entity, &Client, &Character, &mut CommandQueue), With<Online>>,
+    time: Res<Time>,
+) {
+    for (player, client, character, mut queue) in players.iter_mut() {
+        if character.state.is_combat() {
+            bevy.entity(player).remove::<CommandQueue>();
+
+            outbox.send_text(client.id, "You abandon your queued commands.");
+
+            continue;
+        }
+
+        if !queue.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        if let Some(command) = queue.commands.pop_front() {
+            commands.send(ParsedCommand {
                 from: client.id,
                 command,
-            }),
-            Some(Err(error)) => outbox.send_text(client.id, error.to_string()),
-            None => outbox.send_text(client.id, ParseError::UnknownCommand.to_string()),
+            });
+        }
+
+        if queue.commands.is_empty() {
+            bevy.entity(player).remove::<CommandQueue>();
         }
     }
 }
@@ -0,0 +1,248 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    sync::OnceLock,
+};
+
+use bevy::prelude::*;
+use regex::Regex;
+
+use crate::visual::components::Depiction;
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// How many of the matching entities a target refers to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// No quantity was given, e.g. `sword`.
+    One,
+    /// A specific match, counting from 1, e.g. `2.sword`.
+    Nth(usize),
+    /// Up to this many matches, e.g. `3 sword`.
+    Count(usize),
+    /// Every match, e.g. `all.sword`, `all sword` or just `all`.
+    All,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub selection: Selection,
+    pub query: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TargetError {
+    NotFound,
+    Ambiguous(String),
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.query)
+    }
+}
+
+impl Target {
+    pub fn parse(content: &str) -> Self {
+        let regex = REGEX.get_or_init(|| {
+            Regex::new(r"^(?:(?P<all>all)(?:[. ]|$)|(?P<nth>\d+)\.|(?P<count>\d+) )?(?P<query>.*)$")
+                .unwrap()
+        });
+
+        let content = content.trim().to_lowercase();
+
+        let Some(captures) = regex.captures(&content) else {
+            return Self {
+                selection: Selection::One,
+                query: content,
+            };
+        };
+
+        let query = captures
+            .name("query")
+            .map(|m| m.as_str().trim().to_string())
+            .unwrap_or_default();
+
+        let selection = if captures.name("all").is_some() {
+            Selection::All
+        } else if let Some(nth) = captures.name("nth") {
+            Selection::Nth(nth.as_str().parse().unwrap_or(0))
+        } else if let Some(count) = captures.name("count") {
+            Selection::Count(count.as_str().parse().unwrap_or(0))
+        } else {
+            Selection::One
+        };
+
+        Self { selection, query }
+    }
+
+    /// A target that only ever matches the given entity.
+    pub fn entity(entity: Entity) -> Self {
+        Self {
+            selection: Selection::One,
+            query: format!("#{}", entity.index()),
+        }
+    }
+
+    /// Picks the entities this target refers to out of `candidates`, keeping their order.
+    ///
+    /// Exact matches on name, short name, tag or `#<index>` win over partial matches. When
+    /// no quantity was given and the matches don't all share a name, the player is asked
+    /// which one they meant instead of us guessing.
+    pub fn resolve(&self, candidates: &[(Entity, &Depiction)]) -> Result<Vec<Entity>, TargetError> {
+        let matches: Vec<&(Entity, &Depiction)> =
+            if self.query.is_empty() && self.selection == Selection::All {
+                candidates.iter().collect()
+            } else {
+                let exact = candidates
+                    .iter()
+                    .filter(|(entity, depiction)| depiction.matches_query(entity, &self.query))
+                    .collect::<Vec<_>>();
+
+                if exact.is_empty() {
+                    candidates
+                        .iter()
+                        .filter(|(_, depiction)| depiction.matches_partial(&self.query))
+                        .collect()
+                } else {
+                    exact
+                }
+            };
+
+        if matches.is_empty() {
+            return Err(TargetError::NotFound);
+        }
+
+        match self.selection {
+            Selection::One => {
+                let names = matches
+                    .iter()
+                    .map(|(_, depiction)| depiction.name.to_lowercase())
+                    .collect::<HashSet<_>>();
+
+                if names.len() > 1 {
+                    return Err(TargetError::Ambiguous(self.disambiguation(&matches)));
+                }
+
+                Ok(vec![matches[0].0])
+            }
+            Selection::Nth(nth) => nth
+                .checked_sub(1)
+                .and_then(|idx| matches.get(idx))
+                .map(|(entity, _)| vec![*entity])
+                .ok_or(TargetError::NotFound),
+            Selection::Count(count) => {
+                if count == 0 {
+                    return Err(TargetError::NotFound);
+                }
+
+                Ok(matches.iter().take(count).map(|(e, _)| *e).collect())
+            }
+            Selection::All => Ok(matches.iter().map(|(e, _)| *e).collect()),
+        }
+    }
+
+    fn disambiguation(&self, matches: &[&(Entity, &Depiction)]) -> String {
+        let options = matches
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, depiction))| format!("{}.{} ({})", idx + 1, self.query, depiction.name))
+            .collect::<Vec<_>>();
+
+        format!("Which one did you mean? {}", options.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depiction(name: &str, tags: Vec<&str>) -> Depiction {
+        Depiction {
+            name: name.into(),
+            short_name: name.into(),
+            description: "".into(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            visible: true,
+        }
+    }
+
+    #[test]
+    fn parses_selections() {
+        assert_eq!(Target::parse("sword").selection, Selection::One);
+        assert_eq!(Target::parse("2.sword").selection, Selection::Nth(2));
+        assert_eq!(Target::parse("3 sword").selection, Selection::Count(3));
+        assert_eq!(Target::parse("all.sword").selection, Selection::All);
+        assert_eq!(Target::parse("all sword").selection, Selection::All);
+        assert_eq!(Target::parse("all").selection, Selection::All);
+        assert_eq!(Target::parse("2.iron sword").query, "iron sword");
+        assert_eq!(Target::parse("allspice").selection, Selection::One);
+    }
+
+    #[test]
+    fn nth_match() {
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+
+        let iron = depiction("iron sword", vec!["sword"]);
+        let bronze = depiction("bronze sword", vec!["sword"]);
+
+        let candidates = vec![(first, &iron), (second, &bronze)];
+
+        assert_eq!(
+            Target::parse("2.sword").resolve(&candidates),
+            Ok(vec![second])
+        );
+        assert_eq!(
+            Target::parse("3.sword").resolve(&candidates),
+            Err(TargetError::NotFound)
+        );
+    }
+
+    #[test]
+    fn partial_match() {
+        let entity = Entity::from_raw(1);
+        let lamp = depiction("oil lamp", vec![]);
+
+        let candidates = vec![(entity, &lamp)];
+
+        assert_eq!(Target::parse("lam").resolve(&candidates), Ok(vec![entity]));
+        assert_eq!(Target::parse("oi").resolve(&candidates), Ok(vec![entity]));
+        assert_eq!(
+            Target::parse("amp").resolve(&candidates),
+            Err(TargetError::NotFound)
+        );
+    }
+
+    #[test]
+    fn ambiguous_match() {
+        let iron = depiction("iron sword", vec!["sword"]);
+        let bronze = depiction("bronze sword", vec!["sword"]);
+
+        let candidates = vec![(Entity::from_raw(1), &iron), (Entity::from_raw(2), &bronze)];
+
+        assert_eq!(
+            Target::parse("sword").resolve(&candidates),
+            Err(TargetError::Ambiguous(
+                "Which one did you mean? 1.sword (iron sword), 2.sword (bronze sword)".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn identical_matches_are_not_ambiguous() {
+        let first = Entity::from_raw(1);
+        let rock = depiction("rock", vec![]);
+
+        let candidates = vec![(first, &rock), (Entity::from_raw(2), &rock)];
+
+        assert_eq!(Target::parse("rock").resolve(&candidates), Ok(vec![first]));
+        assert_eq!(
+            Target::parse("all.rock")
+                .resolve(&candidates)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    items::components::{Inventory, Item},
+    npc::components::{Dialogue, Npc},
+    player::components::{Client, Online},
+    quest::components::Journal,
+    spatial::components::Tile,
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+use super::talk::{dialogue_context, enter_node, speak};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_ask(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX
+        .get_or_init(|| Regex::new(r"^ask( (?P<target>.+?) about (?P<topic>.+)| .*)?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let (Some(target), Some(topic)) = (captures.name("target"), captures.name("topic"))
+            else {
+                return Err(ParseError::InvalidArguments("Ask whom about what?".into()));
+            };
+
+            Ok(Command::Ask((
+                Target::parse(target.as_str()),
+                topic.as_str().trim().to_string(),
+            )))
+        }
+    }
+}
+
+pub fn ask(
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    items: Query<&Name, With<Item>>,
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    npcs: Query<(Entity, &Depiction, Option<&Dialogue>), With<Npc>>,
+    players: Query<(Entity, &Client, &Parent, &Children, Option<&Journal>), With<Online>>,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Ask((target, topic)) = &command.command {
+            let (player, client, tile, children, journal) =
+                value_or_continue!(players.iter().find(|(_, c, _, _, _)| c.id == command.from));
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+
+            let candidates = siblings
+                .iter()
+                .filter_map(|sibling| npcs.get(*sibling).ok())
+                .map(|(entity, depiction, _)| (entity, depiction))
+                .collect::<Vec<_>>();
+
+            let npc = match target.resolve(&candidates) {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (_, depiction, dialogue) = value_or_continue!(npcs.get(npc).ok());
+            let context = dialogue_context(children, journal, &inventories, &items);
+
+            let Some(node) = dialogue.and_then(|dialogue| dialogue.topic(topic, &context)) else {
+                outbox.send_text(
+                    client.id,
+                    format!("{} has nothing to say about {topic}.", depiction.name),
+                );
+
+                continue;
+            };
+
+            outbox.send_text(client.id, speak(depiction, node, &context));
+
+            enter_node(&mut bevy, player, npc, node, &context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        npc::components::DialogueNode,
+        test::{
+            app_builder::AppBuilder,
+            npc_builder::NpcBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    fn smith_dialogue() -> Dialogue {
+        Dialogue {
+            greetings: vec![],
+            nodes: vec![DialogueNode {
+                id: "swords".into(),
+                line: "The finest blades in Trinus.".into(),
+                keywords: vec!["swords".into(), "blades".into()],
+                conditions: vec![],
+                choices: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn known_topic() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(ask);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        NpcBuilder::new()
+            .name("Smith")
+            .short_name("smith")
+            .dialogue(smith_dialogue())
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "ask smith about Blades");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Smith says \"The finest blades in Trinus.\"");
+    }
+
+    #[test]
+    fn unknown_topic() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(ask);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        NpcBuilder::new()
+            .name("Smith")
+            .short_name("smith")
+            .dialogue(smith_dialogue())
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "ask smith about the weather");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Smith has nothing to say about the weather.");
+    }
+}
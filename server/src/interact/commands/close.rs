@@ -0,0 +1,151 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::{components::Openable, utils::find_target},
+    items::components::Inventory,
+    player::components::{Client, Online},
+    spatial::components::{Doorway, Tile},
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_close(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^close( (?P<target>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let target = captures
+                .name("target")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Close what?".into()))?;
+
+            Ok(Command::Close(Target::parse(target)))
+        }
+    }
+}
+
+pub fn close(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    mut openables: Query<(Entity, &Depiction, Option<&mut Openable>)>,
+    doorways: Query<(Entity, &Doorway)>,
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    players: Query<(&Client, &Parent, &Children), With<Online>>,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Close(target) = &command.command {
+            let (client, tile, children) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+            let inventory = value_or_continue!(children
+                .iter()
+                .find_map(|child| inventories.get(*child).ok()));
+
+            let found = find_target(
+                target,
+                tile.get(),
+                siblings,
+                inventory,
+                &doorways,
+                |entity| {
+                    openables
+                        .get(entity)
+                        .ok()
+                        .map(|(_, depiction, ..)| depiction)
+                },
+            );
+
+            let entity = match found {
+                Ok(entity) => entity,
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (_, depiction, openable) = value_or_continue!(openables.get_mut(entity).ok());
+
+            let Some(mut openable) = openable else {
+                outbox.send_text(
+                    client.id,
+                    format!("The {} can't be closed.", depiction.name),
+                );
+
+                continue;
+            };
+
+            if !openable.open {
+                outbox.send_text(
+                    client.id,
+                    format!("The {} is already closed.", depiction.name),
+                );
+
+                continue;
+            }
+
+            openable.open = false;
+
+            outbox.send_text(client.id, format!("You close the {}.", depiction.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn closes() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(close);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let chest = ItemBuilder::new()
+            .name("chest")
+            .openable(true)
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "close chest");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You close the chest.");
+        assert!(!app.world.get::<Openable>(chest).unwrap().open);
+    }
+}
@@ -5,7 +5,10 @@ use bevy_nest::prelude::*;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand, ProxyCommand},
+    input::{
+        events::{Command, ParseError, ParsedCommand, ProxyCommand},
+        target::{Target, TargetError},
+    },
     interact::components::{InMenu, Interaction, Interactions, MenuType},
     player::components::{Client, Online},
     spatial::components::Tile,
@@ -27,9 +30,7 @@ pub fn handle_examine(content: &str) -> Result<Command, ParseError> {
                 .name("option")
                 .and_then(|m| m.as_str().parse::<usize>().ok());
 
-            let target = captures
-                .name("target")
-                .map(|m| m.as_str().trim().to_lowercase());
+            let target = captures.name("target").map(|m| Target::parse(m.as_str()));
 
             if option.is_none() && target.is_none() {
                 return Err(ParseError::InvalidArguments("Examine what?".into()));
@@ -56,17 +57,29 @@ pub fn examine(
             let siblings = value_or_continue!(tiles.get(tile.get()).ok());
 
             if let Some(target) = target {
-                let found = siblings
+                let candidates = siblings
                     .iter()
                     .filter_map(|sibling| interactable.get(*sibling).ok())
-                    .find(|(e, d, _)| d.matches_query(e, target));
+                    .map(|(entity, depiction, _)| (entity, depiction))
+                    .collect::<Vec<_>>();
 
-                let Some((entity, depiction, interactions)) = found else {
-                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+                let found = match target.resolve(&candidates) {
+                    Ok(found) => found[0],
+                    Err(TargetError::NotFound) => {
+                        outbox.send_text(client.id, format!("You don't see a {target} here."));
 
-                    continue;
+                        continue;
+                    }
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
+
+                        continue;
+                    }
                 };
 
+                let (entity, depiction, interactions) =
+                    value_or_continue!(interactable.get(found).ok());
+
                 if let Some(interactions) = interactions {
                     let opts: Vec<String> = interactions
                         .0
@@ -101,7 +114,6 @@ pub fn examine(
                     continue;
                 };
 
-                #[allow(irrefutable_let_patterns)]
                 if let MenuType::Examine(entity) = menu.0 {
                     let (_, depiction, interactions) =
                         value_or_continue!(interactable.get(entity).ok());
@@ -116,7 +128,7 @@ pub fn examine(
                         match interaction {
                             Interaction::Take => proxy.send(ProxyCommand(ParsedCommand {
                                 from: client.id,
-                                command: Command::Take((depiction.name.clone(), false, None)),
+                                command: Command::Take((Target::entity(entity), None)),
                             })),
                             _ => debug!("Unhandled interaction: {:?}", interaction),
                         }
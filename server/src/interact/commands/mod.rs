@@ -1,3 +1,9 @@
+pub mod ask;
+pub mod close;
 pub mod examine;
+pub mod lock;
+pub mod open;
 pub mod place;
 pub mod take;
+pub mod talk;
+pub mod unlock;
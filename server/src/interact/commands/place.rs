@@ -5,8 +5,11 @@ use bevy_nest::prelude::*;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
-    interact::components::{Interaction, Interactions},
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::components::{Interaction, Interactions, Openable},
     items::components::{Inventory, Item, Surface},
     player::components::{Client, Online},
     spatial::components::Tile,
@@ -34,7 +37,10 @@ pub fn handle_place(content: &str) -> Result<Command, ParseError> {
                 .map(|m| m.as_str().trim())
                 .ok_or(ParseError::InvalidArguments("Place where?".into()))?;
 
-            Ok(Command::Place((object.into(), target.into())))
+            Ok(Command::Place((
+                Target::parse(object),
+                Target::parse(target),
+            )))
         }
     }
 }
@@ -54,6 +60,7 @@ pub fn place(
         Option<&Children>,
     )>,
     surfaces: Query<&Surface>,
+    openables: Query<&Openable>,
 ) {
     for command in commands.iter() {
         if let Command::Place((object, target)) = &command.command {
@@ -64,19 +71,30 @@ pub fn place(
                 .iter()
                 .find_map(|child| inventories.get(*child).ok()));
 
-            let Some((object, object_item, object_depiction, object_interactable, _)) = inventory
+            let carried = inventory
                 .iter()
                 .flat_map(|children| children.iter())
                 .filter_map(|child| items.get(*child).ok())
-                .find(|(e, _, d, _, _)| d.matches_query(e, object)) else {
-                outbox.send_text(
-                    client.id,
-                    format!("You don't have a {object}."),
-                );
+                .map(|(entity, _, depiction, _, _)| (entity, depiction))
+                .collect::<Vec<_>>();
 
-                continue;
+            let object = match object.resolve(&carried) {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't have a {object}."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
             };
 
+            let (object, object_item, object_depiction, object_interactable, _) =
+                value_or_continue!(items.get(object).ok());
+
             if !object_interactable.map_or(false, |i| i.0.contains(&Interaction::Place)) {
                 outbox.send_text(
                     client.id,
@@ -86,26 +104,49 @@ pub fn place(
                 continue;
             }
 
-            let Some((target, _, target_depiction, _, target_children)) = siblings
+            let nearby = siblings
                 .iter()
                 .filter_map(|child| items.get(*child).ok())
-                .find(|(e, _,d,  _, _)| d.matches_query(e, target)) else {
+                .map(|(entity, _, depiction, _, _)| (entity, depiction))
+                .collect::<Vec<_>>();
+
+            let target = match target.resolve(&nearby) {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (target, _, target_depiction, _, target_children) =
+                value_or_continue!(items.get(target).ok());
+
+            let Ok(surface) = surfaces.get(target) else {
                 outbox.send_text(
                     client.id,
-                    format!("You don't see a {target} here."),
+                    format!(
+                        "You can't place the {} on the {}.",
+                        object_depiction.name, target_depiction.name
+                    ),
                 );
 
                 continue;
             };
 
-            let Ok(surface) = surfaces.get(target) else {
+            if openables.get(target).is_ok_and(|openable| !openable.open) {
                 outbox.send_text(
                     client.id,
-                    format!("You can't place the {} on the {}.", object_depiction.name, target_depiction.name),
+                    format!("The {} is closed.", target_depiction.name),
                 );
 
                 continue;
-            };
+            }
 
             if target_children.map_or(false, |children| {
                 children
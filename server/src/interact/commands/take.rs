@@ -6,8 +6,11 @@ use indefinite::indefinite;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
-    interact::components::{Interaction, Interactions},
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::components::{Interaction, Interactions, Openable},
     items::components::{Inventory, Item, Surface},
     player::components::{Client, Online},
     spatial::components::Tile,
@@ -19,8 +22,7 @@ static REGEX: OnceLock<Regex> = OnceLock::new();
 
 pub fn handle_take(content: &str) -> Result<Command, ParseError> {
     let regex = REGEX.get_or_init(|| {
-        Regex::new(r"^(take|get)( (?P<all>all))?( (?P<target>.*?))?( (from))?( (?P<source>.*))?$")
-            .unwrap()
+        Regex::new(r"^(take|get)( (?P<target>.*?))?( from (?P<source>.*))?$").unwrap()
     });
 
     match regex.captures(content) {
@@ -29,15 +31,12 @@ pub fn handle_take(content: &str) -> Result<Command, ParseError> {
             let target = captures
                 .name("target")
                 .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
                 .ok_or(ParseError::InvalidArguments("Take what?".into()))?;
 
-            let all = captures.name("all").is_some();
-
-            let source = captures
-                .name("source")
-                .map(|m| m.as_str().trim().to_lowercase());
+            let source = captures.name("source").map(|m| Target::parse(m.as_str()));
 
-            Ok(Command::Take((target.into(), all, source)))
+            Ok(Command::Take((Target::parse(target), source)))
         }
     }
 }
@@ -51,9 +50,10 @@ pub fn take(
     tiles: Query<&Children, With<Tile>>,
     items: Query<(Entity, &Depiction, Option<&Interactions>, Option<&Children>), With<Item>>,
     surfaces: Query<&Surface>,
+    openables: Query<&Openable>,
 ) {
     for command in commands.iter() {
-        if let Command::Take((target, all, source)) = &command.command {
+        if let Command::Take((target, source)) = &command.command {
             let (client, tile, children) =
                 value_or_continue!(players.iter_mut().find(|(c, _, _)| c.id == command.from));
             let siblings = value_or_continue!(tiles.get(tile.get()).ok());
@@ -62,46 +62,82 @@ pub fn take(
                 .find_map(|child| inventories.get(*child).ok()));
 
             let to_search = if let Some(source) = source {
-                siblings
+                let containers = siblings
                     .iter()
+                    .filter(|sibling| surfaces.get(**sibling).is_ok())
                     .filter_map(|sibling| items.get(*sibling).ok())
-                    .find(|(sibling, depiction, _, _)| {
-                        surfaces.get(*sibling).is_ok() && depiction.matches_query(sibling, source)
-                    })
+                    .map(|(entity, depiction, _, _)| (entity, depiction))
+                    .collect::<Vec<_>>();
+
+                let container = match source.resolve(&containers) {
+                    Ok(found) => found[0],
+                    Err(TargetError::NotFound) => {
+                        outbox.send_text(
+                            client.id,
+                            format!("You don't see {} here.", indefinite(&source.query)),
+                        );
+
+                        continue;
+                    }
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
+
+                        continue;
+                    }
+                };
+
+                if openables
+                    .get(container)
+                    .is_ok_and(|openable| !openable.open)
+                {
+                    let (_, depiction, _, _) = value_or_continue!(items.get(container).ok());
+
+                    outbox.send_text(client.id, format!("The {} is closed.", depiction.name));
+
+                    continue;
+                }
+
+                items
+                    .get(container)
+                    .ok()
                     .and_then(|(_, _, _, children)| children)
-                    .map(|children| {
-                        children
-                            .iter()
-                            .filter_map(|child| items.get(*child).ok())
-                            .collect()
-                    })
-                    .unwrap_or_else(Vec::new)
+                    .map(|children| children.iter().copied().collect())
+                    .unwrap_or_default()
             } else {
-                siblings
-                    .iter()
-                    .filter_map(|sibling| items.get(*sibling).ok())
-                    .collect()
+                siblings.iter().copied().collect::<Vec<_>>()
             };
 
-            let mut items_found = to_search
+            let candidates = to_search
                 .iter()
-                .filter(|(entity, depiction, _, _)| depiction.matches_query(entity, target))
+                .filter_map(|entity| items.get(*entity).ok())
+                .map(|(entity, depiction, _, _)| (entity, depiction))
                 .collect::<Vec<_>>();
 
-            if items_found.is_empty() {
-                let target = if let Some(source) = source {
-                    source
-                } else {
-                    target
-                };
-
-                outbox.send_text(
-                    client.id,
-                    format!("You don't see {} here.", indefinite(target)),
-                );
-
-                continue;
-            }
+            let items_found = match target.resolve(&candidates) {
+                Ok(found) => found
+                    .iter()
+                    .filter_map(|entity| items.get(*entity).ok())
+                    .collect::<Vec<_>>(),
+                Err(TargetError::NotFound) => {
+                    let target = if let Some(source) = source {
+                        &source.query
+                    } else {
+                        &target.query
+                    };
+
+                    outbox.send_text(
+                        client.id,
+                        format!("You don't see {} here.", indefinite(target)),
+                    );
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
 
             if items_found.iter().any(|(_, _, interactable, _)| {
                 interactable.map_or(true, |i| !i.0.contains(&Interaction::Take))
@@ -111,10 +147,6 @@ pub fn take(
                 continue;
             }
 
-            if !*all {
-                items_found.truncate(1);
-            }
-
             items_found.iter().for_each(|(entity, _, _, _)| {
                 bevy.entity(*entity).set_parent(inventory);
             });
@@ -307,6 +339,87 @@ mod tests {
             .contains(&plate),);
     }
 
+    #[test]
+    fn by_ordinal() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(take);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        ItemBuilder::new()
+            .name("iron sword")
+            .tags(vec!["sword"])
+            .interactions(vec![Interaction::Take])
+            .tile(tile)
+            .build(&mut app);
+
+        let bronze = ItemBuilder::new()
+            .name("bronze sword")
+            .tags(vec!["sword"])
+            .interactions(vec![Interaction::Take])
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "take 2.sword");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You take a bronze sword.");
+
+        assert!(app
+            .world
+            .get::<Children>(inventory.unwrap())
+            .unwrap()
+            .contains(&bronze));
+    }
+
+    #[test]
+    fn ambiguous() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(take);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        ItemBuilder::new()
+            .name("iron sword")
+            .tags(vec!["sword"])
+            .interactions(vec![Interaction::Take])
+            .tile(tile)
+            .build(&mut app);
+
+        ItemBuilder::new()
+            .name("bronze sword")
+            .tags(vec!["sword"])
+            .interactions(vec![Interaction::Take])
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "take sword");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "Which one did you mean? 1.sword (iron sword), 2.sword (bronze sword)"
+        );
+
+        assert!(app.world.get::<Children>(inventory.unwrap()).is_none());
+    }
+
     #[test]
     fn not_found() {
         let mut app = AppBuilder::new().build();
@@ -0,0 +1,444 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::components::{InMenu, MenuType},
+    items::components::{Inventory, Item},
+    npc::components::{Dialogue, DialogueContext, DialogueNode, Npc},
+    paint,
+    player::components::{Client, Online},
+    quest::{components::Journal, events::QuestEvent},
+    spatial::components::Tile,
+    utils::prototype_id,
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_talk(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"^talk(?: do (?P<option>\d+))?(?: (?:to )?(?P<target>.*))?$").unwrap()
+    });
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let option = captures
+                .name("option")
+                .and_then(|m| m.as_str().parse::<usize>().ok());
+
+            let target = captures
+                .name("target")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .map(Target::parse);
+
+            if option.is_none() && target.is_none() {
+                return Err(ParseError::InvalidArguments("Talk to whom?".into()));
+            }
+
+            Ok(Command::Talk((target, option)))
+        }
+    }
+}
+
+pub fn talk(
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    items: Query<&Name, With<Item>>,
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    mut quest_events: EventWriter<QuestEvent>,
+    npcs: Query<(Entity, &Depiction, Option<&Dialogue>), With<Npc>>,
+    players: Query<
+        (
+            Entity,
+            &Client,
+            &Parent,
+            &Children,
+            Option<&InMenu>,
+            Option<&Journal>,
+        ),
+        With<Online>,
+    >,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Talk((target, option)) = &command.command {
+            let (player, client, tile, children, in_menu, journal) = value_or_continue!(players
+                .iter()
+                .find(|(_, c, _, _, _, _)| c.id == command.from));
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+            let context = dialogue_context(children, journal, &inventories, &items);
+
+            if let Some(target) = target {
+                let candidates = siblings
+                    .iter()
+                    .filter_map(|sibling| npcs.get(*sibling).ok())
+                    .map(|(entity, depiction, _)| (entity, depiction))
+                    .collect::<Vec<_>>();
+
+                let npc = match target.resolve(&candidates) {
+                    Ok(found) => found[0],
+                    Err(TargetError::NotFound) => {
+                        outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                        continue;
+                    }
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
+
+                        continue;
+                    }
+                };
+
+                let (_, depiction, dialogue) = value_or_continue!(npcs.get(npc).ok());
+
+                quest_events.send(QuestEvent::TalkedTo { player, npc });
+
+                let Some(node) = dialogue.and_then(|dialogue| dialogue.greeting(&context)) else {
+                    outbox.send_text(
+                        client.id,
+                        format!("{} has nothing to say to you.", depiction.name),
+                    );
+
+                    continue;
+                };
+
+                outbox.send_text(client.id, speak(depiction, node, &context));
+
+                enter_node(&mut bevy, player, npc, node, &context);
+            }
+
+            if let Some(option) = option {
+                let Some(InMenu(MenuType::Dialogue(npc, node_id))) = in_menu else {
+                    outbox.send_text(client.id, "You are not talking to anyone.");
+
+                    continue;
+                };
+
+                let Some((_, depiction, Some(dialogue))) = npcs
+                    .get(*npc)
+                    .ok()
+                    .filter(|(entity, _, _)| siblings.contains(entity))
+                else {
+                    outbox.send_text(client.id, "You are not talking to anyone.");
+
+                    bevy.entity(player).remove::<InMenu>();
+
+                    continue;
+                };
+
+                let node = value_or_continue!(dialogue.node(node_id));
+
+                let Some(choice) = option
+                    .checked_sub(1)
+                    .and_then(|idx| node.choices(&context).nth(idx))
+                else {
+                    outbox.send_text(client.id, "That is not an option.");
+
+                    continue;
+                };
+
+                let said = format!("You say \"{}\"", choice.text);
+
+                if let Some(quest) = &choice.quest {
+                    quest_events.send(QuestEvent::Accepted {
+                        player,
+                        quest: quest.clone(),
+                    });
+                }
+
+                match choice.next.as_ref().and_then(|next| dialogue.node(next)) {
+                    Some(next) => {
+                        outbox.send_text(
+                            client.id,
+                            format!("{said}\n\n{}", speak(depiction, next, &context)),
+                        );
+
+                        enter_node(&mut bevy, player, *npc, next, &context);
+                    }
+                    None => {
+                        outbox.send_text(client.id, said);
+
+                        bevy.entity(player).remove::<InMenu>();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Gathers what the player has that dialogue conditions can depend on.
+pub fn dialogue_context(
+    children: &Children,
+    journal: Option<&Journal>,
+    inventories: &Query<Option<&Children>, With<Inventory>>,
+    items: &Query<&Name, With<Item>>,
+) -> DialogueContext {
+    let items = children
+        .iter()
+        .filter_map(|child| inventories.get(*child).ok())
+        .flatten()
+        .flat_map(|inventory| inventory.iter())
+        .filter_map(|item| items.get(*item).ok())
+        .map(|name| prototype_id(name).to_string())
+        .collect();
+
+    let quests = |completed: bool| {
+        journal
+            .iter()
+            .flat_map(|journal| journal.0.iter())
+            .filter(|progress| progress.completed == completed)
+            .map(|progress| progress.quest.clone())
+            .collect()
+    };
+
+    DialogueContext {
+        items,
+        active_quests: quests(false),
+        completed_quests: quests(true),
+    }
+}
+
+/// The NPC's line followed by the numbered choices the player can pick from.
+pub fn speak(depiction: &Depiction, node: &DialogueNode, context: &DialogueContext) -> String {
+    let choices = node
+        .choices(context)
+        .enumerate()
+        .map(|(idx, choice)| format!("[{}] {}", idx + 1, choice.text))
+        .collect::<Vec<_>>();
+
+    let line = paint!("<fg.npc>{}</> says \"{}\"", depiction.name, node.line);
+
+    if choices.is_empty() {
+        line
+    } else {
+        format!("{line}\n\n{}", choices.join("\n"))
+    }
+}
+
+/// Keeps the player in the dialogue menu while the node has choices left to pick.
+pub fn enter_node(
+    bevy: &mut Commands,
+    player: Entity,
+    npc: Entity,
+    node: &DialogueNode,
+    context: &DialogueContext,
+) {
+    if node.choices(context).next().is_some() {
+        bevy.entity(player)
+            .insert(InMenu(MenuType::Dialogue(npc, node.id.clone())));
+    } else {
+        bevy.entity(player).remove::<InMenu>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        npc::components::{DialogueChoice, DialogueCondition},
+        test::{
+            app_builder::AppBuilder,
+            item_builder::ItemBuilder,
+            npc_builder::NpcBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    fn node(id: &str, line: &str, choices: Vec<DialogueChoice>) -> DialogueNode {
+        DialogueNode {
+            id: id.into(),
+            line: line.into(),
+            keywords: vec![],
+            conditions: vec![],
+            choices,
+        }
+    }
+
+    fn choice(text: &str, next: Option<&str>) -> DialogueChoice {
+        DialogueChoice {
+            text: text.into(),
+            next: next.map(|next| next.into()),
+            conditions: vec![],
+            quest: None,
+        }
+    }
+
+    fn guard_dialogue() -> Dialogue {
+        let mut ring = node("ring", "Is that my ring?", vec![]);
+        ring.conditions = vec![DialogueCondition::HasItem("items.ring".into())];
+
+        Dialogue {
+            greetings: vec!["ring".into(), "halt".into()],
+            nodes: vec![
+                ring,
+                node(
+                    "halt",
+                    "Halt!",
+                    vec![
+                        choice("Who are you?", Some("who")),
+                        choice("Goodbye.", None),
+                    ],
+                ),
+                node("who", "I guard the gate.", vec![]),
+            ],
+        }
+    }
+
+    #[test]
+    fn greets_with_choices() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(talk);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        NpcBuilder::new()
+            .name("Guard")
+            .short_name("guard")
+            .dialogue(guard_dialogue())
+            .tile(tile)
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "talk guard");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "Guard says \"Halt!\"\n\n[1] Who are you?\n[2] Goodbye."
+        );
+        assert!(app.world.get::<InMenu>(player).is_some());
+    }
+
+    #[test]
+    fn picks_choice() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(talk);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        NpcBuilder::new()
+            .name("Guard")
+            .short_name("guard")
+            .dialogue(guard_dialogue())
+            .tile(tile)
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "talk guard");
+        app.update();
+
+        send_message(&mut app, client_id, "talk do 1");
+        app.update();
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "You say \"Who are you?\"\n\nGuard says \"I guard the gate.\""
+        );
+        assert!(app.world.get::<InMenu>(player).is_none());
+    }
+
+    #[test]
+    fn greeting_condition() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(talk);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        NpcBuilder::new()
+            .name("Guard")
+            .short_name("guard")
+            .dialogue(guard_dialogue())
+            .tile(tile)
+            .build(&mut app);
+
+        let ring = ItemBuilder::new().prototype("items.ring").build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(ring);
+
+        send_message(&mut app, client_id, "talk guard");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Guard says \"Is that my ring?\"");
+    }
+
+    #[test]
+    fn offers_quest() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(talk);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let mut accept = choice("I'll deal with them.", None);
+        accept.quest = Some("quests.rats".into());
+        accept.conditions = vec![DialogueCondition::QuestNotStarted("quests.rats".into())];
+
+        NpcBuilder::new()
+            .name("Innkeeper")
+            .short_name("innkeeper")
+            .dialogue(Dialogue {
+                greetings: vec!["rats".into()],
+                nodes: vec![node("rats", "The cellar is full of rats!", vec![accept])],
+            })
+            .tile(tile)
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "talk innkeeper");
+        app.update();
+
+        send_message(&mut app, client_id, "talk do 1");
+        app.update();
+
+        let accepted = app
+            .world
+            .resource_mut::<Events<QuestEvent>>()
+            .drain()
+            .any(|event| {
+                matches!(event, QuestEvent::Accepted { player: p, quest } if p == player && quest == "quests.rats")
+            });
+
+        assert!(accepted);
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::{components::Lock, utils::find_target},
+    items::components::{Inventory, Item},
+    player::components::{Client, Online},
+    spatial::components::{Doorway, Tile},
+    utils::prototype_id,
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_unlock(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^unlock( (?P<target>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let target = captures
+                .name("target")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Unlock what?".into()))?;
+
+            Ok(Command::Unlock(Target::parse(target)))
+        }
+    }
+}
+
+pub fn unlock(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    mut lockables: Query<(Entity, &Depiction, Option<&mut Lock>)>,
+    doorways: Query<(Entity, &Doorway)>,
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    keys: Query<&Name, With<Item>>,
+    players: Query<(&Client, &Parent, &Children), With<Online>>,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Unlock(target) = &command.command {
+            let (client, tile, children) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+            let inventory = value_or_continue!(children
+                .iter()
+                .find_map(|child| inventories.get(*child).ok()));
+
+            let found = find_target(
+                target,
+                tile.get(),
+                siblings,
+                inventory,
+                &doorways,
+                |entity| {
+                    lockables
+                        .get(entity)
+                        .ok()
+                        .map(|(_, depiction, ..)| depiction)
+                },
+            );
+
+            let entity = match found {
+                Ok(entity) => entity,
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (_, depiction, lock) = value_or_continue!(lockables.get_mut(entity).ok());
+
+            let Some(mut lock) = lock else {
+                outbox.send_text(client.id, format!("The {} has no lock.", depiction.name));
+
+                continue;
+            };
+
+            if !lock.locked {
+                outbox.send_text(
+                    client.id,
+                    format!("The {} is already unlocked.", depiction.name),
+                );
+
+                continue;
+            }
+
+            let has_key = inventory
+                .iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|child| keys.get(*child).ok())
+                .any(|name| prototype_id(name) == lock.key);
+
+            if !has_key {
+                outbox.send_text(
+                    client.id,
+                    format!("You don't have the key to the {}.", depiction.name),
+                );
+
+                continue;
+            }
+
+            lock.locked = false;
+
+            outbox.send_text(client.id, format!("You unlock the {}.", depiction.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn with_key() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(unlock);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let chest = ItemBuilder::new()
+            .name("chest")
+            .lock("items.bronze-key", true)
+            .tile(tile)
+            .build(&mut app);
+
+        let key = ItemBuilder::new()
+            .name("bronze key")
+            .prototype("items.bronze-key")
+            .build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(key);
+
+        send_message(&mut app, client_id, "unlock chest");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You unlock the chest.");
+        assert!(!app.world.get::<Lock>(chest).unwrap().locked);
+    }
+
+    #[test]
+    fn without_key() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(unlock);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let chest = ItemBuilder::new()
+            .name("chest")
+            .lock("items.bronze-key", true)
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "unlock chest");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You don't have the key to the chest.");
+        assert!(app.world.get::<Lock>(chest).unwrap().locked);
+    }
+
+    #[test]
+    fn no_lock() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(unlock);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        ItemBuilder::new().name("crate").tile(tile).build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "unlock crate");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The crate has no lock.");
+    }
+}
@@ -34,9 +34,49 @@ impl Interaction {
     }
 }
 
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Lock {
+    /// Prototype name of the key that fits this lock.
+    pub key: String,
+    pub locked: bool,
+    pub difficulty: u8,
+}
+
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Openable {
+    pub open: bool,
+}
+
+/// Added to a zone to decide when its locks go back to how they were designed.
+/// Zones without one keep whatever state players leave their locks in.
+#[derive(Clone, Copy, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub enum LockReset {
+    /// Lock state is saved and restored across restarts.
+    Never,
+    /// Lock state is not saved, so a restart resets it.
+    Restart,
+    /// Locks reset this many seconds after being changed.
+    After(f32),
+}
+
+/// The state a lock was spawned with.
+#[derive(Component)]
+pub struct LockDefaults {
+    pub locked: bool,
+    pub open: Option<bool>,
+}
+
+#[derive(Component)]
+pub struct LockResetTimer(pub Timer);
+
 #[derive(Component)]
 pub struct InMenu(pub MenuType);
 
 pub enum MenuType {
     Examine(Entity),
+    /// Talking to an NPC, at the dialogue node with this id.
+    Dialogue(Entity, String),
 }
@@ -2,3 +2,4 @@ pub mod commands;
 pub mod components;
 pub mod plugin;
 pub mod systems;
+pub mod utils;
@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 
+use crate::world::resources::WorldStateLoaded;
+
 use super::{
-    commands::{examine::*, place::*, take::*},
+    commands::{
+        ask::*, close::*, examine::*, lock::*, open::*, place::*, take::*, talk::*, unlock::*,
+    },
     components::*,
     systems::*,
 };
@@ -12,8 +16,18 @@ impl Plugin for InteractPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Interaction>()
             .register_type::<Vec<Interaction>>()
-            .register_type::<Interactions>();
+            .register_type::<Interactions>()
+            .register_type::<Lock>()
+            .register_type::<LockReset>()
+            .register_type::<Openable>();
 
         app.add_systems((examine, take, place, remove_menu_if_changed_tiles));
+        app.add_systems((close, lock, open, unlock));
+        app.add_systems((talk, ask));
+        app.add_systems((
+            restore_locks.run_if(resource_exists::<WorldStateLoaded>()),
+            start_lock_reset_timers,
+            reset_locks,
+        ));
     }
 }
@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 
-use super::components::InMenu;
+use crate::{
+    spatial::components::{Tile, Zone},
+    world::resources::WorldState,
+};
+
+use super::components::{InMenu, Lock, LockDefaults, LockReset, LockResetTimer, Openable};
 
 pub fn remove_menu_if_changed_tiles(
     mut commands: Commands,
@@ -10,3 +15,158 @@ pub fn remove_menu_if_changed_tiles(
         commands.entity(entity).remove::<InMenu>();
     }
 }
+
+pub fn restore_locks(
+    mut bevy: Commands,
+    mut locks: Query<
+        (
+            Entity,
+            Option<&Name>,
+            &mut Lock,
+            Option<&mut Openable>,
+            Option<&Parent>,
+        ),
+        Without<LockDefaults>,
+    >,
+    tiles: Query<(&Name, &Parent), With<Tile>>,
+    zones: Query<Option<&LockReset>, With<Zone>>,
+    world_state: Res<WorldState>,
+) {
+    for (entity, name, mut lock, openable, parent) in locks.iter_mut() {
+        bevy.entity(entity).insert(LockDefaults {
+            locked: lock.locked,
+            open: openable.as_ref().map(|openable| openable.open),
+        });
+
+        let Some(name) = name else {
+            continue;
+        };
+
+        let Some((tile, zone)) = parent.and_then(|parent| tiles.get(parent.get()).ok()) else {
+            continue;
+        };
+
+        if let Ok(Some(LockReset::Restart)) = zones.get(zone.get()) {
+            continue;
+        }
+
+        let Some(saved) = world_state
+            .locks
+            .iter()
+            .find(|saved| saved.tile == tile.as_str() && saved.name == name.as_str())
+        else {
+            continue;
+        };
+
+        lock.locked = saved.locked;
+
+        if let (Some(mut openable), Some(open)) = (openable, saved.open) {
+            openable.open = open;
+        }
+    }
+}
+
+pub fn start_lock_reset_timers(
+    mut bevy: Commands,
+    locks: Query<
+        (Entity, &Lock, Option<&Openable>, &LockDefaults, &Parent),
+        (
+            Without<LockResetTimer>,
+            Or<(Changed<Lock>, Changed<Openable>)>,
+        ),
+    >,
+    tiles: Query<&Parent, With<Tile>>,
+    zones: Query<&LockReset, With<Zone>>,
+) {
+    for (entity, lock, openable, defaults, parent) in locks.iter() {
+        if lock.locked == defaults.locked && openable.map(|o| o.open) == defaults.open {
+            continue;
+        }
+
+        let Ok(zone) = tiles.get(parent.get()) else {
+            continue;
+        };
+
+        if let Ok(LockReset::After(seconds)) = zones.get(zone.get()) {
+            bevy.entity(entity)
+                .insert(LockResetTimer(Timer::from_seconds(
+                    *seconds,
+                    TimerMode::Once,
+                )));
+        }
+    }
+}
+
+pub fn reset_locks(
+    mut bevy: Commands,
+    mut locks: Query<(
+        Entity,
+        &mut Lock,
+        Option<&mut Openable>,
+        &LockDefaults,
+        &mut LockResetTimer,
+    )>,
+    time: Res<Time>,
+) {
+    for (entity, mut lock, openable, defaults, mut timer) in locks.iter_mut() {
+        if timer.0.tick(time.delta()).just_finished() {
+            lock.locked = defaults.locked;
+
+            if let (Some(mut openable), Some(open)) = (openable, defaults.open) {
+                openable.open = open;
+            }
+
+            bevy.entity(entity).remove::<LockResetTimer>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test::{
+            app_builder::AppBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+        },
+        world::resources::WorldStateLock,
+    };
+
+    use super::*;
+
+    #[test]
+    fn restores_saved_lock_state() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(restore_locks);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, zone);
+        let destination = TileBuilder::new().build(&mut app, zone);
+
+        app.world.entity_mut(start).insert(Name::new("gate"));
+
+        let transition =
+            TransitionBuilder::new()
+                .lock("key", true)
+                .build(&mut app, start, destination);
+
+        app.world
+            .entity_mut(transition)
+            .insert(Name::new("gate door"));
+
+        app.world
+            .resource_mut::<WorldState>()
+            .locks
+            .push(WorldStateLock {
+                tile: "gate".into(),
+                name: "gate door".into(),
+                locked: false,
+                open: None,
+            });
+
+        app.update();
+
+        assert!(!app.world.get::<Lock>(transition).unwrap().locked);
+        assert!(app.world.get::<LockDefaults>(transition).unwrap().locked);
+    }
+}
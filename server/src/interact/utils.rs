@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::{
+    input::target::{Target, TargetError},
+    spatial::components::Doorway,
+    visual::components::Depiction,
+};
+
+/// Finds what a player means by `target` among what's on their tile, what they're carrying
+/// and the doorways leading into their tile. `depict` picks out the entities a command can
+/// act on, e.g. only ones that could have a lock.
+pub fn find_target<'a>(
+    target: &Target,
+    tile: Entity,
+    siblings: &Children,
+    inventory: Option<&Children>,
+    doorways: &Query<(Entity, &Doorway)>,
+    depict: impl Fn(Entity) -> Option<&'a Depiction>,
+) -> Result<Entity, TargetError> {
+    let candidates = siblings
+        .iter()
+        .chain(inventory.iter().flat_map(|children| children.iter()))
+        .copied()
+        .chain(
+            doorways
+                .iter()
+                .filter(|(_, doorway)| doorway.to == tile)
+                .map(|(entity, _)| entity),
+        )
+        .filter_map(|entity| depict(entity).map(|depiction| (entity, depiction)))
+        .collect::<Vec<_>>();
+
+    target.resolve(&candidates).map(|found| found[0])
+}
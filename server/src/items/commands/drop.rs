@@ -5,7 +5,10 @@ use bevy_nest::prelude::*;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
     items::components::{Inventory, Item},
     player::components::{Client, Online},
     spatial::components::Tile,
@@ -16,8 +19,7 @@ use crate::{
 static REGEX: OnceLock<Regex> = OnceLock::new();
 
 pub fn handle_drop(content: &str) -> Result<Command, ParseError> {
-    let regex =
-        REGEX.get_or_init(|| Regex::new(r"^drop( (?P<all>all))?( (?P<target>.*))?$").unwrap());
+    let regex = REGEX.get_or_init(|| Regex::new(r"^drop( (?P<target>.*))?$").unwrap());
 
     match regex.captures(content) {
         None => Err(ParseError::WrongCommand),
@@ -25,11 +27,10 @@ pub fn handle_drop(content: &str) -> Result<Command, ParseError> {
             let target = captures
                 .name("target")
                 .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
                 .ok_or(ParseError::InvalidArguments("Drop what?".into()))?;
 
-            let all = captures.name("all").is_some();
-
-            Ok(Command::Drop((target.to_string(), all)))
+            Ok(Command::Drop(Target::parse(target)))
         }
     }
 }
@@ -44,7 +45,7 @@ pub fn drop(
     items: Query<(Entity, &Depiction), With<Item>>,
 ) {
     for command in commands.iter() {
-        if let Command::Drop((target, all)) = &command.command {
+        if let Command::Drop(target) = &command.command {
             let (client, tile, children) =
                 value_or_continue!(players.iter_mut().find(|(c, _, _)| c.id == command.from));
             let tile = value_or_continue!(tiles.get(tile.get()).ok());
@@ -52,35 +53,41 @@ pub fn drop(
                 .iter()
                 .find_map(|child| inventories.get(*child).ok()));
 
-            let mut items_found = items_in_inventory
+            let candidates = items_in_inventory
                 .iter()
                 .flat_map(|children| children.iter())
-                .filter_map(|sibling| items.get(*sibling).ok())
-                .filter(|(e, d)| d.matches_query(e, target))
+                .filter_map(|child| items.get(*child).ok())
                 .collect::<Vec<(Entity, &Depiction)>>();
 
-            if !*all {
-                items_found.truncate(1);
-            }
+            let items_found = match target.resolve(&candidates) {
+                Ok(found) => found,
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't have a {target}."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
 
-            items_found.iter().for_each(|(entity, _)| {
+                    continue;
+                }
+            };
+
+            items_found.iter().for_each(|entity| {
                 bevy.entity(*entity).set_parent(tile);
             });
 
             let item_names = name_list(
                 &items_found
                     .iter()
+                    .filter_map(|entity| items.get(*entity).ok())
                     .map(|(_, item)| item.name.clone())
                     .collect::<Vec<String>>(),
                 None,
                 true,
             );
 
-            if item_names.is_empty() {
-                outbox.send_text(client.id, format!("You don't have a {target}."));
-            } else {
-                outbox.send_text(client.id, format!("You drop {item_names}."));
-            }
+            outbox.send_text(client.id, format!("You drop {item_names}."));
         }
     }
 }
@@ -215,6 +222,36 @@ mod tests {
             .contains(&rock),);
     }
 
+    #[test]
+    fn quantity() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(drop);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        for _ in 0..3 {
+            let rock = ItemBuilder::new().name("rock").build(&mut app);
+            app.world.entity_mut(inventory.unwrap()).add_child(rock);
+        }
+
+        send_message(&mut app, client_id, "drop 2 rock");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You drop 2 rocks.");
+        assert_eq!(
+            app.world.get::<Children>(inventory.unwrap()).unwrap().len(),
+            1
+        );
+    }
+
     #[test]
     fn not_found() {
         let mut app = AppBuilder::new().build();
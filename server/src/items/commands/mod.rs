@@ -1,2 +1,4 @@
 pub mod drop;
 pub mod inventory;
+pub mod read;
+pub mod write;
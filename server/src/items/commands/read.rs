@@ -0,0 +1,227 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    items::components::{Inventory, Item, Readable, WrittenPages},
+    paint,
+    player::components::{Client, Online},
+    spatial::components::Tile,
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_read(content: &str) -> Result<Command, ParseError> {
+    let regex =
+        REGEX.get_or_init(|| Regex::new(r"^read( (?P<target>.+?))?( (?P<page>\d+))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let target = captures
+                .name("target")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Read what?".into()))?;
+
+            let page = captures
+                .name("page")
+                .and_then(|m| m.as_str().parse::<usize>().ok());
+
+            Ok(Command::Read((Target::parse(target), page)))
+        }
+    }
+}
+
+pub fn read(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    items: Query<(Entity, &Depiction, Option<&Readable>, Option<&WrittenPages>), With<Item>>,
+    players: Query<(&Client, &Parent, &Children), With<Online>>,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Read((target, page)) = &command.command {
+            let (client, tile, children) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+            let inventory = value_or_continue!(children
+                .iter()
+                .find_map(|child| inventories.get(*child).ok()));
+
+            let candidates = inventory
+                .iter()
+                .flat_map(|children| children.iter())
+                .chain(siblings.iter())
+                .filter_map(|entity| items.get(*entity).ok())
+                .map(|(entity, depiction, _, _)| (entity, depiction))
+                .collect::<Vec<_>>();
+
+            let entity = match target.resolve(&candidates) {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (_, depiction, readable, written) = value_or_continue!(items.get(entity).ok());
+
+            let Some(readable) = readable else {
+                outbox.send_text(
+                    client.id,
+                    format!("There is nothing to read on the {}.", depiction.name),
+                );
+
+                continue;
+            };
+
+            let pages = readable
+                .pages
+                .iter()
+                .chain(written.iter().flat_map(|written| written.0.iter()))
+                .collect::<Vec<_>>();
+
+            if pages.is_empty() {
+                outbox.send_text(client.id, format!("The {} is blank.", depiction.name));
+
+                continue;
+            }
+
+            let number = page.unwrap_or(1);
+
+            let Some(text) = number.checked_sub(1).and_then(|idx| pages.get(idx)) else {
+                outbox.send_text(
+                    client.id,
+                    format!(
+                        "The {} only has {} {}.",
+                        depiction.name,
+                        pages.len(),
+                        if pages.len() == 1 { "page" } else { "pages" }
+                    ),
+                );
+
+                continue;
+            };
+
+            if pages.len() == 1 {
+                outbox.send_text(client.id, paint!("{}", text));
+            } else {
+                outbox.send_text(
+                    client.id,
+                    paint!("{}\n\nPage {} of {}.", text, number, pages.len()),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn sign_on_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(read);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        ItemBuilder::new()
+            .name("sign")
+            .readable(vec!["Welcome to Trinus Castra."], false)
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "read sign");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Welcome to Trinus Castra.");
+    }
+
+    #[test]
+    fn book_page() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(read);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let book = ItemBuilder::new()
+            .name("book")
+            .readable(vec!["Chapter one.", "Chapter two."], false)
+            .build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(book);
+
+        send_message(&mut app, client_id, "read book 2");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Chapter two.\n\nPage 2 of 2.");
+    }
+
+    #[test]
+    fn missing_page() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(read);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        ItemBuilder::new()
+            .name("scroll")
+            .readable(vec!["Beware."], false)
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "read scroll 3");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The scroll only has 1 page.");
+    }
+}
@@ -0,0 +1,239 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        components::Editor,
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    items::components::{Inventory, Item, Readable, Writing, WrittenPages},
+    player::components::{Client, Online},
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_write(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^write( (?P<target>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let target = captures
+                .name("target")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Write in what?".into()))?;
+
+            Ok(Command::Write(Target::parse(target)))
+        }
+    }
+}
+
+pub fn write(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut items: Query<
+        (
+            Entity,
+            &Depiction,
+            Option<&Readable>,
+            Option<&mut WrittenPages>,
+        ),
+        With<Item>,
+    >,
+    mut outbox: EventWriter<Outbox>,
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    players: Query<(Entity, &Client, &Children, Option<&Writing>), With<Online>>,
+) {
+    for command in commands.iter() {
+        match &command.command {
+            Command::Write(target) => {
+                let (player, client, children, _) =
+                    value_or_continue!(players.iter().find(|(_, c, _, _)| c.id == command.from));
+                let inventory = value_or_continue!(children
+                    .iter()
+                    .find_map(|child| inventories.get(*child).ok()));
+
+                let candidates = inventory
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .filter_map(|child| items.get(*child).ok())
+                    .map(|(entity, depiction, _, _)| (entity, depiction))
+                    .collect::<Vec<_>>();
+
+                let entity = match target.resolve(&candidates) {
+                    Ok(found) => found[0],
+                    Err(TargetError::NotFound) => {
+                        outbox.send_text(client.id, format!("You don't have a {target}."));
+
+                        continue;
+                    }
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
+
+                        continue;
+                    }
+                };
+
+                let (_, depiction, readable, _) = value_or_continue!(items.get(entity).ok());
+
+                if !readable.is_some_and(|readable| readable.writable) {
+                    outbox.send_text(
+                        client.id,
+                        format!("You can't write in the {}.", depiction.name),
+                    );
+
+                    continue;
+                }
+
+                bevy.entity(player)
+                    .insert((Editor::default(), Writing(entity)));
+
+                outbox.send_text(
+                    client.id,
+                    format!(
+                        "You begin writing in the {}. Finish with a line containing only \".\".",
+                        depiction.name
+                    ),
+                );
+            }
+            Command::EditorDone(lines) => {
+                let (player, client, _, writing) =
+                    value_or_continue!(players.iter().find(|(_, c, _, _)| c.id == command.from));
+                let writing = value_or_continue!(writing);
+
+                bevy.entity(player).remove::<Writing>();
+
+                let (entity, depiction, readable, written) =
+                    value_or_continue!(items.get_mut(writing.0).ok());
+
+                if lines.is_empty() {
+                    outbox.send_text(
+                        client.id,
+                        format!(
+                            "You put away the {} without writing anything.",
+                            depiction.name
+                        ),
+                    );
+
+                    continue;
+                }
+
+                let page = lines.join("\n");
+                let printed = readable.map(|readable| readable.pages.len()).unwrap_or(0);
+
+                let number = match written {
+                    Some(mut written) => {
+                        written.0.push(page);
+
+                        printed + written.0.len()
+                    }
+                    None => {
+                        bevy.entity(entity).insert(WrittenPages(vec![page]));
+
+                        printed + 1
+                    }
+                };
+
+                outbox.send_text(
+                    client.id,
+                    format!(
+                        "You finish writing page {} of the {}.",
+                        number, depiction.name
+                    ),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn writes_a_page() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(write);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let book = ItemBuilder::new()
+            .name("journal")
+            .readable(vec![], true)
+            .build(&mut app);
+
+        let (player, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(book);
+
+        send_message(&mut app, client_id, "write journal");
+        app.update();
+
+        assert!(app.world.get::<Editor>(player).is_some());
+
+        send_message(&mut app, client_id, "Dear diary,");
+        app.update();
+        send_message(&mut app, client_id, "today was long.");
+        app.update();
+        send_message(&mut app, client_id, ".");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You finish writing page 1 of the journal.");
+        assert!(app.world.get::<Editor>(player).is_none());
+        assert_eq!(
+            app.world.get::<WrittenPages>(book).unwrap().0,
+            vec!["Dear diary,\ntoday was long.".to_string()]
+        );
+    }
+
+    #[test]
+    fn not_writable() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(write);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let scroll = ItemBuilder::new()
+            .name("scroll")
+            .readable(vec!["Beware."], false)
+            .build(&mut app);
+
+        let (player, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(scroll);
+
+        send_message(&mut app, client_id, "write scroll");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You can't write in the scroll.");
+        assert!(app.world.get::<Editor>(player).is_none());
+    }
+}
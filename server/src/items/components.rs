@@ -54,3 +54,37 @@ impl Display for SurfaceKind {
         }
     }
 }
+
+/// Text that can be read a page at a time, such as signs, books and scrolls.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Readable {
+    /// Painted text of each page.
+    pub pages: Vec<String>,
+    /// Whether players can add pages with `write`.
+    pub writable: bool,
+}
+
+/// Pages players have written, which are saved with the item rather than its prototype. Only
+/// items in a player's inventory are saved, so pages written in a book left on a tile are lost
+/// when the server restarts.
+#[derive(Component, Default)]
+pub struct WrittenPages(pub Vec<String>);
+
+/// Added to a player while they write in the given item.
+#[derive(Component)]
+pub struct Writing(pub Entity);
+
+/// Lets players see in dark tiles while it's carried or lying on the ground.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct LightSource {
+    /// Seconds left before it burns out while carried, or `None` if it never does.
+    pub burn_time: Option<f32>,
+}
+
+impl LightSource {
+    pub fn is_lit(&self) -> bool {
+        self.burn_time.is_none_or(|burn_time| burn_time > 0.0)
+    }
+}
@@ -2,3 +2,4 @@ pub mod bundles;
 pub mod commands;
 pub mod components;
 pub mod plugin;
+pub mod systems;
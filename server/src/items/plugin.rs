@@ -2,20 +2,24 @@ use bevy::prelude::*;
 
 use super::{
     bundles::ItemBundle,
-    commands::{drop::*, inventory::*},
+    commands::{drop::*, inventory::*, read::*, write::*},
     components::*,
+    systems::*,
 };
 
 pub struct ItemPlugin;
 
 impl Plugin for ItemPlugin {
     fn build(&self, app: &mut App) {
-        app.register_type::<ItemBundle>()
+        app.register_type::<Option<f32>>()
+            .register_type::<ItemBundle>()
             .register_type::<Item>()
+            .register_type::<LightSource>()
+            .register_type::<Readable>()
             .register_type::<Surface>()
             .register_type::<SurfaceKind>()
             .register_type::<Size>();
 
-        app.add_systems((inventory, drop));
+        app.add_systems((inventory, drop, read, write, burn_light_sources));
     }
 }
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+
+use crate::{player::components::Client, visual::components::Depiction};
+
+use super::components::{Inventory, LightSource};
+
+/// Burns down light sources that are being carried, telling the carrier when one goes out.
+pub fn burn_light_sources(
+    clients: Query<&Client>,
+    inventories: Query<&Parent, With<Inventory>>,
+    mut lights: Query<(&Depiction, &mut LightSource, &Parent)>,
+    mut outbox: EventWriter<Outbox>,
+    time: Res<Time>,
+) {
+    for (depiction, mut light, parent) in lights.iter_mut() {
+        let Ok(carrier) = inventories.get(parent.get()) else {
+            continue;
+        };
+
+        let Some(burn_time) = light.burn_time.filter(|burn_time| *burn_time > 0.0) else {
+            continue;
+        };
+
+        let remaining = (burn_time - time.delta_seconds()).max(0.0);
+
+        // Lighting is worked out again whenever a light source changes, so it's only flagged
+        // as changed when another whole second has burned away or the light goes out.
+        light.bypass_change_detection().burn_time = Some(remaining);
+
+        if remaining.ceil() < burn_time.ceil() {
+            light.set_changed();
+        }
+
+        if remaining == 0.0 {
+            if let Ok(client) = clients.get(carrier.get()) {
+                outbox.send_text(
+                    client.id,
+                    format!("Your {} burns out.", depiction.short_name),
+                );
+            }
+        }
+    }
+}
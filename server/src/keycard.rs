@@ -3,9 +3,13 @@ use bevy::prelude::*;
 pub const SHUTDOWN: u32 = 1 << 2;
 pub const ANNOUNCE: u32 = 1 << 3;
 pub const TELEPORT: u32 = 1 << 4;
+pub const RELOAD: u32 = 1 << 5;
+pub const BUILD: u32 = 1 << 6;
+pub const EVENTS: u32 = 1 << 7;
 
 const PLAYER: u32 = 0;
-const ADMIN: u32 = PLAYER | SHUTDOWN | ANNOUNCE | TELEPORT;
+const BUILDER: u32 = PLAYER | BUILD;
+const ADMIN: u32 = BUILDER | SHUTDOWN | ANNOUNCE | TELEPORT | RELOAD | EVENTS;
 
 #[derive(Component)]
 pub struct Keycard {
@@ -17,6 +21,12 @@ impl Keycard {
         Self { permissions: ADMIN }
     }
 
+    pub fn builder() -> Self {
+        Self {
+            permissions: BUILDER,
+        }
+    }
+
     pub fn player() -> Self {
         Self {
             permissions: PLAYER,
@@ -39,6 +49,18 @@ mod tests {
         assert!(keycard.can(SHUTDOWN));
         assert!(keycard.can(ANNOUNCE));
         assert!(keycard.can(TELEPORT));
+        assert!(keycard.can(RELOAD));
+        assert!(keycard.can(BUILD));
+        assert!(keycard.can(EVENTS));
+    }
+
+    #[test]
+    fn builder_keycard() {
+        let keycard = Keycard::builder();
+
+        assert!(keycard.can(BUILD));
+        assert!(!keycard.can(SHUTDOWN));
+        assert!(!keycard.can(TELEPORT));
     }
 
     #[test]
@@ -48,5 +70,8 @@ mod tests {
         assert!(!keycard.can(SHUTDOWN));
         assert!(!keycard.can(ANNOUNCE));
         assert!(!keycard.can(TELEPORT));
+        assert!(!keycard.can(RELOAD));
+        assert!(!keycard.can(BUILD));
+        assert!(!keycard.can(EVENTS));
     }
 }
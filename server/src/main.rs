@@ -1,5 +1,6 @@
 mod auth;
 mod combat;
+mod content;
 mod db;
 mod input;
 mod interact;
@@ -7,15 +8,21 @@ mod items;
 mod keycard;
 mod net;
 mod npc;
+mod olc;
 mod player;
+mod quest;
+mod scheduler;
+mod script;
 mod social;
 mod spatial;
 mod test;
 mod utils;
 mod visual;
+mod weather;
+mod wilderness;
 mod world;
 
-use std::{env, time::Duration};
+use std::{env, path::PathBuf, time::Duration};
 
 use bevy::{
     app::ScheduleRunnerSettings,
@@ -29,11 +36,26 @@ use dotenvy::dotenv;
 use sqlx::{migrate, postgres::PgPoolOptions};
 
 use crate::{
-    auth::plugin::AuthPlugin, combat::plugin::CombatPlugin, db::pool::DatabasePool,
-    input::plugin::InputPlugin, interact::plugin::InteractPlugin, items::plugin::ItemPlugin,
-    net::plugin::NetPlugin, npc::plugin::NpcPlugin, player::plugin::PlayerPlugin,
-    social::plugin::SocialPlugin, spatial::plugin::SpatialPlugin, visual::plugin::VisualPlugin,
-    world::plugin::WorldPlugin,
+    auth::plugin::AuthPlugin,
+    combat::plugin::CombatPlugin,
+    content::plugin::{ContentCheckPlugin, MapExportPlugin},
+    db::pool::DatabasePool,
+    input::plugin::InputPlugin,
+    interact::plugin::InteractPlugin,
+    items::plugin::ItemPlugin,
+    net::plugin::NetPlugin,
+    npc::plugin::NpcPlugin,
+    olc::plugin::OlcPlugin,
+    player::plugin::PlayerPlugin,
+    quest::plugin::QuestPlugin,
+    scheduler::plugin::SchedulerPlugin,
+    script::plugin::ScriptPlugin,
+    social::plugin::SocialPlugin,
+    spatial::plugin::SpatialPlugin,
+    visual::plugin::VisualPlugin,
+    weather::plugin::WeatherPlugin,
+    wilderness::plugin::WildernessPlugin,
+    world::{loader::WorldLoader, plugin::WorldPlugin, resources::LoadFailures},
 };
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
@@ -44,25 +66,16 @@ pub enum Set {
 }
 
 fn load_prototypes(mut prototypes: PrototypesMut) {
-    match prototypes.load_folder("world/") {
-        Ok(loaded) => {
-            loaded.iter().for_each(|proto| {
-                info!("Loaded zones: {:?}", proto);
-            });
-        }
-        Err(err) => {
-            error!("Failed to load prototypes: {}", err);
-        }
-    }
-
-    match prototypes.load_folder("enemies/") {
-        Ok(loaded) => {
-            loaded.iter().for_each(|proto| {
-                info!("Loaded enemies: {:?}", proto);
-            });
-        }
-        Err(err) => {
-            error!("Failed to load prototypes: {}", err);
+    for folder in ["enemies", "quests", "events", "calendar"] {
+        match prototypes.load_folder(format!("{folder}/")) {
+            Ok(loaded) => {
+                loaded.iter().for_each(|proto| {
+                    info!("Loaded {}: {:?}", folder, proto);
+                });
+            }
+            Err(err) => {
+                error!("Failed to load prototypes: {}", err);
+            }
         }
     }
 }
@@ -75,14 +88,32 @@ fn setup_network(server: Res<Server>) {
 async fn main() -> Result<(), sqlx::Error> {
     dotenv().ok();
 
-    let pool = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&env::var("DATABASE_URL").unwrap())
-        .await?;
+    let args = env::args().collect::<Vec<_>>();
+    let check_content = args.iter().any(|arg| arg == "--check-content");
+    let export_maps = args
+        .iter()
+        .position(|arg| arg == "--export-maps")
+        .map(|idx| PathBuf::from(args.get(idx + 1).map_or("maps", String::as_str)));
+
+    // Neither mode touches the database, so don't require one to be running.
+    let pool = if check_content || export_maps.is_some() {
+        None
+    } else {
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&env::var("DATABASE_URL").unwrap())
+            .await?;
+
+        migrate!().run(&pool).await?;
+
+        Some(pool)
+    };
 
-    migrate!().run(&pool).await?;
+    let load_failures = LoadFailures::default();
 
-    App::new()
+    let mut app = App::new();
+
+    app
         // Stages
         .configure_set(Set::Input.before(CoreSet::Update))
         .configure_set(Set::WorldSave.after(CoreSet::Update))
@@ -90,7 +121,7 @@ async fn main() -> Result<(), sqlx::Error> {
         .insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
             1.0 / 60.0,
         )))
-        .insert_resource(DatabasePool(pool))
+        .insert_resource(load_failures.clone())
         // Bevy plugins
         .add_plugins(MinimalPlugins)
         .add_plugin(AssetPlugin::default())
@@ -100,7 +131,9 @@ async fn main() -> Result<(), sqlx::Error> {
         })
         // 3rd party plugins
         .add_plugin(NestPlugin)
-        .add_plugin(ProtoPlugin::new())
+        .add_plugin(ProtoPlugin::new_with_loader(WorldLoader::new(
+            load_failures,
+        )))
         // Our plugins
         .add_plugin(AuthPlugin)
         .add_plugin(CombatPlugin)
@@ -109,14 +142,33 @@ async fn main() -> Result<(), sqlx::Error> {
         .add_plugin(ItemPlugin)
         .add_plugin(NpcPlugin)
         .add_plugin(NetPlugin)
+        .add_plugin(OlcPlugin)
         .add_plugin(PlayerPlugin)
+        .add_plugin(QuestPlugin)
+        .add_plugin(SchedulerPlugin)
+        .add_plugin(ScriptPlugin)
         .add_plugin(SocialPlugin)
         .add_plugin(SpatialPlugin)
         .add_plugin(VisualPlugin)
+        .add_plugin(WeatherPlugin)
+        .add_plugin(WildernessPlugin)
         .add_plugin(WorldPlugin)
         // Get it started
-        .add_startup_systems((load_prototypes, setup_network))
-        .run();
+        .add_startup_system(load_prototypes);
+
+    if let Some(pool) = pool {
+        app.insert_resource(DatabasePool(pool));
+    }
+
+    if check_content {
+        app.add_plugin(ContentCheckPlugin);
+    } else if let Some(dir) = export_maps {
+        app.add_plugin(MapExportPlugin { dir });
+    } else {
+        app.add_startup_system(setup_network);
+    }
+
+    app.run();
 
     Ok(())
 }
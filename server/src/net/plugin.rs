@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 
+use crate::db::pool::DatabasePool;
+
 use super::systems::*;
 
 pub struct NetPlugin;
 
 impl Plugin for NetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(on_network_event);
+        app.add_system(on_network_event.run_if(resource_exists::<DatabasePool>()));
     }
 }
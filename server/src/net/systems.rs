@@ -8,11 +8,11 @@ use sqlx::{Pool, Postgres};
 use crate::{
     auth::components::Authenticating,
     db::{pool::DatabasePool, utils::store_world_state},
-    items::components::{Inventory, Item},
+    items::components::{Inventory, Item, WrittenPages},
     player::components::{Character, Client, Online},
     spatial::components::Tile,
     value_or_continue,
-    world::resources::{WorldState, WorldStateCharacter},
+    world::resources::{WorldState, WorldStateCharacter, WorldStateItem},
 };
 
 use super::telnet::NAWS;
@@ -28,7 +28,7 @@ pub fn on_network_event(
     database: Res<DatabasePool>,
     world_state: Res<WorldState>,
     inventories: Query<Option<&Children>, With<Inventory>>,
-    items: Query<(Entity, &Name), With<Item>>,
+    items: Query<(&Name, Option<&WrittenPages>), With<Item>>,
     tiles: Query<&Name, With<Tile>>,
 ) {
     for event in events.iter() {
@@ -56,7 +56,10 @@ pub fn on_network_event(
                 .iter()
                 .flat_map(|children| children.iter())
                 .filter_map(|child| items.get(*child).ok())
-                .map(|(_, name)| name.to_string())
+                .map(|(name, written)| WorldStateItem {
+                    name: name.to_string(),
+                    written_pages: written.map(|w| w.0.clone()).unwrap_or_default(),
+                })
                 .collect::<Vec<_>>();
 
                 let state = WorldStateCharacter {
@@ -73,7 +76,11 @@ pub fn on_network_event(
                     characters.push(state);
                 }
 
-                let state = WorldState { characters };
+                let state = WorldState {
+                    characters,
+                    locks: world_state.locks.clone(),
+                    epoch: world_state.epoch,
+                };
 
                 bevy.spawn(SaveCharacterTask(spawn_save_character_task(
                     database.0.clone(),
@@ -1,6 +1,10 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 use bevy_proto::prelude::*;
 
+use crate::spatial::pathfinding::PathStep;
+
 #[derive(Component, Reflect, FromReflect)]
 pub struct Npc;
 
@@ -14,3 +18,182 @@ pub struct EnemySpawner {
 
 #[derive(Component)]
 pub struct SpawnTimer(pub Timer);
+
+/// What an NPC has to say, as a tree of nodes linked by the choices players pick.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Dialogue {
+    /// Nodes that can open a conversation, tried in order until one's conditions are met.
+    pub greetings: Vec<String>,
+    pub nodes: Vec<DialogueNode>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct DialogueNode {
+    pub id: String,
+    /// Painted text the NPC says.
+    pub line: String,
+    /// Topics that lead straight to this node through `ask <npc> about <topic>`.
+    pub keywords: Vec<String>,
+    pub conditions: Vec<DialogueCondition>,
+    pub choices: Vec<DialogueChoice>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct DialogueChoice {
+    pub text: String,
+    /// Node the NPC answers with, or `None` to end the conversation.
+    pub next: Option<String>,
+    pub conditions: Vec<DialogueCondition>,
+    /// Quest offered to the player when they pick this choice.
+    pub quest: Option<String>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub enum DialogueCondition {
+    /// The player carries an item with this prototype name.
+    HasItem(String),
+    LacksItem(String),
+    /// The player hasn't taken on the quest with this prototype name.
+    QuestNotStarted(String),
+    QuestActive(String),
+    QuestCompleted(String),
+}
+
+impl Dialogue {
+    pub fn node(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    pub fn greeting(&self, context: &DialogueContext) -> Option<&DialogueNode> {
+        self.greetings
+            .iter()
+            .filter_map(|id| self.node(id))
+            .find(|node| context.meets(&node.conditions))
+    }
+
+    pub fn topic(&self, topic: &str, context: &DialogueContext) -> Option<&DialogueNode> {
+        let topic = topic.to_lowercase();
+
+        self.nodes.iter().find(|node| {
+            node.keywords
+                .iter()
+                .any(|keyword| keyword.to_lowercase() == topic)
+                && context.meets(&node.conditions)
+        })
+    }
+}
+
+impl DialogueNode {
+    /// Choices the player can pick, in the order they are numbered.
+    pub fn choices<'a>(
+        &'a self,
+        context: &'a DialogueContext,
+    ) -> impl Iterator<Item = &'a DialogueChoice> {
+        self.choices
+            .iter()
+            .filter(|choice| context.meets(&choice.conditions))
+    }
+}
+
+/// What dialogue conditions are checked against.
+pub struct DialogueContext {
+    /// Prototype names of the items the player carries.
+    pub items: Vec<String>,
+    /// Prototype names of quests the player is on.
+    pub active_quests: Vec<String>,
+    pub completed_quests: Vec<String>,
+}
+
+impl DialogueContext {
+    pub fn meets(&self, conditions: &[DialogueCondition]) -> bool {
+        conditions.iter().all(|condition| match condition {
+            DialogueCondition::HasItem(item) => self.items.contains(item),
+            DialogueCondition::LacksItem(item) => !self.items.contains(item),
+            DialogueCondition::QuestNotStarted(quest) => {
+                !self.active_quests.contains(quest) && !self.completed_quests.contains(quest)
+            }
+            DialogueCondition::QuestActive(quest) => self.active_quests.contains(quest),
+            DialogueCondition::QuestCompleted(quest) => self.completed_quests.contains(quest),
+        })
+    }
+}
+
+/// Makes an NPC roam between tiles on its own.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Wander {
+    /// Seconds between moves.
+    pub interval: f32,
+    /// How the NPC moves, e.g. `scurries` in "The rat scurries north."
+    pub verb: String,
+    /// How many tiles it may stray from where it started, keeping it in that zone.
+    pub radius: Option<i32>,
+    /// Prototype names of the tiles it may enter, or any tile when empty.
+    pub tiles: Vec<String>,
+    /// Whether it may leave through transitions.
+    pub transitions: bool,
+}
+
+/// Where a wandering NPC started out, which `Wander::radius` is counted from.
+#[derive(Component)]
+pub struct WanderHome {
+    pub zone: Entity,
+    pub position: IVec3,
+}
+
+#[derive(Component)]
+pub struct WanderTimer(pub Timer);
+
+/// Paces an NPC walking to where its schedule wants it.
+#[derive(Component)]
+pub struct ScheduleStepTimer(pub Timer);
+
+/// The rest of the way an NPC is walking to where its schedule wants it. It's worked out
+/// again once the NPC ends up somewhere else or its schedule sends it elsewhere.
+#[derive(Component)]
+pub struct ScheduleRoute {
+    /// The tile the NPC should be on for the next step.
+    pub from: Entity,
+    pub target: Entity,
+    pub steps: VecDeque<PathStep>,
+}
+
+/// Where an NPC should be and what it's doing at each time of day.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Schedule {
+    pub entries: Vec<ScheduleEntry>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct ScheduleEntry {
+    /// In-game hour this entry starts at. It lasts until the next entry starts, wrapping
+    /// around midnight.
+    pub from: u32,
+    pub place: SchedulePlace,
+    /// Shown by `look`, e.g. "is polishing glasses behind the bar".
+    pub activity: String,
+}
+
+#[derive(Reflect, FromReflect)]
+pub enum SchedulePlace {
+    /// Prototype name of a tile.
+    Tile(String),
+    /// Name of a zone, where any tile will do.
+    Zone(String),
+}
+
+impl Schedule {
+    pub fn entry_at(&self, hour: u32) -> Option<&ScheduleEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.from <= hour)
+            .max_by_key(|entry| entry.from)
+            .or_else(|| self.entries.iter().max_by_key(|entry| entry.from))
+    }
+}
+
+/// What an NPC is currently busy with, shown by `look`.
+#[derive(Component)]
+pub struct Activity(pub String);
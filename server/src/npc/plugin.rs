@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use super::{bundles::*, components::*, systems::*};
+use super::{
+    bundles::*,
+    components::{Schedule, *},
+    systems::*,
+};
 
 pub struct NpcPlugin;
 
@@ -10,8 +14,22 @@ impl Plugin for NpcPlugin {
             .register_type::<(String, u16, u16)>()
             .register_type::<NpcBundle>()
             .register_type::<Npc>()
-            .register_type::<EnemySpawner>();
+            .register_type::<EnemySpawner>()
+            .register_type::<Option<String>>()
+            .register_type::<Dialogue>()
+            .register_type::<DialogueNode>()
+            .register_type::<Vec<DialogueNode>>()
+            .register_type::<DialogueChoice>()
+            .register_type::<Vec<DialogueChoice>>()
+            .register_type::<DialogueCondition>()
+            .register_type::<Vec<DialogueCondition>>()
+            .register_type::<Option<i32>>()
+            .register_type::<Wander>()
+            .register_type::<Schedule>()
+            .register_type::<ScheduleEntry>()
+            .register_type::<Vec<ScheduleEntry>>()
+            .register_type::<SchedulePlace>();
 
-        app.add_system(handle_enemy_spawner);
+        app.add_systems((handle_enemy_spawner, wander, follow_schedule));
     }
 }
@@ -1,10 +1,31 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
 use bevy_proto::prelude::*;
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{
+    interact::components::{Lock, Openable},
+    player::components::{Character, Online},
+    spatial::{
+        components::{Doorway, Exits, Position, Tile, Transition, Zone},
+        events::{EntityMoved, MoveMethod},
+        pathfinding::{Pathfinder, Step},
+        utils::{offset_for_direction, passage, Passage, DIRECTIONS},
+    },
+    utils::prototype_id,
+    value_or_continue,
+    visual::components::Depiction,
+    world::resources::WorldTime,
+};
 
-use crate::{spatial::components::Tile, value_or_continue};
+use super::components::{
+    Activity, EnemySpawner, Npc, Schedule, SchedulePlace, ScheduleRoute, ScheduleStepTimer,
+    SpawnTimer, Wander, WanderHome, WanderTimer,
+};
 
-use super::components::{EnemySpawner, SpawnTimer};
+/// Seconds an NPC takes for each step on its way to a scheduled place.
+const SCHEDULE_STEP_SECONDS: f32 = 1.0;
 
 pub fn handle_enemy_spawner(
     mut bevy: Commands,
@@ -44,3 +65,508 @@ pub fn handle_enemy_spawner(
         }
     }
 }
+
+pub fn wander(
+    doorways: Query<(&Doorway, &Depiction, Option<&Openable>)>,
+    exits: Query<&Exits>,
+    mut bevy: Commands,
+    mut moved: EventWriter<EntityMoved>,
+    mut npcs: Query<
+        (
+            Entity,
+            &Wander,
+            &Parent,
+            Option<&WanderHome>,
+            Option<&mut WanderTimer>,
+        ),
+        With<Npc>,
+    >,
+    players: Query<&Character, With<Online>>,
+    tiles: Query<(Entity, &Position, &Parent, Option<&Name>, Option<&Children>), With<Tile>>,
+    time: Res<Time>,
+    transitions: Query<(&Transition, Option<&Lock>, Option<&Openable>)>,
+    zones: Query<(&Zone, &Children)>,
+) {
+    for (npc, wander, tile, home, timer) in npcs.iter_mut() {
+        let (tile, position, zone, _, siblings) = value_or_continue!(tiles.get(tile.get()).ok());
+
+        let (Some(home), Some(mut timer)) = (home, timer) else {
+            bevy.entity(npc).insert((
+                WanderHome {
+                    zone: zone.get(),
+                    position: position.0,
+                },
+                WanderTimer(Timer::from_seconds(wander.interval, TimerMode::Repeating)),
+            ));
+
+            continue;
+        };
+
+        if !timer.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        if players
+            .iter()
+            .any(|character| character.state.as_combat() == Some(&npc))
+        {
+            continue;
+        }
+
+        let may_enter = |target: Entity| {
+            let Ok((_, target_position, target_zone, name, _)) = tiles.get(target) else {
+                return false;
+            };
+
+            let within_radius = wander.radius.is_none_or(|radius| {
+                target_zone.get() == home.zone
+                    && (target_position.0 - home.position).abs().max_element() <= radius
+            });
+
+            let allowed_tile = wander.tiles.is_empty()
+                || name.is_some_and(|name| {
+                    wander
+                        .tiles
+                        .iter()
+                        .any(|allowed| allowed == prototype_id(name))
+                });
+
+            within_radius && allowed_tile
+        };
+
+        let (_, zone_tiles) = value_or_continue!(zones.get(zone.get()).ok());
+
+        let mut moves: Vec<(Entity, Option<&str>)> = DIRECTIONS
+            .iter()
+            .filter_map(|direction| {
+                let offset = offset_for_direction(direction)?;
+
+                let neighbour = zone_tiles.iter().copied().find(|child| {
+                    tiles
+                        .get(*child)
+                        .is_ok_and(|(_, p, _, _, _)| p.0 == position.0 + offset)
+                })?;
+
+                match passage(tile, neighbour, offset, &exits, &doorways) {
+                    Passage::Open => Some((neighbour, Some(*direction))),
+                    _ => None,
+                }
+            })
+            .filter(|(target, _)| may_enter(*target))
+            .collect();
+
+        if wander.transitions {
+            moves.extend(
+                siblings
+                    .iter()
+                    .flat_map(|siblings| siblings.iter())
+                    .filter_map(|sibling| transitions.get(*sibling).ok())
+                    .filter(|(_, lock, openable)| {
+                        !lock.is_some_and(|lock| lock.locked)
+                            && !openable.is_some_and(|openable| !openable.open)
+                    })
+                    .filter_map(|(transition, _, _)| {
+                        tiles.iter().find_map(|(target, p, z, _, _)| {
+                            zones
+                                .get(z.get())
+                                .is_ok_and(|(zone, _)| {
+                                    zone.name == transition.zone && p.0 == transition.position
+                                })
+                                .then_some(target)
+                        })
+                    })
+                    .filter(|target| may_enter(*target))
+                    .map(|target| (target, None)),
+            );
+        }
+
+        let Some((target, direction)) = moves.choose(&mut thread_rng()).copied() else {
+            continue;
+        };
+
+        bevy.entity(npc).set_parent(target);
+
+        moved.send(EntityMoved {
+            entity: npc,
+            from: tile,
+            to: target,
+            direction: direction.map(String::from),
+            method: if direction.is_some() {
+                MoveMethod::Walk
+            } else {
+                MoveMethod::Enter
+            },
+        });
+    }
+}
+
+pub fn follow_schedule(
+    mut bevy: Commands,
+    mut moved: EventWriter<EntityMoved>,
+    mut npcs: Query<
+        (
+            Entity,
+            &Schedule,
+            &Parent,
+            Option<&Activity>,
+            Option<&mut ScheduleRoute>,
+            Option<&mut ScheduleStepTimer>,
+        ),
+        With<Npc>,
+    >,
+    pathfinder: Pathfinder,
+    players: Query<&Character, With<Online>>,
+    tiles: Query<(Entity, &Parent, Option<&Name>), With<Tile>>,
+    time: Res<Time>,
+    world_time: Res<WorldTime>,
+    zones: Query<(&Zone, &Children)>,
+) {
+    for (npc, schedule, tile, activity, mut route, timer) in npcs.iter_mut() {
+        let entry = value_or_continue!(schedule.entry_at(world_time.hour));
+
+        if activity.map(|activity| &activity.0) != Some(&entry.activity) {
+            bevy.entity(npc).insert(Activity(entry.activity.clone()));
+        }
+
+        if players
+            .iter()
+            .any(|character| character.state.as_combat() == Some(&npc))
+        {
+            continue;
+        }
+
+        let (tile, zone, name) = value_or_continue!(tiles.get(tile.get()).ok());
+        let is_prototype = |name: Option<&Name>, prototype: &str| {
+            name.is_some_and(|name| prototype_id(name) == prototype)
+        };
+
+        let in_place = match &entry.place {
+            SchedulePlace::Tile(prototype) => is_prototype(name, prototype),
+            SchedulePlace::Zone(zone_name) => zones
+                .get(zone.get())
+                .is_ok_and(|(zone, _)| zone.name == *zone_name),
+        };
+
+        if in_place {
+            if timer.is_some() || route.is_some() {
+                bevy.entity(npc)
+                    .remove::<(ScheduleStepTimer, ScheduleRoute)>();
+            }
+
+            continue;
+        }
+
+        if let Some(mut timer) = timer {
+            if !timer.0.tick(time.delta()).just_finished() {
+                continue;
+            }
+        }
+
+        let target = match &entry.place {
+            SchedulePlace::Tile(prototype) => tiles
+                .iter()
+                .find(|(_, _, name)| is_prototype(*name, prototype))
+                .map(|(entity, _, _)| entity),
+            SchedulePlace::Zone(zone_name) => zones
+                .iter()
+                .find(|(zone, _)| zone.name == *zone_name)
+                .and_then(|(_, children)| children.iter().copied().find(|c| tiles.contains(*c))),
+        };
+
+        let target = value_or_continue!(target);
+
+        let planned = route
+            .as_deref_mut()
+            .filter(|route| route.from == tile && route.target == target)
+            .and_then(|route| {
+                let step = route.steps.pop_front()?;
+                route.from = step.to;
+
+                Some(step)
+            });
+
+        let next = planned.or_else(|| {
+            let mut steps = VecDeque::from(pathfinder.find(tile, target)?);
+            let step = steps.pop_front()?;
+
+            bevy.entity(npc).insert((
+                ScheduleRoute {
+                    from: step.to,
+                    target,
+                    steps,
+                },
+                ScheduleStepTimer(Timer::from_seconds(
+                    SCHEDULE_STEP_SECONDS,
+                    TimerMode::Repeating,
+                )),
+            ));
+
+            Some(step)
+        });
+
+        // Walk there a step at a time when there's a way, otherwise just turn up.
+        let (target, direction, method) = match next {
+            Some(next) => match next.step {
+                Step::Direction(direction) => (next.to, Some(direction.into()), MoveMethod::Walk),
+                Step::Enter(_) => (next.to, None, MoveMethod::Enter),
+            },
+            None => (target, None, MoveMethod::Other),
+        };
+
+        bevy.entity(npc).set_parent(target);
+
+        moved.send(EntityMoved {
+            entity: npc,
+            from: tile,
+            to: target,
+            direction,
+            method,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        npc::components::ScheduleEntry,
+        spatial::systems::announce_movement,
+        test::{
+            app_builder::AppBuilder,
+            npc_builder::NpcBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::get_message_content,
+        },
+    };
+
+    use super::*;
+
+    fn rat(radius: Option<i32>) -> Wander {
+        Wander {
+            interval: 1.0,
+            verb: "scurries".into(),
+            radius,
+            tiles: vec![],
+            transitions: false,
+        }
+    }
+
+    fn wait_for_move(app: &mut App, npc: Entity) {
+        app.world
+            .get_mut::<WanderTimer>(npc)
+            .unwrap()
+            .0
+            .set_elapsed(Duration::from_secs(1));
+
+        app.update();
+    }
+
+    #[test]
+    fn wanders_to_adjacent_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((wander, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, -1, 0))
+            .build(&mut app, zone);
+
+        let npc = NpcBuilder::new()
+            .short_name("rat")
+            .wander(rat(None))
+            .tile(start)
+            .build(&mut app);
+
+        let (_, watcher, _) = PlayerBuilder::new().tile(start).build(&mut app);
+        let (_, greeter, _) = PlayerBuilder::new().tile(destination).build(&mut app);
+
+        app.update();
+        wait_for_move(&mut app, npc);
+
+        assert_eq!(app.world.get::<Parent>(npc).unwrap().get(), destination);
+        assert_eq!(
+            get_message_content(&mut app, watcher).unwrap(),
+            "The rat scurries north."
+        );
+        assert_eq!(
+            get_message_content(&mut app, greeter).unwrap(),
+            "The rat arrives from the south."
+        );
+    }
+
+    #[test]
+    fn stays_within_radius() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((wander, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+
+        let npc = NpcBuilder::new()
+            .short_name("rat")
+            .wander(rat(Some(0)))
+            .tile(start)
+            .build(&mut app);
+
+        app.update();
+        wait_for_move(&mut app, npc);
+
+        assert_eq!(app.world.get::<Parent>(npc).unwrap().get(), start);
+    }
+
+    fn innkeeper_schedule() -> Schedule {
+        Schedule {
+            entries: vec![
+                ScheduleEntry {
+                    from: 6,
+                    place: SchedulePlace::Tile("lion.bar".into()),
+                    activity: "is polishing glasses behind the bar".into(),
+                },
+                ScheduleEntry {
+                    from: 22,
+                    place: SchedulePlace::Tile("lion.upstairs".into()),
+                    activity: "is asleep".into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn follows_schedule() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((follow_schedule, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let bar = TileBuilder::new()
+            .prototype("lion.bar")
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let upstairs = TileBuilder::new()
+            .prototype("lion.upstairs")
+            .position(IVec3::new(5, 5, 5))
+            .build(&mut app, zone);
+
+        let innkeeper = NpcBuilder::new()
+            .short_name("innkeeper")
+            .schedule(innkeeper_schedule())
+            .tile(bar)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(bar).build(&mut app);
+
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), upstairs);
+        assert_eq!(app.world.get::<Activity>(innkeeper).unwrap().0, "is asleep");
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "The innkeeper leaves."
+        );
+
+        app.world.resource_mut::<WorldTime>().hour = 6;
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), bar);
+        assert_eq!(
+            app.world.get::<Activity>(innkeeper).unwrap().0,
+            "is polishing glasses behind the bar"
+        );
+    }
+
+    #[test]
+    fn walks_to_scheduled_place() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((follow_schedule, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let bar = TileBuilder::new()
+            .prototype("lion.bar")
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let stairs = TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        let upstairs = TileBuilder::new()
+            .prototype("lion.upstairs")
+            .position(IVec3::new(1, 0, 1))
+            .build(&mut app, zone);
+
+        let innkeeper = NpcBuilder::new()
+            .short_name("innkeeper")
+            .schedule(innkeeper_schedule())
+            .tile(bar)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(bar).build(&mut app);
+
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), stairs);
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "The innkeeper leaves east."
+        );
+
+        app.world
+            .get_mut::<ScheduleStepTimer>(innkeeper)
+            .unwrap()
+            .0
+            .set_elapsed(Duration::from_secs_f32(SCHEDULE_STEP_SECONDS));
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), upstairs);
+    }
+
+    #[test]
+    fn reroutes_when_moved_off_course() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((follow_schedule, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let bar = TileBuilder::new()
+            .prototype("lion.bar")
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let stairs = TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .prototype("lion.upstairs")
+            .position(IVec3::new(1, 0, 1))
+            .build(&mut app, zone);
+
+        let innkeeper = NpcBuilder::new()
+            .short_name("innkeeper")
+            .schedule(innkeeper_schedule())
+            .tile(bar)
+            .build(&mut app);
+
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), stairs);
+
+        app.world.entity_mut(innkeeper).set_parent(bar);
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), bar);
+
+        app.world
+            .get_mut::<ScheduleStepTimer>(innkeeper)
+            .unwrap()
+            .0
+            .set_elapsed(Duration::from_secs_f32(SCHEDULE_STEP_SECONDS));
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(innkeeper).unwrap().get(), stairs);
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    keycard::{Keycard, BUILD},
+    olc::components::Unsaved,
+    player::components::{Client, Online},
+    spatial::{
+        bundles::TileBundle,
+        components::{Position, Tile},
+        utils::{full_direction, offset_for_direction},
+    },
+    value_or_continue,
+    visual::components::Sprite,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_dig(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^dig( (?P<direction>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let direction = captures
+                .name("direction")
+                .and_then(|m| full_direction(m.as_str().trim()))
+                .ok_or(ParseError::InvalidArguments("Dig which way?".into()))?;
+
+            Ok(Command::Dig(direction.into()))
+        }
+    }
+}
+
+pub fn dig(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Keycard, &Parent), With<Online>>,
+    tiles: Query<(&Position, &Sprite, &Parent), With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Dig(direction) = &command.command {
+            let (client, keycard, tile) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+
+            if !keycard.can(BUILD) {
+                continue;
+            }
+
+            let (position, sprite, zone) = value_or_continue!(tiles.get(tile.get()).ok());
+            let offset = value_or_continue!(offset_for_direction(direction));
+            let target = position.0 + offset;
+
+            let way = match direction.as_str() {
+                "up" => "above you".to_string(),
+                "down" => "below you".to_string(),
+                direction => format!("to the {direction}"),
+            };
+
+            if tiles
+                .iter()
+                .any(|(position, _, parent)| parent.get() == zone.get() && position.0 == target)
+            {
+                outbox.send_text(client.id, format!("There's already a tile {way}."));
+
+                continue;
+            }
+
+            bevy.spawn((
+                TileBundle {
+                    tile: Tile {
+                        name: "New tile".into(),
+                        description: "Nothing has been built here yet.".into(),
+                    },
+                    sprite: Sprite {
+                        character: sprite.character.clone(),
+                    },
+                    position: Position(target),
+                },
+                Unsaved,
+            ))
+            .set_parent(zone.get());
+
+            outbox.send_text(client.id, format!("You dig out a new tile {way}."));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn digs_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(dig);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .sprite("#")
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "dig n");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You dig out a new tile to the north.");
+
+        let (position, sprite, _) = app
+            .world
+            .query_filtered::<(&Position, &Sprite, &Parent), With<Unsaved>>()
+            .single(&app.world);
+
+        assert_eq!(position.0, IVec3::new(0, -1, 0));
+        assert_eq!(sprite.character, "#");
+    }
+
+    #[test]
+    fn tile_exists() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(dig);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, 0, 1))
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "dig up");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "There's already a tile above you.");
+    }
+
+    #[test]
+    fn not_a_builder() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(dig);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "dig n");
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+        assert!(app
+            .world
+            .query_filtered::<(), With<Unsaved>>()
+            .iter(&app.world)
+            .next()
+            .is_none());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod dig;
+pub mod oedit;
+pub mod redit;
+pub mod zsave;
@@ -0,0 +1,189 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    items::components::{Inventory, Item},
+    keycard::{Keycard, BUILD},
+    olc::components::Unsaved,
+    player::components::{Client, Online},
+    spatial::components::Tile,
+    value_or_continue,
+    visual::components::Depiction,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_oedit(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"^oedit(\s+(?P<target>.+?)\s+(?P<field>name|short|desc)\s+(?P<value>.+))?$")
+            .unwrap()
+    });
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let (target, field, value) = captures
+                .name("target")
+                .zip(captures.name("field"))
+                .zip(captures.name("value"))
+                .map(|((target, field), value)| {
+                    (target.as_str(), field.as_str(), value.as_str().trim())
+                })
+                .ok_or(ParseError::InvalidArguments(
+                    "Edit an item's name, short or desc, e.g. `oedit sword name Rusty Sword`."
+                        .into(),
+                ))?;
+
+            Ok(Command::Oedit((
+                Target::parse(target),
+                field.into(),
+                value.into(),
+            )))
+        }
+    }
+}
+
+pub fn oedit(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    inventories: Query<Option<&Children>, With<Inventory>>,
+    mut items: Query<(Entity, &mut Depiction), With<Item>>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Keycard, &Parent, Option<&Children>), With<Online>>,
+    tiles: Query<&Children, With<Tile>>,
+) {
+    for command in commands.iter() {
+        if let Command::Oedit((target, field, value)) = &command.command {
+            let (client, keycard, tile, children) =
+                value_or_continue!(players.iter().find(|(c, _, _, _)| c.id == command.from));
+
+            if !keycard.can(BUILD) {
+                continue;
+            }
+
+            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
+            let inventory = children
+                .iter()
+                .flat_map(|children| children.iter())
+                .find_map(|child| inventories.get(*child).ok())
+                .flatten();
+
+            let found = {
+                let candidates = siblings
+                    .iter()
+                    .chain(inventory.iter().flat_map(|children| children.iter()))
+                    .filter_map(|entity| items.get(*entity).ok())
+                    .collect::<Vec<_>>();
+
+                target.resolve(&candidates)
+            };
+
+            let item = match found {
+                Ok(found) => found[0],
+                Err(TargetError::NotFound) => {
+                    outbox.send_text(client.id, format!("You don't see a {target} here."));
+
+                    continue;
+                }
+                Err(TargetError::Ambiguous(question)) => {
+                    outbox.send_text(client.id, question);
+
+                    continue;
+                }
+            };
+
+            let (_, mut depiction) = value_or_continue!(items.get_mut(item).ok());
+
+            let reply = match field.as_str() {
+                "name" => {
+                    depiction.name = value.clone();
+
+                    format!("Item name set to \"{value}\".")
+                }
+                "short" => {
+                    depiction.short_name = value.clone();
+
+                    format!("Item short name set to \"{value}\".")
+                }
+                "desc" => {
+                    depiction.description = value.clone();
+
+                    "Item description updated.".into()
+                }
+                _ => continue,
+            };
+
+            bevy.entity(item).insert(Unsaved);
+
+            outbox.send_text(client.id, reply);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn edits_item() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(oedit);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+        let sword = ItemBuilder::new().name("sword").tile(tile).build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "oedit sword name Rusty Sword");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Item name set to \"Rusty Sword\".");
+        assert_eq!(
+            app.world.get::<Depiction>(sword).unwrap().name,
+            "Rusty Sword"
+        );
+        assert!(app.world.get::<Unsaved>(sword).is_some());
+    }
+
+    #[test]
+    fn item_not_found() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(oedit);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "oedit shield desc A dented shield.");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You don't see a shield here.");
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    keycard::{Keycard, BUILD},
+    olc::components::Unsaved,
+    player::components::{Client, Online},
+    spatial::components::Tile,
+    value_or_continue,
+    visual::components::Sprite,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_redit(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| {
+        Regex::new(r"^redit(\s+(?P<field>name|desc|sprite)\s+(?P<value>.+))?$").unwrap()
+    });
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let (field, value) = captures
+                .name("field")
+                .zip(captures.name("value"))
+                .map(|(field, value)| (field.as_str(), value.as_str().trim()))
+                .ok_or(ParseError::InvalidArguments(
+                    "Edit the tile's name, desc or sprite, e.g. `redit name Market Square`.".into(),
+                ))?;
+
+            Ok(Command::Redit((field.into(), value.into())))
+        }
+    }
+}
+
+pub fn redit(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Keycard, &Parent), With<Online>>,
+    mut tiles: Query<(&mut Tile, &mut Sprite)>,
+) {
+    for command in commands.iter() {
+        if let Command::Redit((field, value)) = &command.command {
+            let (client, keycard, tile) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+
+            if !keycard.can(BUILD) {
+                continue;
+            }
+
+            let (mut tile_data, mut sprite) = value_or_continue!(tiles.get_mut(tile.get()).ok());
+
+            let reply = match field.as_str() {
+                "name" => {
+                    tile_data.name = value.clone();
+
+                    format!("Tile name set to \"{value}\".")
+                }
+                "desc" => {
+                    tile_data.description = value.clone();
+
+                    "Tile description updated.".into()
+                }
+                "sprite" if value.chars().count() != 1 => {
+                    outbox.send_text(client.id, "A sprite has to be a single character.");
+
+                    continue;
+                }
+                "sprite" => {
+                    sprite.character = value.clone();
+
+                    format!("Tile sprite set to \"{value}\".")
+                }
+                _ => continue,
+            };
+
+            bevy.entity(tile.get()).insert(Unsaved);
+
+            outbox.send_text(client.id, reply);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn edits_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(redit);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "redit name Market Square");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Tile name set to \"Market Square\".");
+
+        send_message(&mut app, client_id, "redit sprite +");
+        app.update();
+
+        assert_eq!(app.world.get::<Tile>(tile).unwrap().name, "Market Square");
+        assert_eq!(app.world.get::<Sprite>(tile).unwrap().character, "+");
+        assert!(app.world.get::<Unsaved>(tile).is_some());
+    }
+
+    #[test]
+    fn sprite_too_long() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(redit);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "redit sprite ##");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "A sprite has to be a single character.");
+        assert!(app.world.get::<Unsaved>(tile).is_none());
+    }
+
+    #[test]
+    fn missing_field() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(redit);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::builder())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "redit");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "Edit the tile's name, desc or sprite, e.g. `redit name Market Square`."
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use bevy::{
+    asset::{AssetPath, FileAssetIo},
+    prelude::*,
+};
+use bevy_nest::prelude::*;
+use bevy_proto::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    items::{bundles::ItemBundle, components::Item},
+    keycard::{Keycard, BUILD},
+    olc::{
+        components::Unsaved,
+        utils::{new_prototype, updated_prototype},
+    },
+    player::components::{Client, Online},
+    spatial::{
+        bundles::TileBundle,
+        components::{Position, Tile, Zone},
+    },
+    utils::prototype_id,
+    value_or_continue,
+    visual::components::{Depiction, Sprite},
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_zsave(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^zsave$").unwrap());
+
+    match regex.is_match(content) {
+        false => Err(ParseError::WrongCommand),
+        true => Ok(Command::Zsave),
+    }
+}
+
+/// Writes the tiles and items changed in the builder's zone back to their prototype files,
+/// giving dug tiles files of their own next to the zone's.
+pub fn zsave(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Prototype>>,
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    items: Query<(&Item, &Depiction)>,
+    mut outbox: EventWriter<Outbox>,
+    parents: Query<&Parent>,
+    players: Query<(&Client, &Keycard, &Parent), With<Online>>,
+    registry: Res<AppTypeRegistry>,
+    tiles: Query<(&Tile, &Sprite, &Position)>,
+    unsaved: Query<(Entity, Option<&Name>), With<Unsaved>>,
+    zones: Query<(&Zone, Option<&Name>)>,
+) {
+    for command in commands.iter() {
+        if let Command::Zsave = &command.command {
+            let (client, keycard, tile) =
+                value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+
+            if !keycard.can(BUILD) {
+                continue;
+            }
+
+            let zone = value_or_continue!(parents.get(tile.get()).ok()).get();
+            let (zone_data, zone_name) = value_or_continue!(zones.get(zone).ok());
+
+            let Some(zone_prototype) = zone_name.and_then(|name| {
+                let id = prototype_id(name);
+
+                assets
+                    .iter()
+                    .map(|(_, prototype)| prototype)
+                    .find(|prototype| prototype.id() == id)
+            }) else {
+                outbox.send_text(
+                    client.id,
+                    format!("{} wasn't spawned from a prototype.", zone_data.name),
+                );
+
+                continue;
+            };
+
+            let registry = registry.read();
+            let zone_path = AssetPath::from(zone_prototype.path()).path().to_path_buf();
+            let directory = zone_path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+            let in_zone = |entity: Entity| {
+                std::iter::successors(Some(entity), |entity| {
+                    parents.get(*entity).ok().map(|parent| parent.get())
+                })
+                .any(|ancestor| ancestor == zone)
+            };
+
+            let mut files: Vec<(PathBuf, Result<String, String>)> = vec![];
+            let mut saved = vec![];
+            let mut dug = vec![];
+
+            for (entity, name) in unsaved.iter().filter(|(entity, _)| in_zone(*entity)) {
+                let prototype = name.and_then(|name| {
+                    let id = prototype_id(name);
+
+                    assets
+                        .iter()
+                        .map(|(_, prototype)| prototype)
+                        .find(|prototype| prototype.id() == id)
+                });
+
+                if let Ok((tile, sprite, position)) = tiles.get(entity) {
+                    let bundle = TileBundle {
+                        tile: Tile {
+                            name: tile.name.clone(),
+                            description: tile.description.clone(),
+                        },
+                        sprite: Sprite {
+                            character: sprite.character.clone(),
+                        },
+                        position: Position(position.0),
+                    };
+
+                    match prototype {
+                        Some(prototype) => files.push((
+                            AssetPath::from(prototype.path()).path().to_path_buf(),
+                            updated_prototype(prototype, &assets, &[&bundle], &[], &registry),
+                        )),
+                        None => {
+                            let IVec3 { x, y, z } = position.0;
+                            let id = format!("{}.{x}_{y}_{z}", zone_prototype.id());
+                            let path = directory.join(format!("{x}_{y}_{z}.prototype.ron"));
+
+                            bevy.entity(entity)
+                                .insert(Name::new(format!("{id} (Prototype)")));
+
+                            dug.push(format!("/{}", path.display()));
+                            files.push((path, new_prototype(&id, &[&bundle], &registry)));
+                        }
+                    }
+                } else if let (Ok((item, depiction)), Some(prototype)) =
+                    (items.get(entity), prototype)
+                {
+                    let copy = || Depiction {
+                        name: depiction.name.clone(),
+                        short_name: depiction.short_name.clone(),
+                        description: depiction.description.clone(),
+                        tags: depiction.tags.clone(),
+                        visible: depiction.visible,
+                    };
+
+                    // Items are either made of an `ItemBundle` or an `Item` and `Depiction`,
+                    // and only the schematics a prototype has are replaced.
+                    let depiction = copy();
+                    let bundle = ItemBundle {
+                        item: Item { size: item.size },
+                        depiction: copy(),
+                    };
+
+                    files.push((
+                        AssetPath::from(prototype.path()).path().to_path_buf(),
+                        updated_prototype(
+                            prototype,
+                            &assets,
+                            &[&bundle, &depiction],
+                            &[],
+                            &registry,
+                        ),
+                    ));
+                } else {
+                    continue;
+                }
+
+                saved.push(entity);
+            }
+
+            if !dug.is_empty() {
+                files.push((
+                    zone_path.clone(),
+                    updated_prototype(zone_prototype, &assets, &[], &dug, &registry),
+                ));
+            }
+
+            if files.is_empty() {
+                outbox.send_text(
+                    client.id,
+                    format!("There's nothing to save in {}.", zone_data.name),
+                );
+
+                continue;
+            }
+
+            let root = FileAssetIo::get_base_path().join("assets");
+
+            let written = files.into_iter().try_for_each(|(path, content)| {
+                let content = content?;
+                let full_path = root.join(&path);
+
+                fs::create_dir_all(full_path.parent().unwrap_or(&root))
+                    .and_then(|_| fs::write(&full_path, content))
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+
+                asset_server.reload_asset(path);
+
+                Ok::<_, String>(())
+            });
+
+            if let Err(err) = written {
+                error!("Failed to save zone {}: {}", zone_data.name, err);
+
+                outbox.send_text(
+                    client.id,
+                    format!("Failed to save {}: {}", zone_data.name, err),
+                );
+
+                continue;
+            }
+
+            for entity in saved.iter() {
+                bevy.entity(*entity).remove::<Unsaved>();
+            }
+
+            info!("Saved zone {}", zone_data.name);
+
+            outbox.send_text(
+                client.id,
+                format!(
+                    "Saved {} {} in {}.",
+                    saved.len(),
+                    if saved.len() == 1 {
+                        "change"
+                    } else {
+                        "changes"
+                    },
+                    zone_data.name
+                ),
+            );
+        }
+    }
+}
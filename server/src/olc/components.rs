@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// A tile or item a builder has changed since its zone was last saved with `zsave`.
+#[derive(Component)]
+pub struct Unsaved;
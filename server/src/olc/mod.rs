@@ -0,0 +1,4 @@
+pub mod commands;
+pub mod components;
+pub mod plugin;
+mod utils;
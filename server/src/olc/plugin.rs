@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use super::commands::{dig::*, oedit::*, redit::*, zsave::*};
+
+pub struct OlcPlugin;
+
+impl Plugin for OlcPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems((dig, redit, oedit, zsave));
+    }
+}
@@ -0,0 +1,204 @@
+use bevy::{
+    asset::AssetPath,
+    prelude::*,
+    reflect::{serde::TypedReflectSerializer, TypeRegistryInternal},
+};
+use bevy_proto::{backend::children::PrototypicalChild, prelude::*};
+use ron::ser::PrettyConfig;
+use serde::{Serialize, Serializer};
+
+/// A prototype file as bevy_proto reads it.
+#[derive(Serialize)]
+struct PrototypeFile<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    templates: Vec<String>,
+    schematics: SchematicMap<'a>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<String>,
+    #[serde(skip_serializing_if = "is_true")]
+    entity: bool,
+}
+
+/// Schematic inputs keyed by type name, serialized through reflection.
+struct SchematicMap<'a> {
+    schematics: Vec<(&'a str, &'a dyn Reflect)>,
+    registry: &'a TypeRegistryInternal,
+}
+
+impl<'a> Serialize for SchematicMap<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.schematics
+                .iter()
+                .map(|(name, input)| (name, TypedReflectSerializer::new(*input, self.registry))),
+        )
+    }
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
+}
+
+/// An asset path as written in prototype files, from the root of `assets/`.
+fn absolute(path: &AssetPath) -> String {
+    format!("/{}", path.path().display())
+}
+
+/// RON for a prototype that only exists in the world so far, made of `schematics`.
+pub fn new_prototype(
+    name: &str,
+    schematics: &[&dyn Reflect],
+    registry: &TypeRegistryInternal,
+) -> Result<String, String> {
+    to_ron(&PrototypeFile {
+        name,
+        templates: vec![],
+        schematics: SchematicMap {
+            schematics: schematics
+                .iter()
+                .map(|input| (input.type_name(), *input))
+                .collect(),
+            registry,
+        },
+        children: vec![],
+        entity: true,
+    })
+}
+
+/// RON for a loaded prototype, with each of `overrides` replacing the schematic of the same
+/// type and `extra_children` added after its own children.
+pub fn updated_prototype(
+    prototype: &Prototype,
+    assets: &Assets<Prototype>,
+    overrides: &[&dyn Reflect],
+    extra_children: &[String],
+    registry: &TypeRegistryInternal,
+) -> Result<String, String> {
+    let mut schematics = prototype
+        .schematics()
+        .iter()
+        .map(|(name, schematic)| {
+            let input = overrides
+                .iter()
+                .find(|input| input.type_name() == name.as_ref())
+                .copied()
+                .unwrap_or(schematic.input());
+
+            (name.as_ref(), input)
+        })
+        .collect::<Vec<_>>();
+
+    schematics.sort_by_key(|(name, _)| *name);
+
+    let templates = prototype
+        .templates()
+        .iter()
+        .flat_map(|templates| templates.iter())
+        .map(|(path, _)| absolute(path.asset_path()))
+        .collect();
+
+    let mut children = vec![];
+
+    for child in prototype
+        .children()
+        .iter()
+        .flat_map(|children| children.iter())
+    {
+        let path = assets
+            .get(child.handle())
+            .map(|child| AssetPath::from(child.path()))
+            .filter(|path| path.label().is_none() && child.merge_key().is_none())
+            .ok_or_else(|| format!("{} has inline or merged children", prototype.id()))?;
+
+        children.push(absolute(&path));
+    }
+
+    children.extend(
+        extra_children
+            .iter()
+            .filter(|child| !children.contains(child))
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+
+    to_ron(&PrototypeFile {
+        name: prototype.id(),
+        templates,
+        schematics: SchematicMap {
+            schematics,
+            registry,
+        },
+        children,
+        entity: prototype.requires_entity(),
+    })
+}
+
+fn to_ron(file: &PrototypeFile) -> Result<String, String> {
+    ron::ser::to_string_pretty(file, PrettyConfig::default())
+        .map(|ron| ron + "\n")
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        spatial::{
+            bundles::TileBundle,
+            components::{Position, Tile},
+        },
+        visual::components::Sprite,
+    };
+
+    use super::*;
+
+    #[test]
+    fn writes_new_prototype() {
+        let mut registry = TypeRegistryInternal::default();
+        registry.register::<TileBundle>();
+        registry.register::<Tile>();
+        registry.register::<Sprite>();
+        registry.register::<Position>();
+        registry.register::<IVec3>();
+
+        let tile = TileBundle {
+            tile: Tile {
+                name: "Well".into(),
+                description: "A \"deep\" well.".into(),
+            },
+            sprite: Sprite {
+                character: "o".into(),
+            },
+            position: Position(IVec3::new(1, -2, 0)),
+        };
+
+        let ron = new_prototype("trinus.well", &[&tile], &registry).unwrap();
+
+        assert_eq!(
+            ron,
+            [
+                "(",
+                "    name: \"trinus.well\",",
+                "    schematics: {",
+                "        \"server::spatial::bundles::TileBundle\": (",
+                "            tile: (",
+                "                name: \"Well\",",
+                "                description: \"A \\\"deep\\\" well.\",",
+                "            ),",
+                "            sprite: (",
+                "                character: \"o\",",
+                "            ),",
+                "            position: ((",
+                "                x: 1,",
+                "                y: -2,",
+                "                z: 0,",
+                "            )),",
+                "        ),",
+                "    },",
+                ")",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
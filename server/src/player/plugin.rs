@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::db::pool::DatabasePool;
+
 use super::{
     commands::{config::*, describe::*},
     events::Prompt,
@@ -15,9 +17,9 @@ impl Plugin for PlayerPlugin {
         app.insert_resource(PromptTimer(Timer::from_seconds(60.0, TimerMode::Repeating)));
 
         app.add_systems((
-            config,
+            config.run_if(resource_exists::<DatabasePool>()),
             handle_save_config_task,
-            describe,
+            describe.run_if(resource_exists::<DatabasePool>()),
             handle_save_description_task,
             send_prompt,
             send_prompt_on_timer,
@@ -0,0 +1,2 @@
+pub mod quest;
+pub mod quests;
@@ -0,0 +1,156 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    player::components::{Client, Online},
+    quest::components::{Journal, Quest},
+    utils::prototype_id,
+    value_or_continue,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_quest(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^quest( (?P<name>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let name = captures
+                .name("name")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Which quest?".into()))?;
+
+            Ok(Command::Quest(name.to_lowercase()))
+        }
+    }
+}
+
+pub fn quest(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, Option<&Journal>), With<Online>>,
+    quests: Query<(&Name, &Quest)>,
+) {
+    for command in commands.iter() {
+        if let Command::Quest(name) = &command.command {
+            let (client, journal) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            let found = journal
+                .iter()
+                .flat_map(|journal| journal.0.iter())
+                .find_map(|progress| {
+                    quests
+                        .iter()
+                        .find(|(id, quest)| {
+                            prototype_id(id) == progress.quest
+                                && quest.name.to_lowercase().contains(name)
+                        })
+                        .map(|(_, quest)| (progress, quest))
+                });
+
+            let Some((progress, quest)) = found else {
+                outbox.send_text(
+                    client.id,
+                    format!("Your journal has no quest called {name}."),
+                );
+
+                continue;
+            };
+
+            let mut lines = vec![quest.name.clone(), quest.description.clone(), "".into()];
+
+            match quest.stages.get(progress.stage) {
+                Some(stage) if !progress.completed => {
+                    lines.push(stage.description.clone());
+                    lines.extend(stage.objectives.iter().enumerate().map(|(i, objective)| {
+                        format!(
+                            "  {} ({}/{})",
+                            objective.description,
+                            progress.counts.get(i).copied().unwrap_or(0),
+                            objective.goal.count()
+                        )
+                    }));
+                }
+                _ => lines.push("You have completed this quest.".into()),
+            }
+
+            outbox.send_text(client.id, lines.join("\n"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        quest::components::{Goal, QuestProgress},
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            quest_builder::QuestBuilder,
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn shows_progress() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(quest);
+
+        QuestBuilder::new()
+            .id("quests.rats")
+            .name("Rat Problem")
+            .description("The innkeeper wants the cellar cleared.")
+            .stage(
+                "Kill the rats in the cellar.",
+                vec![(
+                    "Rats killed",
+                    Goal::Kill {
+                        npc: "npcs.rat".into(),
+                        count: 5,
+                    },
+                )],
+            )
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new().build(&mut app);
+
+        let mut progress = QuestProgress::new("quests.rats", 1);
+        progress.counts = vec![2];
+
+        app.world.entity_mut(player).insert(Journal(vec![progress]));
+
+        send_message(&mut app, client_id, "quest rat");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "Rat Problem\nThe innkeeper wants the cellar cleared.\n\nKill the rats in the cellar.\n  Rats killed (2/5)"
+        );
+    }
+
+    #[test]
+    fn unknown_quest() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(quest);
+
+        let (_, client_id, _) = PlayerBuilder::new().build(&mut app);
+
+        send_message(&mut app, client_id, "quest dragons");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Your journal has no quest called dragons.");
+    }
+}
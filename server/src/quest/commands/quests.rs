@@ -0,0 +1,136 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    player::components::{Client, Online},
+    quest::{
+        components::{Journal, Quest},
+        systems::find_quest,
+    },
+    value_or_continue,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_quests(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^(quests|journal)$").unwrap());
+
+    match regex.is_match(content) {
+        false => Err(ParseError::WrongCommand),
+        true => Ok(Command::Quests),
+    }
+}
+
+pub fn quests(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, Option<&Journal>), With<Online>>,
+    quests: Query<(&Name, &Quest)>,
+) {
+    for command in commands.iter() {
+        if let Command::Quests = &command.command {
+            let (client, journal) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            let names = |completed: bool| {
+                journal
+                    .iter()
+                    .flat_map(|journal| journal.0.iter())
+                    .filter(|progress| progress.completed == completed)
+                    .filter_map(|progress| find_quest(&quests, &progress.quest))
+                    .map(|quest| format!("  {}", quest.name))
+                    .collect::<Vec<_>>()
+            };
+
+            let (active, completed) = (names(false), names(true));
+
+            if active.is_empty() && completed.is_empty() {
+                outbox.send_text(client.id, "You have not taken on any quests.");
+
+                continue;
+            }
+
+            let mut lines = vec![];
+
+            if !active.is_empty() {
+                lines.push("Active quests:".to_string());
+                lines.extend(active);
+            }
+
+            if !completed.is_empty() {
+                lines.push("Completed quests:".to_string());
+                lines.extend(completed);
+            }
+
+            outbox.send_text(client.id, lines.join("\n"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        quest::components::QuestProgress,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            quest_builder::QuestBuilder,
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn lists_journal() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(quests);
+
+        QuestBuilder::new()
+            .id("quests.rats")
+            .name("Rat Problem")
+            .build(&mut app);
+        QuestBuilder::new()
+            .id("quests.ring")
+            .name("The Lost Ring")
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new().build(&mut app);
+
+        let mut ring = QuestProgress::new("quests.ring", 0);
+        ring.completed = true;
+
+        app.world
+            .entity_mut(player)
+            .insert(Journal(vec![QuestProgress::new("quests.rats", 1), ring]));
+
+        send_message(&mut app, client_id, "quests");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "Active quests:\n  Rat Problem\nCompleted quests:\n  The Lost Ring"
+        );
+    }
+
+    #[test]
+    fn empty_journal() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(quests);
+
+        let (_, client_id, _) = PlayerBuilder::new().build(&mut app);
+
+        send_message(&mut app, client_id, "quests");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You have not taken on any quests.");
+    }
+}
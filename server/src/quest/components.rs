@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+/// A quest as defined by its prototype, made of stages that are completed in order.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Quest {
+    pub name: String,
+    pub description: String,
+    pub stages: Vec<QuestStage>,
+    pub rewards: Vec<QuestReward>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct QuestStage {
+    /// What the journal says about the stage while it's under way.
+    pub description: String,
+    /// Every objective has to be met before moving on to the next stage.
+    pub objectives: Vec<Objective>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct Objective {
+    pub description: String,
+    pub goal: Goal,
+}
+
+/// What an objective is waiting on. NPCs, items and tiles are named by prototype.
+#[derive(Reflect, FromReflect)]
+pub enum Goal {
+    Kill {
+        npc: String,
+        count: u32,
+    },
+    /// Hand an item over by talking to the NPC while carrying it.
+    Deliver {
+        item: String,
+        npc: String,
+    },
+    Visit {
+        tile: String,
+    },
+    Talk {
+        npc: String,
+    },
+}
+
+#[derive(Reflect, FromReflect)]
+pub enum QuestReward {
+    /// An item prototype placed in the player's inventory.
+    Item(String),
+}
+
+impl Goal {
+    /// How many times the goal has to be met.
+    pub fn count(&self) -> u32 {
+        match self {
+            Self::Kill { count, .. } => *count,
+            _ => 1,
+        }
+    }
+}
+
+/// The quests a character has started, loaded from the database once they log in.
+#[derive(Component, Default)]
+pub struct Journal(pub Vec<QuestProgress>);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuestProgress {
+    /// Prototype name of the quest.
+    pub quest: String,
+    pub stage: usize,
+    /// How far along each objective of the current stage is.
+    pub counts: Vec<u32>,
+    pub completed: bool,
+}
+
+impl Journal {
+    pub fn get(&self, quest: &str) -> Option<&QuestProgress> {
+        self.0.iter().find(|progress| progress.quest == quest)
+    }
+}
+
+impl QuestProgress {
+    /// A freshly started quest, at the first stage with this many objectives.
+    pub fn new(quest: &str, objectives: usize) -> Self {
+        Self {
+            quest: quest.into(),
+            stage: 0,
+            counts: vec![0; objectives],
+            completed: false,
+        }
+    }
+}
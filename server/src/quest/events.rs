@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Things players do that quests care about.
+pub enum QuestEvent {
+    /// The player took on a quest, e.g. by picking a dialogue choice that offers it.
+    Accepted {
+        player: Entity,
+        quest: String,
+    },
+    TalkedTo {
+        player: Entity,
+        npc: Entity,
+    },
+}
+
+/// Sent once a player finishes the last stage of a quest, so they can be rewarded.
+pub struct QuestCompleted {
+    pub player: Entity,
+    pub quest: String,
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+use crate::db::pool::DatabasePool;
+
+use super::{
+    commands::{quest::*, quests::*},
+    components::*,
+    events::{QuestCompleted, QuestEvent},
+    systems::*,
+};
+
+pub struct QuestPlugin;
+
+impl Plugin for QuestPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Quest>()
+            .register_type::<QuestStage>()
+            .register_type::<Vec<QuestStage>>()
+            .register_type::<Objective>()
+            .register_type::<Vec<Objective>>()
+            .register_type::<Goal>()
+            .register_type::<QuestReward>()
+            .register_type::<Vec<QuestReward>>();
+
+        app.add_event::<QuestEvent>().add_event::<QuestCompleted>();
+
+        app.add_systems((quest, quests, update_journals, reward_quests));
+        app.add_systems((
+            load_journals.run_if(resource_exists::<DatabasePool>()),
+            handle_load_journal_task,
+            save_journals.run_if(resource_exists::<DatabasePool>()),
+            handle_save_journal_task,
+        ));
+
+        app.add_system(spawn_quests.run_if(prototype_ready("quests").and_then(run_once())));
+    }
+}
@@ -0,0 +1,683 @@
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use bevy_nest::prelude::*;
+use bevy_proto::prelude::*;
+use futures_lite::future;
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    combat::events::Killed,
+    db::{models::CharacterQuestModel, pool::DatabasePool},
+    items::components::{Inventory, Item},
+    player::components::{Character, Client, Online},
+    utils::prototype_id,
+    value_or_continue,
+};
+
+use super::{
+    components::{Goal, Journal, Quest, QuestProgress, QuestReward},
+    events::{QuestCompleted, QuestEvent},
+};
+
+#[derive(Component)]
+pub struct LoadJournalTask(Task<Result<(Entity, Vec<CharacterQuestModel>), sqlx::Error>>);
+
+#[derive(Component)]
+pub struct SaveJournalTask(Task<Result<(), sqlx::Error>>);
+
+pub fn spawn_quests(mut commands: ProtoCommands) {
+    commands.spawn("quests");
+}
+
+/// Finds a quest by its prototype name.
+pub fn find_quest<'a>(quests: &'a Query<(&Name, &Quest)>, id: &str) -> Option<&'a Quest> {
+    quests
+        .iter()
+        .find(|(name, _)| prototype_id(name) == id)
+        .map(|(_, quest)| quest)
+}
+
+pub fn update_journals(
+    mut bevy: Commands,
+    mut completed: EventWriter<QuestCompleted>,
+    mut events: EventReader<QuestEvent>,
+    mut kills: EventReader<Killed>,
+    mut outbox: EventWriter<Outbox>,
+    mut players: Query<
+        (
+            Entity,
+            &Client,
+            &mut Journal,
+            Option<&Children>,
+            Ref<Parent>,
+        ),
+        With<Online>,
+    >,
+    inventories: Query<&Children, With<Inventory>>,
+    items: Query<&Name, With<Item>>,
+    names: Query<&Name>,
+    quests: Query<(&Name, &Quest)>,
+) {
+    let prototype = |entity: Entity| {
+        names
+            .get(entity)
+            .ok()
+            .map(|name| prototype_id(name).to_string())
+    };
+
+    for event in events.iter() {
+        match event {
+            QuestEvent::Accepted { player, quest: id } => {
+                let (_, client, mut journal, _, _) =
+                    value_or_continue!(players.get_mut(*player).ok());
+
+                let Some(quest) = find_quest(&quests, id) else {
+                    warn!("Quest {} does not exist", id);
+
+                    continue;
+                };
+
+                if journal.get(id).is_some() {
+                    outbox.send_text(
+                        client.id,
+                        format!("You have already taken on {}.", quest.name),
+                    );
+
+                    continue;
+                }
+
+                let Some(stage) = quest.stages.first() else {
+                    warn!("Quest {} has no stages", id);
+
+                    continue;
+                };
+
+                journal
+                    .0
+                    .push(QuestProgress::new(id, stage.objectives.len()));
+
+                outbox.send_text(
+                    client.id,
+                    format!("New quest: {}. {}", quest.name, stage.description),
+                );
+            }
+            QuestEvent::TalkedTo { player, npc } => {
+                let (player, client, mut journal, children, _) =
+                    value_or_continue!(players.get_mut(*player).ok());
+                let talked_to = value_or_continue!(prototype(*npc));
+
+                let mut carried = children
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .filter_map(|child| inventories.get(*child).ok())
+                    .flat_map(|inventory| inventory.iter())
+                    .filter_map(|item| {
+                        items
+                            .get(*item)
+                            .ok()
+                            .map(|name| (*item, prototype_id(name).to_string()))
+                    })
+                    .collect::<Vec<_>>();
+                let mut delivered = vec![];
+
+                let (messages, finished) = advance_all(&mut journal, &quests, |goal| match goal {
+                    Goal::Talk { npc } => *npc == talked_to,
+                    Goal::Deliver { item, npc } if *npc == talked_to => {
+                        let Some(index) = carried.iter().position(|(_, name)| name == item) else {
+                            return false;
+                        };
+
+                        delivered.push(carried.remove(index).0);
+
+                        true
+                    }
+                    _ => false,
+                });
+
+                for item in delivered {
+                    bevy.entity(item).despawn_recursive();
+                }
+
+                notify(&mut outbox, client, messages);
+                complete(&mut completed, player, finished);
+            }
+        }
+    }
+
+    for kill in kills.iter() {
+        let (player, client, mut journal, _, _) =
+            value_or_continue!(players.get_mut(kill.killer).ok());
+
+        let (messages, finished) = advance_all(
+            &mut journal,
+            &quests,
+            |goal| matches!(goal, Goal::Kill { npc, .. } if *npc == kill.npc),
+        );
+
+        notify(&mut outbox, client, messages);
+        complete(&mut completed, player, finished);
+    }
+
+    for (player, client, mut journal, _, tile) in players.iter_mut() {
+        if !tile.is_changed() {
+            continue;
+        }
+
+        let visited = value_or_continue!(prototype(tile.get()));
+
+        let (messages, finished) = advance_all(
+            &mut journal,
+            &quests,
+            |goal| matches!(goal, Goal::Visit { tile } if *tile == visited),
+        );
+
+        notify(&mut outbox, client, messages);
+        complete(&mut completed, player, finished);
+    }
+}
+
+/// Counts towards the objectives of every active quest's current stage that `matches`,
+/// moving quests on to their next stage once all its objectives are met. Returns what to
+/// tell the player and the quests that were just finished.
+fn advance_all(
+    journal: &mut Mut<Journal>,
+    quests: &Query<(&Name, &Quest)>,
+    mut matches: impl FnMut(&Goal) -> bool,
+) -> (Vec<String>, Vec<String>) {
+    let mut messages = vec![];
+    let mut finished = vec![];
+
+    let active = journal
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_, progress)| !progress.completed)
+        .filter_map(|(index, progress)| Some((index, find_quest(quests, &progress.quest)?)))
+        .collect::<Vec<_>>();
+
+    for (index, quest) in active {
+        let Some(stage) = quest.stages.get(journal.0[index].stage) else {
+            continue;
+        };
+
+        let updates = stage
+            .objectives
+            .iter()
+            .enumerate()
+            .filter(|(i, objective)| {
+                journal.0[index].counts.get(*i).copied().unwrap_or(0) < objective.goal.count()
+                    && matches(&objective.goal)
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        if updates.is_empty() {
+            continue;
+        }
+
+        // Only borrowed mutably once something moved, so untouched journals aren't saved.
+        let progress = &mut journal.0[index];
+        progress.counts.resize(stage.objectives.len(), 0);
+
+        for i in updates {
+            progress.counts[i] += 1;
+
+            let objective = &stage.objectives[i];
+
+            messages.push(format!(
+                "{}: {} ({}/{}).",
+                quest.name,
+                objective.description,
+                progress.counts[i],
+                objective.goal.count()
+            ));
+        }
+
+        let done = stage
+            .objectives
+            .iter()
+            .zip(progress.counts.iter())
+            .all(|(objective, count)| *count >= objective.goal.count());
+
+        if !done {
+            continue;
+        }
+
+        progress.stage += 1;
+
+        match quest.stages.get(progress.stage) {
+            Some(next) => {
+                progress.counts = vec![0; next.objectives.len()];
+
+                messages.push(format!("{}: {}", quest.name, next.description));
+            }
+            None => {
+                progress.counts = vec![];
+                progress.completed = true;
+
+                messages.push(format!("Quest complete: {}.", quest.name));
+                finished.push(progress.quest.clone());
+            }
+        }
+    }
+
+    (messages, finished)
+}
+
+fn notify(outbox: &mut EventWriter<Outbox>, client: &Client, messages: Vec<String>) {
+    for message in messages {
+        outbox.send_text(client.id, message);
+    }
+}
+
+fn complete(completed: &mut EventWriter<QuestCompleted>, player: Entity, finished: Vec<String>) {
+    for quest in finished {
+        completed.send(QuestCompleted { player, quest });
+    }
+}
+
+pub fn reward_quests(
+    mut bevy: Commands,
+    mut completed: EventReader<QuestCompleted>,
+    mut outbox: EventWriter<Outbox>,
+    mut proto: ProtoCommands,
+    inventories: Query<Entity, With<Inventory>>,
+    players: Query<(&Client, &Children), With<Online>>,
+    quests: Query<(&Name, &Quest)>,
+) {
+    for event in completed.iter() {
+        let (client, children) = value_or_continue!(players.get(event.player).ok());
+        let quest = value_or_continue!(find_quest(&quests, &event.quest));
+        let inventory = value_or_continue!(children
+            .iter()
+            .find_map(|child| inventories.get(*child).ok()));
+
+        if quest.rewards.is_empty() {
+            continue;
+        }
+
+        for reward in quest.rewards.iter() {
+            match reward {
+                QuestReward::Item(item) => {
+                    let item = proto.spawn(item.as_str()).id();
+
+                    bevy.entity(inventory).add_child(item);
+                }
+            }
+        }
+
+        outbox.send_text(client.id, "You receive your reward.");
+    }
+}
+
+pub fn load_journals(
+    database: Res<DatabasePool>,
+    mut bevy: Commands,
+    players: Query<(Entity, &Character), Added<Online>>,
+) {
+    for (player, character) in players.iter() {
+        bevy.spawn(LoadJournalTask(spawn_load_journal_task(
+            database.0.clone(),
+            player,
+            character.id,
+        )));
+    }
+}
+
+fn spawn_load_journal_task(
+    pool: Pool<Postgres>,
+    player: Entity,
+    character_id: i64,
+) -> Task<Result<(Entity, Vec<CharacterQuestModel>), sqlx::Error>> {
+    AsyncComputeTaskPool::get().spawn(async move {
+        let quests = sqlx::query_as::<_, CharacterQuestModel>(
+            "SELECT quest, stage, progress, completed FROM character_quests WHERE character_id = $1",
+        )
+        .bind(character_id)
+        .fetch_all(&pool)
+        .await?;
+
+        Ok((player, quests))
+    })
+}
+
+pub fn handle_load_journal_task(
+    mut bevy: Commands,
+    mut tasks: Query<(Entity, &mut LoadJournalTask)>,
+    players: Query<Entity, With<Online>>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(result) = future::block_on(future::poll_once(&mut task.0)) {
+            bevy.entity(entity).remove::<LoadJournalTask>();
+
+            let (player, quests) = match result {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    error!("Failed to load journal: {}", err);
+
+                    continue;
+                }
+            };
+
+            if !players.contains(player) {
+                continue;
+            }
+
+            let journal = quests
+                .into_iter()
+                .map(|model| QuestProgress {
+                    quest: model.quest,
+                    stage: model.stage as usize,
+                    counts: model.progress.into_iter().map(|c| c as u32).collect(),
+                    completed: model.completed,
+                })
+                .collect();
+
+            bevy.entity(player).insert(Journal(journal));
+        }
+    }
+}
+
+pub fn save_journals(
+    database: Res<DatabasePool>,
+    mut bevy: Commands,
+    players: Query<(&Character, Ref<Journal>)>,
+) {
+    for (character, journal) in players.iter() {
+        if !journal.is_changed() || journal.is_added() {
+            continue;
+        }
+
+        bevy.spawn(SaveJournalTask(spawn_save_journal_task(
+            database.0.clone(),
+            character.id,
+            journal.0.clone(),
+        )));
+    }
+}
+
+fn spawn_save_journal_task(
+    pool: Pool<Postgres>,
+    character_id: i64,
+    journal: Vec<QuestProgress>,
+) -> Task<Result<(), sqlx::Error>> {
+    AsyncComputeTaskPool::get().spawn(async move {
+        let mut transaction = pool.begin().await?;
+
+        for progress in journal {
+            sqlx::query(
+                "INSERT INTO character_quests (character_id, quest, stage, progress, completed)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (character_id, quest) DO UPDATE
+                SET stage = $3, progress = $4, completed = $5, updated_at = NOW()",
+            )
+            .bind(character_id)
+            .bind(&progress.quest)
+            .bind(progress.stage as i32)
+            .bind(
+                progress
+                    .counts
+                    .iter()
+                    .map(|c| *c as i32)
+                    .collect::<Vec<_>>(),
+            )
+            .bind(progress.completed)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    })
+}
+
+pub fn handle_save_journal_task(
+    mut bevy: Commands,
+    mut tasks: Query<(Entity, &mut SaveJournalTask)>,
+) {
+    for (entity, mut task) in &mut tasks {
+        if let Some(result) = future::block_on(future::poll_once(&mut task.0)) {
+            if let Err(err) = result {
+                error!("Failed to save journal: {}", err);
+            }
+
+            bevy.entity(entity).remove::<SaveJournalTask>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        npc_builder::NpcBuilder,
+        player_builder::PlayerBuilder,
+        quest_builder::QuestBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::get_message_content,
+    };
+
+    use super::*;
+
+    fn rat_quest() -> QuestBuilder {
+        QuestBuilder::new()
+            .id("quests.rats")
+            .name("Rat Problem")
+            .stage(
+                "Clear the cellar of rats.",
+                vec![(
+                    "Rats killed",
+                    Goal::Kill {
+                        npc: "npcs.rat".into(),
+                        count: 2,
+                    },
+                )],
+            )
+            .stage(
+                "Tell the innkeeper the cellar is clear.",
+                vec![(
+                    "Innkeeper told",
+                    Goal::Talk {
+                        npc: "npcs.innkeeper".into(),
+                    },
+                )],
+            )
+    }
+
+    #[test]
+    fn accepts_quest() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(update_journals);
+
+        rat_quest().build(&mut app);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.world.entity_mut(player).insert(Journal::default());
+        app.world.send_event(QuestEvent::Accepted {
+            player,
+            quest: "quests.rats".into(),
+        });
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "New quest: Rat Problem. Clear the cellar of rats.");
+        assert!(app
+            .world
+            .get::<Journal>(player)
+            .unwrap()
+            .get("quests.rats")
+            .is_some_and(|progress| !progress.completed));
+    }
+
+    #[test]
+    fn advances_through_stages() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(update_journals);
+
+        rat_quest().build(&mut app);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let innkeeper = NpcBuilder::new()
+            .prototype("npcs.innkeeper")
+            .tile(tile)
+            .build(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.world
+            .entity_mut(player)
+            .insert(Journal(vec![QuestProgress::new("quests.rats", 1)]));
+
+        app.update();
+        app.update();
+
+        app.world.send_event(Killed {
+            killer: player,
+            npc: "npcs.rat".into(),
+        });
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Rat Problem: Rats killed (1/2).");
+
+        app.world.send_event(Killed {
+            killer: player,
+            npc: "npcs.rat".into(),
+        });
+        app.update();
+        app.update();
+
+        let journal = app.world.get::<Journal>(player).unwrap();
+
+        assert_eq!(journal.get("quests.rats").unwrap().stage, 1);
+
+        app.world.send_event(QuestEvent::TalkedTo {
+            player,
+            npc: innkeeper,
+        });
+        app.update();
+
+        assert!(app
+            .world
+            .get::<Journal>(player)
+            .unwrap()
+            .get("quests.rats")
+            .is_some_and(|progress| progress.completed));
+        assert_eq!(app.world.resource::<Events<QuestCompleted>>().len(), 1);
+    }
+
+    #[test]
+    fn delivers_items() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(update_journals);
+
+        QuestBuilder::new()
+            .id("quests.ring")
+            .name("The Lost Ring")
+            .stage(
+                "Return the ring to the guard.",
+                vec![(
+                    "Ring returned",
+                    Goal::Deliver {
+                        item: "items.ring".into(),
+                        npc: "npcs.guard".into(),
+                    },
+                )],
+            )
+            .build(&mut app);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let guard = NpcBuilder::new()
+            .prototype("npcs.guard")
+            .tile(tile)
+            .build(&mut app);
+        let ring = ItemBuilder::new().prototype("items.ring").build(&mut app);
+
+        let (player, _, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(ring);
+        app.world
+            .entity_mut(player)
+            .insert(Journal(vec![QuestProgress::new("quests.ring", 1)]));
+
+        app.world
+            .send_event(QuestEvent::TalkedTo { player, npc: guard });
+        app.update();
+
+        assert!(app.world.get_entity(ring).is_none());
+        assert!(app
+            .world
+            .get::<Journal>(player)
+            .unwrap()
+            .get("quests.ring")
+            .is_some_and(|progress| progress.completed));
+    }
+
+    #[test]
+    fn visits_tiles() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(update_journals);
+
+        QuestBuilder::new()
+            .id("quests.well")
+            .name("Down the Well")
+            .stage(
+                "Find the old well.",
+                vec![(
+                    "Well found",
+                    Goal::Visit {
+                        tile: "trinus.well".into(),
+                    },
+                )],
+            )
+            .stage("Climb down.", vec![])
+            .build(&mut app);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, zone);
+        let well = TileBuilder::new()
+            .prototype("trinus.well")
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        app.world
+            .entity_mut(player)
+            .insert(Journal(vec![QuestProgress::new("quests.well", 1)]));
+        app.update();
+        app.update();
+
+        app.world.entity_mut(player).set_parent(well);
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "Down the Well: Well found (1/1).");
+        assert_eq!(
+            app.world
+                .get::<Journal>(player)
+                .unwrap()
+                .get("quests.well")
+                .unwrap()
+                .stage,
+            1
+        );
+    }
+}
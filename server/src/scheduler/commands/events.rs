@@ -0,0 +1,205 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    keycard::{Keycard, EVENTS},
+    player::components::{Client, Online},
+    scheduler::{components::WorldEvent, events::TriggerEvent, resources::Scheduler},
+    utils::prototype_id,
+    value_or_continue,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_events(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^events( trigger( (?P<event>.*))?)?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            if captures.get(1).is_none() {
+                return Ok(Command::Events(None));
+            }
+
+            let event = captures
+                .name("event")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Trigger which event?".into()))?;
+
+            Ok(Command::Events(Some(event.into())))
+        }
+    }
+}
+
+pub fn events(
+    mut commands: EventReader<ParsedCommand>,
+    events: Query<(&Name, &WorldEvent)>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Keycard), With<Online>>,
+    scheduler: Res<Scheduler>,
+    mut triggers: EventWriter<TriggerEvent>,
+) {
+    for command in commands.iter() {
+        if let Command::Events(trigger) = &command.command {
+            let (client, keycard) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            if !keycard.can(EVENTS) {
+                continue;
+            }
+
+            let mut listed = events
+                .iter()
+                .map(|(name, event)| (prototype_id(name), event))
+                .collect::<Vec<_>>();
+
+            listed.sort_by_key(|(id, _)| *id);
+
+            let Some(trigger) = trigger else {
+                if listed.is_empty() {
+                    outbox.send_text(client.id, "There are no events.");
+
+                    continue;
+                }
+
+                let lines = listed
+                    .iter()
+                    .map(|(id, event)| {
+                        let running = match scheduler.running.get(*id) {
+                            Some(_) => " - running",
+                            None => "",
+                        };
+
+                        format!("{id}: {}{running}", event.trigger)
+                    })
+                    .collect::<Vec<_>>();
+
+                outbox.send_text(client.id, lines.join("\n"));
+
+                continue;
+            };
+
+            if !listed.iter().any(|(id, _)| id == trigger) {
+                outbox.send_text(client.id, format!("Event \"{trigger}\" not found."));
+            } else if scheduler.running.contains_key(trigger) {
+                outbox.send_text(client.id, format!("{trigger} is already running."));
+            } else {
+                info!("Triggering event {}", trigger);
+
+                triggers.send(TriggerEvent {
+                    event: trigger.clone(),
+                });
+
+                outbox.send_text(client.id, format!("Triggering {trigger}."));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        scheduler::components::{Clock, Trigger},
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    fn midnight() -> WorldEvent {
+        WorldEvent {
+            trigger: Trigger {
+                clock: Clock::Game,
+                month: None,
+                day: None,
+                hour: Some(0),
+                minute: Some(0),
+            },
+            duration: 0,
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn lists_events() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(events);
+
+        app.world
+            .spawn((Name::new("events.boss (Prototype)"), midnight()));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::admin())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "events");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "events.boss: 0 0 * * (game time)"
+        );
+    }
+
+    #[test]
+    fn triggers_event() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(events);
+
+        app.world
+            .spawn((Name::new("events.boss (Prototype)"), midnight()));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::admin())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "events trigger events.boss");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Triggering events.boss."
+        );
+
+        let triggers = app.world.resource::<Events<TriggerEvent>>();
+        let mut reader = triggers.get_reader();
+
+        assert_eq!(
+            reader.iter(triggers).next().map(|t| t.event.as_str()),
+            Some("events.boss")
+        );
+    }
+
+    #[test]
+    fn players_cannot() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(events);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "events");
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+    }
+}
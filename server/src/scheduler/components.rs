@@ -0,0 +1,124 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+
+use crate::world::resources::WorldTime;
+
+/// Something that happens in the world on a schedule, defined as a child of the `events`
+/// prototype and named by its prototype.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct WorldEvent {
+    pub trigger: Trigger,
+    /// In-game minutes before what the event spawned is despawned and what it opened is
+    /// closed again. Nothing is undone when it's 0.
+    pub duration: u32,
+    pub actions: Vec<Action>,
+}
+
+/// When an event fires, like a cron entry. Fields left out match anything, and the event
+/// fires as the clock comes to match, not for as long as it does.
+#[derive(Reflect, FromReflect)]
+pub struct Trigger {
+    pub clock: Clock,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+    pub hour: Option<u32>,
+    pub minute: Option<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+pub enum Clock {
+    Game,
+    /// UTC.
+    Real,
+}
+
+#[derive(Reflect, FromReflect)]
+pub enum Action {
+    /// Tells everyone online.
+    Announce(String),
+    /// Spawns a prototype on the tile spawned from the `tile` prototype.
+    Spawn { prototype: String, tile: String },
+    /// Opens everything spawned from this prototype that can be opened, like a gate.
+    Open(String),
+}
+
+impl Trigger {
+    pub fn matches(&self, world_time: &WorldTime, now: &DateTime<Utc>) -> bool {
+        let (month, day, hour, minute) = match self.clock {
+            Clock::Game => (
+                world_time.month,
+                world_time.day,
+                world_time.hour,
+                world_time.minute,
+            ),
+            Clock::Real => (now.month(), now.day(), now.hour(), now.minute()),
+        };
+
+        [
+            (self.month, month),
+            (self.day, day),
+            (self.hour, hour),
+            (self.minute, minute),
+        ]
+        .iter()
+        .all(|(wanted, current)| wanted.is_none_or(|wanted| wanted == *current))
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let field = |value: Option<u32>| value.map_or("*".into(), |value| value.to_string());
+
+        write!(
+            f,
+            "{} {} {} {} ({} time)",
+            field(self.minute),
+            field(self.hour),
+            field(self.day),
+            field(self.month),
+            match self.clock {
+                Clock::Game => "game",
+                Clock::Real => "real",
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trigger(hour: Option<u32>, minute: Option<u32>) -> Trigger {
+        Trigger {
+            clock: Clock::Game,
+            month: None,
+            day: None,
+            hour,
+            minute,
+        }
+    }
+
+    #[test]
+    fn matches_set_fields() {
+        let now = Utc::now();
+        let world_time = WorldTime {
+            hour: 6,
+            minute: 30,
+            ..default()
+        };
+
+        assert!(trigger(Some(6), None).matches(&world_time, &now));
+        assert!(trigger(Some(6), Some(30)).matches(&world_time, &now));
+        assert!(!trigger(Some(6), Some(0)).matches(&world_time, &now));
+        assert!(!trigger(Some(0), None).matches(&world_time, &now));
+    }
+
+    #[test]
+    fn displays_like_cron() {
+        assert_eq!(trigger(Some(6), Some(0)).to_string(), "0 6 * * (game time)");
+    }
+}
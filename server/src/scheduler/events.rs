@@ -0,0 +1,4 @@
+/// Starts a world event by its prototype name, whether it's due or an admin asked for it.
+pub struct TriggerEvent {
+    pub event: String,
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+use crate::world::resources::WorldStateLoaded;
+
+use super::{
+    commands::events::*, components::*, events::TriggerEvent, resources::Scheduler, systems::*,
+};
+
+pub struct SchedulerPlugin;
+
+impl Plugin for SchedulerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WorldEvent>()
+            .register_type::<Trigger>()
+            .register_type::<Clock>()
+            .register_type::<Option<u32>>()
+            .register_type::<Action>()
+            .register_type::<Vec<Action>>();
+
+        app.init_resource::<Scheduler>().add_event::<TriggerEvent>();
+
+        app.add_systems((
+            events,
+            // The clock only means anything once it's been read from the save.
+            check_schedule.run_if(resource_exists::<WorldStateLoaded>()),
+            start_events.after(check_schedule).after(events),
+            spawn_event_prototypes.after(start_events),
+            end_events,
+        ));
+
+        app.add_system(spawn_events.run_if(prototype_ready("events").and_then(run_once())));
+    }
+}
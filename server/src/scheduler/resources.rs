@@ -0,0 +1,21 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+#[derive(Default, Resource)]
+pub struct Scheduler {
+    /// Events whose trigger matched the last time it was checked, so they don't fire again
+    /// until it stops matching.
+    pub matched: HashSet<String>,
+    pub running: HashMap<String, RunningEvent>,
+    /// Prototypes waiting to be spawned for an event, with the tile they go on.
+    pub spawning: Vec<(String, String, Entity)>,
+}
+
+/// What an event has done that's undone when it ends.
+pub struct RunningEvent {
+    /// In-game minutes since the epoch when it ends.
+    pub ends: u64,
+    pub spawned: Vec<Entity>,
+    pub opened: Vec<Entity>,
+}
@@ -0,0 +1,287 @@
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use bevy_proto::prelude::*;
+use chrono::Utc;
+
+use crate::{
+    interact::components::Openable,
+    player::components::{Client, Online},
+    spatial::{
+        components::Tile,
+        events::{EntityMoved, MoveMethod},
+    },
+    utils::prototype_id,
+    world::resources::WorldTime,
+};
+
+use super::{
+    components::{Action, WorldEvent},
+    events::TriggerEvent,
+    resources::{RunningEvent, Scheduler},
+};
+
+pub fn spawn_events(mut commands: ProtoCommands) {
+    commands.spawn("events");
+}
+
+/// Fires events as the clock comes to match their trigger.
+pub fn check_schedule(
+    events: Query<(&Name, &WorldEvent)>,
+    mut scheduler: ResMut<Scheduler>,
+    mut triggers: EventWriter<TriggerEvent>,
+    world_time: Res<WorldTime>,
+) {
+    let now = Utc::now();
+
+    for (name, event) in events.iter() {
+        let id = prototype_id(name);
+
+        if !event.trigger.matches(&world_time, &now) {
+            scheduler.matched.remove(id);
+
+            continue;
+        }
+
+        if scheduler.matched.insert(id.into()) {
+            info!("Starting scheduled event {}", id);
+
+            triggers.send(TriggerEvent { event: id.into() });
+        }
+    }
+}
+
+/// Carries out a triggered event's actions, unless it's already running. Prototypes are
+/// spawned by `spawn_event_prototypes`.
+pub fn start_events(
+    events: Query<(&Name, &WorldEvent)>,
+    mut openables: Query<(Entity, &Name, &mut Openable)>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<&Client, With<Online>>,
+    mut scheduler: ResMut<Scheduler>,
+    tiles: Query<(Entity, &Name), With<Tile>>,
+    mut triggers: EventReader<TriggerEvent>,
+    world_time: Res<WorldTime>,
+) {
+    for TriggerEvent { event: id } in triggers.iter() {
+        if scheduler.running.contains_key(id) {
+            continue;
+        }
+
+        let Some((_, event)) = events.iter().find(|(name, _)| prototype_id(name) == id) else {
+            continue;
+        };
+
+        let mut opened = vec![];
+
+        for action in &event.actions {
+            match action {
+                Action::Announce(message) => {
+                    for client in players.iter() {
+                        outbox.send_text(client.id, message);
+                    }
+                }
+                Action::Spawn { prototype, tile } => {
+                    let Some((tile, _)) = tiles.iter().find(|(_, name)| prototype_id(name) == tile)
+                    else {
+                        warn!("Event {} can't find tile {}", id, tile);
+
+                        continue;
+                    };
+
+                    scheduler
+                        .spawning
+                        .push((id.clone(), prototype.clone(), tile));
+                }
+                Action::Open(prototype) => {
+                    for (entity, _, mut openable) in
+                        openables.iter_mut().filter(|(_, name, openable)| {
+                            prototype_id(name) == prototype && !openable.open
+                        })
+                    {
+                        openable.open = true;
+                        opened.push(entity);
+                    }
+                }
+            }
+        }
+
+        if event.duration > 0 {
+            scheduler.running.insert(
+                id.clone(),
+                RunningEvent {
+                    ends: world_time.minutes + u64::from(event.duration),
+                    spawned: vec![],
+                    opened,
+                },
+            );
+        }
+    }
+}
+
+pub fn spawn_event_prototypes(
+    mut bevy: Commands,
+    mut proto: ProtoCommands,
+    prototypes: Prototypes,
+    mut scheduler: ResMut<Scheduler>,
+) {
+    let Scheduler {
+        running, spawning, ..
+    } = &mut *scheduler;
+
+    spawning.retain(|(event, prototype, tile)| {
+        if !prototypes.is_ready(prototype.as_str()) {
+            return true;
+        }
+
+        let entity = proto.spawn(prototype.as_str()).id();
+        bevy.entity(entity).set_parent(*tile);
+
+        // Nothing was left to get here, and it's sent through commands so it's only read once
+        // the prototype has been spawned.
+        let moved = EntityMoved {
+            entity,
+            from: Entity::PLACEHOLDER,
+            to: *tile,
+            direction: None,
+            method: MoveMethod::Other,
+        };
+
+        bevy.add(move |world: &mut World| world.send_event(moved));
+
+        if let Some(running) = running.get_mut(event) {
+            running.spawned.push(entity);
+        }
+
+        false
+    });
+}
+
+/// Undoes what events did once they've run their course.
+pub fn end_events(
+    mut bevy: Commands,
+    mut openables: Query<&mut Openable>,
+    mut scheduler: ResMut<Scheduler>,
+    world_time: Res<WorldTime>,
+) {
+    scheduler.running.retain(|id, running| {
+        if world_time.minutes < running.ends {
+            return true;
+        }
+
+        info!("Ending event {}", id);
+
+        for entity in &running.spawned {
+            if let Some(entity) = bevy.get_entity(*entity) {
+                entity.despawn_recursive();
+            }
+        }
+
+        for entity in &running.opened {
+            if let Ok(mut openable) = openables.get_mut(*entity) {
+                openable.open = false;
+            }
+        }
+
+        false
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        scheduler::components::{Clock, Trigger},
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+            utils::get_message_content,
+        },
+    };
+
+    use super::*;
+
+    fn at_dawn(duration: u32, actions: Vec<Action>) -> WorldEvent {
+        WorldEvent {
+            trigger: Trigger {
+                clock: Clock::Game,
+                month: None,
+                day: None,
+                hour: Some(6),
+                minute: None,
+            },
+            duration,
+            actions,
+        }
+    }
+
+    fn setup() -> App {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((
+            check_schedule,
+            start_events.after(check_schedule),
+            end_events.after(start_events),
+        ));
+
+        app
+    }
+
+    #[test]
+    fn fires_once_when_due() {
+        let mut app = setup();
+
+        app.world.spawn((
+            Name::new("events.market (Prototype)"),
+            at_dawn(0, vec![Action::Announce("The market opens.".into())]),
+        ));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+        assert!(get_message_content(&mut app, client_id).is_none());
+
+        app.world.resource_mut::<WorldTime>().hour = 6;
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "The market opens."
+        );
+
+        app.update();
+        app.update();
+        assert!(get_message_content(&mut app, client_id).is_none());
+    }
+
+    #[test]
+    fn closes_what_it_opened() {
+        let mut app = setup();
+
+        app.world.spawn((
+            Name::new("events.gate (Prototype)"),
+            at_dawn(60, vec![Action::Open("world.gate".into())]),
+        ));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+        let other = TileBuilder::new().build(&mut app, zone);
+        let gate = TransitionBuilder::new()
+            .openable(false)
+            .build(&mut app, tile, other);
+        app.world
+            .entity_mut(gate)
+            .insert(Name::new("world.gate (Prototype)"));
+
+        app.world.resource_mut::<WorldTime>().hour = 6;
+        app.update();
+
+        assert!(app.world.get::<Openable>(gate).unwrap().open);
+
+        app.world.resource_mut::<WorldTime>().minutes += 60;
+        app.update();
+
+        assert!(!app.world.get::<Openable>(gate).unwrap().open);
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+use rhai::AST;
+
+/// A content script for a tile, item or NPC. The source is Rhai, defining a function for each
+/// event it handles, as described in `script::engine`.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Script {
+    pub source: String,
+    /// Seconds between calls to its `on_tick` function, if it has one.
+    pub tick: Option<f32>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScriptEvent {
+    /// A player arrives on the tile, or on the tile of the NPC or item with the script.
+    Enter,
+    /// A player picks up the item.
+    Take,
+    /// A player says something on the tile.
+    Say,
+    /// Every so many seconds, as set by the script's `tick`.
+    Tick,
+}
+
+impl ScriptEvent {
+    /// The function a script defines to handle this event.
+    pub fn function(&self) -> &'static str {
+        match self {
+            ScriptEvent::Enter => "on_enter",
+            ScriptEvent::Take => "on_take",
+            ScriptEvent::Say => "on_say",
+            ScriptEvent::Tick => "on_tick",
+        }
+    }
+}
+
+/// A `Script` once its source has been compiled.
+#[derive(Component)]
+pub struct CompiledScript {
+    pub ast: AST,
+    pub timer: Option<Timer>,
+}
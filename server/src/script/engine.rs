@@ -0,0 +1,228 @@
+//! Content scripts are written in [Rhai](https://rhai.rs). A script defines a function for
+//! each event it handles, which is called when the event happens:
+//!
+//! ```text
+//! // Someone says something on the tile.
+//! fn on_say(said) {
+//!     if said.to_lower().contains("open sesame") {
+//!         tell("The rock face rumbles.");
+//!         echo(`${actor()} is swallowed by the rock.`);
+//!         move_to("trinus.hidden-cave");
+//!     } else if hour() >= 20 {
+//!         tell("Nothing happens. Perhaps it's too dark to read the runes.");
+//!     }
+//! }
+//! ```
+//!
+//! The other events are `on_enter()`, `on_take()` and `on_tick()`. Scripts can't reach
+//! files, the network or the world directly. They read what they're given through
+//! `actor()`, `hour()`, `minute()` and `time()`, and act through `tell`, `echo`, `move_to`
+//! and `spawn`, which queue actions that are carried out once the script has finished.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use rhai::{
+    CallFnOptions, Dynamic, Engine, EvalAltResult, NativeCallContext, ParseError, Scope, AST,
+};
+
+/// How many operations a script gets before it's stopped, so a runaway loop can't hang the
+/// server.
+const MAX_OPERATIONS: u64 = 50_000;
+
+/// Something a script wants done.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    /// Send a message to whoever set the script off.
+    Tell(String),
+    /// Send a message to everyone on the script's tile.
+    Echo(String),
+    /// Move whoever set the script off to the tile with this prototype name.
+    Move(String),
+    /// Spawn a prototype on the script's tile.
+    Spawn(String),
+}
+
+/// What a script can see about the event that set it off.
+#[derive(Default)]
+pub struct Context {
+    pub actor: Option<String>,
+    pub hour: u32,
+    pub minute: u32,
+    pub time: String,
+}
+
+/// A single run of a script, handed to the functions it calls.
+#[derive(Clone)]
+struct Run(Arc<Mutex<(Context, Vec<Action>)>>);
+
+impl Run {
+    fn of(context: &NativeCallContext) -> Option<Self> {
+        context.tag()?.clone().try_cast::<Run>()
+    }
+
+    fn act(context: NativeCallContext, action: Action) {
+        if let Some(run) = Run::of(&context) {
+            run.0.lock().unwrap().1.push(action);
+        }
+    }
+
+    fn read<T>(context: NativeCallContext, read: impl Fn(&Context) -> T) -> T
+    where
+        T: Default,
+    {
+        Run::of(&context)
+            .map(|run| read(&run.0.lock().unwrap().0))
+            .unwrap_or_default()
+    }
+}
+
+/// The sandboxed engine every script runs in.
+#[derive(Resource)]
+pub struct ScriptEngine(Engine);
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        let mut engine = Engine::new();
+
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(32)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(4096)
+            .set_max_array_size(1024)
+            .set_max_map_size(1024)
+            .disable_symbol("eval")
+            .on_print(|text| info!("Script printed {}", text))
+            .on_debug(|text, _, position| debug!("Script at {} printed {}", position, text));
+
+        engine
+            .register_fn("tell", |context: NativeCallContext, text: &str| {
+                Run::act(context, Action::Tell(text.into()));
+            })
+            .register_fn("echo", |context: NativeCallContext, text: &str| {
+                Run::act(context, Action::Echo(text.into()));
+            })
+            .register_fn("move_to", |context: NativeCallContext, tile: &str| {
+                Run::act(context, Action::Move(tile.into()));
+            })
+            .register_fn("spawn", |context: NativeCallContext, prototype: &str| {
+                Run::act(context, Action::Spawn(prototype.into()));
+            })
+            .register_fn("actor", |context: NativeCallContext| {
+                Run::read(context, |context| context.actor.clone().unwrap_or_default())
+            })
+            .register_fn("hour", |context: NativeCallContext| {
+                Run::read(context, |context| i64::from(context.hour))
+            })
+            .register_fn("minute", |context: NativeCallContext| {
+                Run::read(context, |context| i64::from(context.minute))
+            })
+            .register_fn("time", |context: NativeCallContext| {
+                Run::read(context, |context| context.time.clone())
+            });
+
+        Self(engine)
+    }
+}
+
+impl ScriptEngine {
+    pub fn compile(&self, source: &str) -> Result<AST, ParseError> {
+        self.0.compile(source)
+    }
+
+    /// Calls one of a script's event functions, returning what it asked to have done. Scripts
+    /// that don't handle the event do nothing.
+    pub fn run(
+        &self,
+        ast: &AST,
+        function: &str,
+        arguments: Vec<Dynamic>,
+        context: Context,
+    ) -> Result<Vec<Action>, Box<EvalAltResult>> {
+        if !handles(ast, function, arguments.len()) {
+            return Ok(vec![]);
+        }
+
+        let run = Run(Arc::new(Mutex::new((context, vec![]))));
+        let options = CallFnOptions::new().eval_ast(false).with_tag(run.clone());
+
+        // Whatever the function returns is ignored, only what it asked to have done counts.
+        let _: Dynamic =
+            self.0
+                .call_fn_with_options(options, &mut Scope::new(), ast, function, arguments)?;
+
+        let actions = std::mem::take(&mut run.0.lock().unwrap().1);
+
+        Ok(actions)
+    }
+}
+
+/// Whether a script defines a function for an event.
+fn handles(ast: &AST, function: &str, arity: usize) -> bool {
+    ast.iter_functions()
+        .any(|definition| definition.name == function && definition.params.len() == arity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queues_actions() {
+        let engine = ScriptEngine::default();
+        let ast = engine
+            .compile(
+                r#"
+                    fn on_say(said) {
+                        if said.to_lower().contains("open sesame") {
+                            tell("The rock face rumbles.");
+                            echo(`${actor()} is swallowed by the rock.`);
+                            move_to("trinus.cave");
+                        } else if hour() >= 20 {
+                            tell("Nothing happens.");
+                        }
+                    }
+                "#,
+            )
+            .unwrap();
+
+        let context = || Context {
+            actor: Some("Icauna".into()),
+            hour: 21,
+            ..default()
+        };
+
+        assert_eq!(
+            engine
+                .run(&ast, "on_say", vec!["Open Sesame!".into()], context())
+                .unwrap(),
+            vec![
+                Action::Tell("The rock face rumbles.".into()),
+                Action::Echo("Icauna is swallowed by the rock.".into()),
+                Action::Move("trinus.cave".into()),
+            ]
+        );
+        assert_eq!(
+            engine
+                .run(&ast, "on_say", vec!["hello".into()], context())
+                .unwrap(),
+            vec![Action::Tell("Nothing happens.".into())]
+        );
+        assert!(engine
+            .run(&ast, "on_enter", vec![], context())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn stops_runaway_scripts() {
+        let engine = ScriptEngine::default();
+        let ast = engine.compile("fn on_tick() { loop { } }").unwrap();
+
+        assert!(engine
+            .run(&ast, "on_tick", vec![], Context::default())
+            .is_err());
+        assert!(engine.compile("fn on_tick() { tell(").is_err());
+    }
+}
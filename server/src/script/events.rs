@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// A script asked for a prototype to be spawned on a tile.
+pub struct ScriptSpawn {
+    pub prototype: String,
+    pub tile: Entity,
+}
@@ -0,0 +1,5 @@
+pub mod components;
+pub mod engine;
+pub mod events;
+pub mod plugin;
+pub mod systems;
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use super::{components::*, engine::ScriptEngine, events::ScriptSpawn, systems::*};
+
+pub struct ScriptPlugin;
+
+impl Plugin for ScriptPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Script>();
+
+        app.init_resource::<ScriptEngine>()
+            .add_event::<ScriptSpawn>();
+
+        app.add_systems((
+            compile_scripts,
+            script_on_enter,
+            script_on_take,
+            script_on_say,
+            script_on_tick,
+            spawn_from_scripts,
+        ));
+    }
+}
@@ -0,0 +1,450 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_nest::prelude::*;
+use bevy_proto::prelude::*;
+
+use crate::{
+    input::events::{Command, ParsedCommand, ProxyCommand},
+    items::components::{Inventory, Item},
+    player::components::{Character, Client, Online},
+    spatial::{
+        components::Tile,
+        events::{EntityMoved, MoveMethod},
+    },
+    utils::prototype_id,
+    value_or_continue,
+    world::resources::WorldTime,
+};
+
+use super::{
+    components::{CompiledScript, Script, ScriptEvent},
+    engine::{Action, Context, ScriptEngine},
+    events::ScriptSpawn,
+};
+
+pub fn compile_scripts(
+    mut bevy: Commands,
+    engine: Res<ScriptEngine>,
+    scripts: Query<(Entity, &Script, Option<&Name>), Changed<Script>>,
+) {
+    for (entity, script, name) in scripts.iter() {
+        match engine.compile(&script.source) {
+            Ok(ast) => {
+                bevy.entity(entity).insert(CompiledScript {
+                    ast,
+                    timer: script
+                        .tick
+                        .map(|seconds| Timer::from_seconds(seconds, TimerMode::Repeating)),
+                });
+            }
+            Err(err) => {
+                warn!("Script on {:?} ({:?}) has an error, {}", name, entity, err);
+
+                bevy.entity(entity).remove::<CompiledScript>();
+            }
+        }
+    }
+}
+
+/// Everything scripts are allowed to touch, and nothing else.
+#[derive(SystemParam)]
+pub struct ScriptRunner<'w, 's> {
+    bevy: Commands<'w, 's>,
+    engine: Res<'w, ScriptEngine>,
+    outbox: EventWriter<'w, Outbox>,
+    parents: Query<'w, 's, &'static Parent>,
+    players: Query<'w, 's, (Entity, &'static Client, &'static Character), With<Online>>,
+    proxy: EventWriter<'w, ProxyCommand>,
+    spawns: EventWriter<'w, ScriptSpawn>,
+    tiles: Query<'w, 's, (Entity, Option<&'static Name>, Option<&'static Children>), With<Tile>>,
+    world_time: Res<'w, WorldTime>,
+}
+
+impl<'w, 's> ScriptRunner<'w, 's> {
+    /// The tile a scripted entity is on, which is the entity itself for tiles.
+    pub fn tile_of(&self, entity: Entity) -> Option<Entity> {
+        if self.tiles.contains(entity) {
+            return Some(entity);
+        }
+
+        self.parents
+            .get(entity)
+            .ok()
+            .map(|parent| parent.get())
+            .filter(|parent| self.tiles.contains(*parent))
+    }
+
+    /// The scripted entities a player can set off on a tile: the tile and what's on it.
+    pub fn on_tile(&self, tile: Entity) -> Vec<Entity> {
+        let Ok((_, _, children)) = self.tiles.get(tile) else {
+            return vec![];
+        };
+
+        std::iter::once(tile)
+            .chain(
+                children
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .copied(),
+            )
+            .collect()
+    }
+
+    pub fn run(
+        &mut self,
+        script: &CompiledScript,
+        event: ScriptEvent,
+        tile: Entity,
+        actor: Option<Entity>,
+        said: Option<&str>,
+    ) {
+        let actor = actor.and_then(|actor| self.players.get(actor).ok());
+
+        let context = Context {
+            actor: actor.map(|(_, _, character)| character.name.clone()),
+            hour: self.world_time.hour,
+            minute: self.world_time.minute,
+            time: self.world_time.time_string(),
+        };
+
+        let arguments = said.map(|said| said.into()).into_iter().collect();
+
+        let actions = match self
+            .engine
+            .run(&script.ast, event.function(), arguments, context)
+        {
+            Ok(actions) => actions,
+            Err(err) => {
+                warn!(
+                    "Script on {:?} failed in {}, {}",
+                    tile,
+                    event.function(),
+                    err
+                );
+
+                return;
+            }
+        };
+
+        for action in actions {
+            match action {
+                Action::Tell(text) => {
+                    if let Some((_, client, _)) = actor {
+                        self.outbox.send_text(client.id, text);
+                    }
+                }
+                Action::Echo(text) => {
+                    let Ok((_, _, Some(children))) = self.tiles.get(tile) else {
+                        continue;
+                    };
+
+                    for (_, client, _) in children.iter().filter_map(|c| self.players.get(*c).ok())
+                    {
+                        self.outbox.send_text(client.id, text.clone());
+                    }
+                }
+                Action::Move(prototype) => {
+                    let Some((player, client, character)) = actor else {
+                        continue;
+                    };
+
+                    let Some((target, _, _)) = self.tiles.iter().find(|(_, name, _)| {
+                        name.is_some_and(|name| prototype_id(name) == prototype)
+                    }) else {
+                        warn!(
+                            "Script tried to move {} to unknown tile {}",
+                            character.name, prototype
+                        );
+
+                        continue;
+                    };
+
+                    self.bevy.entity(player).set_parent(target);
+
+                    // Sent through commands since `script_on_enter` reads these while
+                    // running scripts.
+                    let moved = EntityMoved {
+                        entity: player,
+                        from: self.parents.get(player).map_or(tile, |parent| parent.get()),
+                        to: target,
+                        direction: None,
+                        method: MoveMethod::Other,
+                    };
+
+                    self.bevy
+                        .add(move |world: &mut World| world.send_event(moved));
+
+                    self.proxy.send(ProxyCommand(ParsedCommand {
+                        from: client.id,
+                        command: Command::Look(None),
+                    }));
+                }
+                Action::Spawn(prototype) => self.spawns.send(ScriptSpawn { prototype, tile }),
+            }
+        }
+    }
+}
+
+pub fn script_on_enter(
+    mut moves: EventReader<EntityMoved>,
+    mut runner: ScriptRunner,
+    players: Query<(), With<Online>>,
+    scripts: Query<&CompiledScript>,
+) {
+    for moved in moves.iter() {
+        if !players.contains(moved.entity) {
+            continue;
+        }
+
+        for entity in runner.on_tile(moved.to) {
+            let script = value_or_continue!(scripts.get(entity).ok());
+
+            runner.run(
+                script,
+                ScriptEvent::Enter,
+                moved.to,
+                Some(moved.entity),
+                None,
+            );
+        }
+    }
+}
+
+pub fn script_on_take(
+    mut runner: ScriptRunner,
+    inventories: Query<&Parent, With<Inventory>>,
+    items: Query<(&CompiledScript, Ref<Parent>), With<Item>>,
+) {
+    for (script, inventory) in items.iter() {
+        // Items restored into an inventory on login weren't taken by anyone.
+        if !inventory.is_changed() || inventory.is_added() {
+            continue;
+        }
+
+        let player = value_or_continue!(inventories.get(inventory.get()).ok()).get();
+        let tile = value_or_continue!(runner.tile_of(player));
+
+        runner.run(script, ScriptEvent::Take, tile, Some(player), None);
+    }
+}
+
+pub fn script_on_say(
+    mut commands: EventReader<ParsedCommand>,
+    mut runner: ScriptRunner,
+    players: Query<(Entity, &Client, &Parent), With<Online>>,
+    scripts: Query<&CompiledScript>,
+) {
+    for command in commands.iter() {
+        if let Command::Say(message) = &command.command {
+            let (player, _, tile) =
+                value_or_continue!(players.iter().find(|(_, c, _)| c.id == command.from));
+
+            for entity in runner.on_tile(tile.get()) {
+                let script = value_or_continue!(scripts.get(entity).ok());
+
+                runner.run(
+                    script,
+                    ScriptEvent::Say,
+                    tile.get(),
+                    Some(player),
+                    Some(message),
+                );
+            }
+        }
+    }
+}
+
+pub fn script_on_tick(
+    mut runner: ScriptRunner,
+    mut scripts: Query<(Entity, &mut CompiledScript)>,
+    time: Res<Time>,
+) {
+    for (entity, mut script) in scripts.iter_mut() {
+        let Some(tile) = runner.tile_of(entity) else {
+            continue;
+        };
+
+        let Some(timer) = script.timer.as_mut() else {
+            continue;
+        };
+
+        if timer.tick(time.delta()).just_finished() {
+            runner.run(&script, ScriptEvent::Tick, tile, None, None);
+        }
+    }
+}
+
+pub fn spawn_from_scripts(
+    mut bevy: Commands,
+    mut proto: ProtoCommands,
+    mut spawns: EventReader<ScriptSpawn>,
+) {
+    for spawn in spawns.iter() {
+        let entity = proto.spawn(spawn.prototype.as_str()).id();
+
+        bevy.entity(entity).set_parent(spawn.tile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::test::{
+        app_builder::AppBuilder,
+        item_builder::ItemBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    fn script(source: &str) -> Script {
+        Script {
+            source: source.into(),
+            tick: None,
+        }
+    }
+
+    fn setup() -> App {
+        let mut app = AppBuilder::new().build();
+        app.init_resource::<ScriptEngine>();
+
+        app
+    }
+
+    #[test]
+    fn runs_on_enter() {
+        let mut app = setup();
+        app.add_systems((compile_scripts, script_on_enter));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, zone);
+        let shrine = TileBuilder::new().build(&mut app, zone);
+
+        app.world.entity_mut(shrine).insert(script(
+            r#"fn on_enter() { tell(`A chill runs down your spine, ${actor()}.`); }"#,
+        ));
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .name("Icauna")
+            .tile(start)
+            .build(&mut app);
+
+        app.update();
+        app.update();
+
+        app.world.entity_mut(player).set_parent(shrine);
+        app.world.send_event(EntityMoved {
+            entity: player,
+            from: start,
+            to: shrine,
+            direction: None,
+            method: MoveMethod::Teleport,
+        });
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "A chill runs down your spine, Icauna.");
+    }
+
+    #[test]
+    fn runs_on_say() {
+        let mut app = setup();
+        app.add_systems((compile_scripts, script_on_say));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let cliff = TileBuilder::new().build(&mut app, zone);
+        let cave = TileBuilder::new()
+            .prototype("trinus.cave")
+            .build(&mut app, zone);
+
+        app.world.entity_mut(cliff).insert(script(
+            r#"
+                fn on_say(said) {
+                    if said.to_lower().contains("open sesame") {
+                        move_to("trinus.cave");
+                    }
+                }
+            "#,
+        ));
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(cliff).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "say hello");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), cliff);
+
+        send_message(&mut app, client_id, "say Open Sesame!");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), cave);
+    }
+
+    #[test]
+    fn runs_on_take() {
+        let mut app = setup();
+        app.add_systems((compile_scripts, script_on_take));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let idol = ItemBuilder::new().name("idol").tile(tile).build(&mut app);
+
+        app.world.entity_mut(idol).insert(script(
+            r#"fn on_take() { echo("The ground begins to shake!"); }"#,
+        ));
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.update();
+        app.update();
+
+        app.world.entity_mut(inventory.unwrap()).add_child(idol);
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The ground begins to shake!");
+    }
+
+    #[test]
+    fn runs_on_tick() {
+        let mut app = setup();
+        app.add_systems((compile_scripts, script_on_tick));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        app.world.entity_mut(tile).insert(Script {
+            source: r#"fn on_tick() { echo("A bell tolls in the distance."); }"#.into(),
+            tick: Some(30.0),
+        });
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+
+        app.world
+            .get_mut::<CompiledScript>(tile)
+            .unwrap()
+            .timer
+            .as_mut()
+            .unwrap()
+            .set_elapsed(Duration::from_secs(30));
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "A bell tolls in the distance.");
+    }
+}
@@ -7,10 +7,14 @@ use regex::Regex;
 use crate::{
     input::events::{Command, ParseError, ParsedCommand},
     player::components::{Character, Client, Online},
-    spatial::components::Tile,
+    social::utils::Earshot,
     value_or_continue,
 };
 
+/// Tiles away that someone can be heard talking, though only those on the same tile can
+/// make out what's said.
+const SAY_RANGE: i32 = 1;
+
 static REGEX: OnceLock<Regex> = OnceLock::new();
 
 pub fn handle_say(content: &str) -> Result<Command, ParseError> {
@@ -32,21 +36,31 @@ pub fn handle_say(content: &str) -> Result<Command, ParseError> {
 
 pub fn say(
     mut commands: EventReader<ParsedCommand>,
+    earshot: Earshot,
     mut outbox: EventWriter<Outbox>,
     players: Query<(&Client, &Character, &Parent), With<Online>>,
-    tiles: Query<&Children, With<Tile>>,
 ) {
     for command in commands.iter() {
         if let Command::Say(message) = &command.command {
             let (_, character, tile) =
                 value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
-            let siblings = value_or_continue!(tiles.get(tile.get()).ok());
 
-            for (other_client, _, _) in siblings.iter().filter_map(|c| players.get(*c).ok()) {
-                outbox.send_text(
-                    other_client.id,
-                    format!("{} says \"{message}\"", character.name),
-                );
+            let heard = earshot.within(tile.get(), SAY_RANGE);
+
+            for (other_client, _, other_tile) in players.iter() {
+                let heard = value_or_continue!(heard.get(&other_tile.get()));
+
+                if other_tile == tile {
+                    outbox.send_text(
+                        other_client.id,
+                        format!("{} says \"{message}\"", character.name),
+                    );
+                } else {
+                    outbox.send_text(
+                        other_client.id,
+                        format!("Someone says something {}.", heard.whereabouts()),
+                    );
+                }
             }
         }
     }
@@ -106,6 +120,30 @@ mod tests {
         assert_eq!(content, "Flora says \"Hello!\"");
     }
 
+    #[test]
+    fn carries_to_next_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(say);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let next = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        let (_, sender_client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+        let (_, recipient_client_id, _) = PlayerBuilder::new().tile(next).build(&mut app);
+
+        send_message(&mut app, sender_client_id, "say Hello!");
+        app.update();
+
+        let content = get_message_content(&mut app, recipient_client_id).unwrap();
+
+        assert_eq!(content, "Someone says something to the north.");
+    }
+
     #[test]
     fn empty_message() {
         let mut app = AppBuilder::new().build();
@@ -7,10 +7,16 @@ use regex::Regex;
 use crate::{
     input::events::{Command, ParseError, ParsedCommand},
     player::components::{Character, Client, Online},
-    spatial::components::{Tile, Zone},
+    social::utils::Earshot,
     value_or_continue,
 };
 
+/// Yells are made out word for word this many tiles away.
+const YELL_CLEAR_RANGE: i32 = 5;
+
+/// Further out, up to this many tiles, only that someone yelled can be made out.
+const YELL_RANGE: i32 = 10;
+
 static REGEX: OnceLock<Regex> = OnceLock::new();
 
 pub fn handle_yell(content: &str) -> Result<Command, ParseError> {
@@ -32,20 +38,28 @@ pub fn handle_yell(content: &str) -> Result<Command, ParseError> {
 
 pub fn yell(
     mut commands: EventReader<ParsedCommand>,
+    earshot: Earshot,
     mut outbox: EventWriter<Outbox>,
     players: Query<(&Client, &Character, &Parent), With<Online>>,
-    tiles: Query<&Parent, With<Tile>>,
-    zones: Query<&Children, With<Zone>>,
 ) {
     for command in commands.iter() {
         if let Command::Yell(message) = &command.command {
             let (_, character, tile) =
                 value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
-            let zone = value_or_continue!(tiles.get(tile.get()).ok());
-            let zone_tiles = value_or_continue!(zones.get(zone.get()).ok());
 
-            for (client, _, _) in players.iter().filter(|(_, _, t)| zone_tiles.contains(t)) {
-                outbox.send_text(client.id, format!("{} yells \"{message}\"", character.name));
+            let heard = earshot.within(tile.get(), YELL_RANGE);
+
+            for (client, _, tile) in players.iter() {
+                let heard = value_or_continue!(heard.get(&tile.get()));
+
+                if heard.distance <= YELL_CLEAR_RANGE {
+                    outbox.send_text(client.id, format!("{} yells \"{message}\"", character.name));
+                } else {
+                    outbox.send_text(
+                        client.id,
+                        format!("Someone yells something {}.", heard.whereabouts()),
+                    );
+                }
             }
         }
     }
@@ -53,14 +67,30 @@ pub fn yell(
 
 #[cfg(test)]
 mod tests {
+    use std::ops::RangeInclusive;
+
     use super::*;
-    use crate::test::{
-        app_builder::AppBuilder,
-        player_builder::PlayerBuilder,
-        tile_builder::{TileBuilder, ZoneBuilder},
-        utils::{get_message_content, send_message},
+    use crate::{
+        spatial::components::Soundproof,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+            utils::{get_message_content, send_message},
+        },
     };
 
+    /// Lays out tiles next to each other along `y`, one for each of `xs`.
+    fn row(app: &mut App, zone: Entity, y: i32, xs: RangeInclusive<i32>) -> Vec<Entity> {
+        xs.map(|x| {
+            TileBuilder::new()
+                .position(IVec3::new(x, y, 0))
+                .build(app, zone)
+        })
+        .collect()
+    }
+
     #[test]
     fn sends_to_sender() {
         let mut app = AppBuilder::new().build();
@@ -88,8 +118,8 @@ mod tests {
         app.add_system(yell);
 
         let zone = ZoneBuilder::new().build(&mut app);
-        let tile_one = TileBuilder::new().build(&mut app, zone);
-        let tile_two = TileBuilder::new().build(&mut app, zone);
+        let tiles = row(&mut app, zone, 0, 0..=3);
+        let (tile_one, tile_two) = (tiles[0], tiles[3]);
 
         let (_, sender_client_id, _) = PlayerBuilder::new()
             .tile(tile_one)
@@ -106,6 +136,119 @@ mod tests {
         assert_eq!(content, "Flora yells \"Hello!\"");
     }
 
+    #[test]
+    fn muffled_further_out() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(yell);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tiles = row(&mut app, zone, 0, 0..=12);
+        let (near, far, out_of_earshot) = (tiles[0], tiles[8], tiles[12]);
+
+        let (_, sender_client_id, _) = PlayerBuilder::new().tile(near).build(&mut app);
+        let (_, far_client_id, _) = PlayerBuilder::new().tile(far).build(&mut app);
+        let (_, distant_client_id, _) = PlayerBuilder::new().tile(out_of_earshot).build(&mut app);
+
+        send_message(&mut app, sender_client_id, "yell Hello!");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, far_client_id).unwrap(),
+            "Someone yells something to the west."
+        );
+        assert!(get_message_content(&mut app, distant_client_id).is_none());
+    }
+
+    #[test]
+    fn carries_through_transitions() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(yell);
+
+        let town = ZoneBuilder::new().build(&mut app);
+        let gate = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, town);
+
+        let fields = ZoneBuilder::new().build(&mut app);
+        let road = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, fields);
+        let meadow = (1..=8)
+            .map(|y| {
+                TileBuilder::new()
+                    .position(IVec3::new(0, y, 0))
+                    .build(&mut app, fields)
+            })
+            .last()
+            .unwrap();
+
+        TransitionBuilder::new().build(&mut app, gate, road);
+
+        let (_, sender_client_id, _) = PlayerBuilder::new().tile(gate).build(&mut app);
+        let (_, road_client_id, _) = PlayerBuilder::new().tile(road).build(&mut app);
+        let (_, meadow_client_id, _) = PlayerBuilder::new().tile(meadow).build(&mut app);
+
+        send_message(&mut app, sender_client_id, "yell Open the gate!");
+        app.update();
+
+        assert!(get_message_content(&mut app, road_client_id)
+            .unwrap()
+            .ends_with("yells \"Open the gate!\""));
+        assert_eq!(
+            get_message_content(&mut app, meadow_client_id).unwrap(),
+            "Someone yells something to the north."
+        );
+    }
+
+    #[test]
+    fn blocked_by_soundproof_tiles() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(yell);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let hall = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let vault = TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        app.world.entity_mut(vault).insert(Soundproof);
+
+        let (_, sender_client_id, _) = PlayerBuilder::new().tile(hall).build(&mut app);
+        let (_, vault_client_id, _) = PlayerBuilder::new().tile(vault).build(&mut app);
+
+        send_message(&mut app, sender_client_id, "yell Hello!");
+        app.update();
+
+        assert!(get_message_content(&mut app, vault_client_id).is_none());
+
+        // Lets the first yell clear out of the outbox.
+        app.update();
+
+        send_message(&mut app, vault_client_id, "yell Help!");
+        app.update();
+
+        assert!(get_message_content(&mut app, sender_client_id).is_none());
+    }
+
+    #[test]
+    fn walled_off_by_soundproof_tiles() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(yell);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tiles = row(&mut app, zone, 0, 0..=2);
+        app.world.entity_mut(tiles[1]).insert(Soundproof);
+
+        let (_, sender_client_id, _) = PlayerBuilder::new().tile(tiles[0]).build(&mut app);
+        let (_, beyond_client_id, _) = PlayerBuilder::new().tile(tiles[2]).build(&mut app);
+
+        send_message(&mut app, sender_client_id, "yell Hello!");
+        app.update();
+
+        assert!(get_message_content(&mut app, beyond_client_id).is_none());
+    }
+
     #[test]
     fn empty_message() {
         let mut app = AppBuilder::new().build();
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, VecDeque};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::spatial::{
+    components::{Instance, Position, Soundproof, Tile, Transition, Zone},
+    utils::{offset_for_direction, opposite_direction, DIRECTIONS},
+};
+
+/// A tile a sound reaches.
+pub struct Heard {
+    /// How many tiles away it is, counting a step through each transition on the way.
+    pub distance: i32,
+    /// Which way the sound comes from, or `None` when it's right here.
+    pub direction: Option<&'static str>,
+}
+
+/// Works out how far sounds carry.
+#[derive(SystemParam)]
+pub struct Earshot<'w, 's> {
+    soundproof: Query<'w, 's, Entity, With<Soundproof>>,
+    tiles: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static Parent,
+            Option<&'static Children>,
+        ),
+        With<Tile>,
+    >,
+    transitions: Query<'w, 's, &'static Transition>,
+    zones: Query<'w, 's, (&'static Zone, &'static Children, Option<&'static Instance>)>,
+}
+
+/// The position one step along `offset`, unless it's off the edge of the world.
+fn step(from: IVec3, offset: IVec3) -> Option<IVec3> {
+    Some(IVec3::new(
+        from.x.checked_add(offset.x)?,
+        from.y.checked_add(offset.y)?,
+        from.z.checked_add(offset.z)?,
+    ))
+}
+
+impl<'w, 's> Earshot<'w, 's> {
+    /// The tiles within `range` steps of a sound made on `origin`, walking from each tile to
+    /// the ones next to it. It carries through transitions into the zones they lead to, but
+    /// soundproof tiles are as good as walls: it never gets into, out of or past them.
+    pub fn within(&self, origin: Entity, range: i32) -> HashMap<Entity, Heard> {
+        let mut heard = HashMap::from([(
+            origin,
+            Heard {
+                distance: 0,
+                direction: None,
+            },
+        )]);
+
+        if self.soundproof.contains(origin) {
+            return heard;
+        }
+
+        let mut layouts = HashMap::new();
+        let mut queue = VecDeque::from([origin]);
+
+        while let Some(tile) = queue.pop_front() {
+            let distance = heard[&tile].distance;
+
+            if distance >= range {
+                continue;
+            }
+
+            let Ok((position, zone, contents)) = self.tiles.get(tile) else {
+                continue;
+            };
+
+            let layout = layouts
+                .entry(zone.get())
+                .or_insert_with(|| self.layout(zone.get()));
+
+            // Heard from a neighbour, the sound comes from the way back to it.
+            let neighbours = DIRECTIONS
+                .iter()
+                .filter_map(|direction| {
+                    let offset = offset_for_direction(direction)?;
+                    let next = layout.get(&step(position.0, offset)?)?;
+
+                    Some((*next, opposite_direction(direction)))
+                })
+                .collect::<Vec<_>>();
+
+            let arrivals = contents
+                .iter()
+                .flat_map(|contents| contents.iter())
+                .filter_map(|entity| self.transitions.get(*entity).ok())
+                .filter_map(|transition| self.arrival(transition))
+                .map(|arrival| (arrival, None));
+
+            for (next, direction) in neighbours.into_iter().chain(arrivals) {
+                if heard.contains_key(&next) || self.soundproof.contains(next) {
+                    continue;
+                }
+
+                heard.insert(
+                    next,
+                    Heard {
+                        distance: distance + 1,
+                        direction,
+                    },
+                );
+
+                queue.push_back(next);
+            }
+        }
+
+        heard
+    }
+
+    /// Every tile in a zone by its position.
+    fn layout(&self, zone: Entity) -> HashMap<IVec3, Entity> {
+        self.zones
+            .get(zone)
+            .into_iter()
+            .flat_map(|(_, children, _)| children.iter())
+            .filter_map(|child| {
+                self.tiles
+                    .get(*child)
+                    .ok()
+                    .map(|(position, _, _)| (position.0, *child))
+            })
+            .collect()
+    }
+
+    /// The tile a transition leads to, unless sound can't get in. Sound never carries into
+    /// anyone's private instance.
+    fn arrival(&self, transition: &Transition) -> Option<Entity> {
+        let (_, children, _) = self
+            .zones
+            .iter()
+            .find(|(zone, _, instance)| zone.name == transition.zone && instance.is_none())?;
+
+        children
+            .iter()
+            .find(|child| {
+                self.tiles
+                    .get(**child)
+                    .is_ok_and(|(position, _, _)| position.0 == transition.position)
+            })
+            .filter(|tile| !self.soundproof.contains(**tile))
+            .copied()
+    }
+}
+
+impl Heard {
+    /// Where the sound comes from, to finish a sentence like "Someone yells something".
+    pub fn whereabouts(&self) -> String {
+        match self.direction {
+            Some("up") => "above".into(),
+            Some("down") => "below".into(),
+            Some(direction) => format!("to the {direction}"),
+            None => "nearby".into(),
+        }
+    }
+}
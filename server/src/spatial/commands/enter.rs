@@ -6,10 +6,15 @@ use regex::Regex;
 
 use crate::{
     input::events::{Command, ParseError, ParsedCommand, ProxyCommand},
+    interact::components::{Lock, Openable},
     player::components::{Character, Client, Online},
-    spatial::components::{Position, Tile, Transition, Zone},
+    spatial::{
+        components::{Arriving, Instance, Position, Tile, Transition, Zone},
+        events::{EntityMoved, MoveMethod},
+    },
     value_or_continue,
     visual::components::Depiction,
+    world::resources::InstancedZones,
 };
 
 static REGEX: OnceLock<Regex> = OnceLock::new();
@@ -32,10 +37,13 @@ pub fn handle_enter(content: &str) -> Result<Command, ParseError> {
 pub fn enter(
     mut bevy: Commands,
     mut commands: EventReader<ParsedCommand>,
+    instanced: Res<InstancedZones>,
+    instances: Query<&Instance>,
+    mut moved: EventWriter<EntityMoved>,
     mut proxy: EventWriter<ProxyCommand>,
     mut outbox: EventWriter<Outbox>,
     mut players: Query<(Entity, &Client, &Character, &Parent), With<Online>>,
-    transitions: Query<(&Transition, &Depiction)>,
+    transitions: Query<(&Transition, &Depiction, Option<&Lock>, Option<&Openable>)>,
     tiles: Query<(Entity, &Position, &Parent, Option<&Children>), With<Tile>>,
     zones: Query<&Zone>,
 ) {
@@ -67,28 +75,71 @@ pub fn enter(
                 continue;
             }
 
-            let Some((transition, _)) = transitions.iter().find(|(_, depiction)| {
-                target
-                    .as_ref()
-                    .map_or(true, |tag| depiction.tags.contains(tag))
-            }) else {
+            let Some((transition, depiction, lock, openable)) =
+                transitions.iter().find(|(_, depiction, _, _)| {
+                    target
+                        .as_ref()
+                        .map_or(true, |tag| depiction.tags.contains(tag))
+                })
+            else {
                 outbox.send_text(client.id, "Could not find entrance.");
 
                 continue;
             };
 
-            let target = value_or_continue!(tiles.iter().find_map(|(e, p, z, _)| {
+            if lock.is_some_and(|lock| lock.locked) {
+                outbox.send_text(client.id, format!("The {} is locked.", depiction.name));
+
+                continue;
+            }
+
+            if openable.is_some_and(|openable| !openable.open) {
+                outbox.send_text(client.id, format!("The {} is closed.", depiction.name));
+
+                continue;
+            }
+
+            let instanced = instanced.0.contains_key(&transition.zone);
+
+            // Instanced zones only lead to copies the character already belongs to.
+            let target = tiles.iter().find_map(|(e, p, z, _)| {
                 zones.get(z.get()).ok().and_then(|zone| {
-                    if zone.name == transition.zone && p.0 == transition.position {
+                    if zone.name == transition.zone
+                        && p.0 == transition.position
+                        && (!instanced
+                            || instances
+                                .get(z.get())
+                                .is_ok_and(|instance| instance.members.contains(&character.id)))
+                    {
                         Some(e)
                     } else {
                         None
                     }
                 })
-            }));
+            });
+
+            let Some(target) = target else {
+                if instanced {
+                    bevy.entity(player).insert(Arriving {
+                        zone: transition.zone.clone(),
+                        position: transition.position,
+                        instance: None,
+                    });
+                }
+
+                continue;
+            };
 
             bevy.entity(player).set_parent(target);
 
+            moved.send(EntityMoved {
+                entity: player,
+                from: tile.get(),
+                to: target,
+                direction: None,
+                method: MoveMethod::Enter,
+            });
+
             proxy.send(ProxyCommand(ParsedCommand {
                 from: client.id,
                 command: Command::Look(None),
@@ -99,12 +150,15 @@ pub fn enter(
 
 #[cfg(test)]
 mod tests {
-    use crate::test::{
-        app_builder::AppBuilder,
-        player_builder::PlayerBuilder,
-        tile_builder::{TileBuilder, ZoneBuilder},
-        transition_builder::TransitionBuilder,
-        utils::{get_message_content, send_message},
+    use crate::{
+        spatial::systems::arrive_in_instances,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+            utils::{get_message_content, send_message},
+        },
     };
 
     use super::*;
@@ -182,6 +236,33 @@ mod tests {
         assert!(content.contains("Could not find entrance."));
     }
 
+    #[test]
+    fn locked_transition() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(enter);
+
+        let start_zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, start_zone);
+
+        let destination_zone = ZoneBuilder::new().build(&mut app);
+        let destination = TileBuilder::new().build(&mut app, destination_zone);
+
+        TransitionBuilder::new()
+            .tags(&vec!["gate"])
+            .lock("items.gate-key", true)
+            .build(&mut app, start, destination);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "enter gate");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The Transition is locked.");
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), start);
+    }
+
     #[test]
     fn no_transition() {
         let mut app = AppBuilder::new().build();
@@ -199,4 +280,76 @@ mod tests {
 
         assert!(content.contains("There is nowhere to enter from here."));
     }
+
+    fn crypt(app: &mut App, members: Vec<i64>) -> (Entity, Entity) {
+        let zone = ZoneBuilder::new().name("Crypt").build(app);
+        app.world.entity_mut(zone).insert(Instance {
+            members,
+            closing: None,
+        });
+
+        let tile = TileBuilder::new().position(IVec3::ZERO).build(app, zone);
+
+        (zone, tile)
+    }
+
+    #[test]
+    fn enters_own_instance() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(enter);
+
+        app.world
+            .resource_mut::<InstancedZones>()
+            .0
+            .insert("Crypt".into(), "crypt".into());
+
+        let start_zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, start_zone);
+
+        let (_, theirs) = crypt(&mut app, vec![2]);
+        let (_, ours) = crypt(&mut app, vec![1]);
+
+        TransitionBuilder::new().build(&mut app, start, theirs);
+
+        let (player, client_id, _) = PlayerBuilder::new().id(1).tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "enter");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), ours);
+    }
+
+    #[test]
+    fn waits_for_new_instance() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((enter, arrive_in_instances));
+
+        app.world
+            .resource_mut::<InstancedZones>()
+            .0
+            .insert("Crypt".into(), "crypt".into());
+
+        let start_zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new().build(&mut app, start_zone);
+
+        let (_, theirs) = crypt(&mut app, vec![2]);
+
+        TransitionBuilder::new().build(&mut app, start, theirs);
+
+        let (player, client_id, _) = PlayerBuilder::new().id(1).tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "enter");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), start);
+        assert_eq!(app.world.get::<Arriving>(player).unwrap().zone, "Crypt");
+
+        // Spawning the copy is left to the world plugin.
+        let (zone, ours) = crypt(&mut app, vec![1]);
+        app.world.get_mut::<Arriving>(player).unwrap().instance = Some(zone);
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), ours);
+        assert!(app.world.get::<Arriving>(player).is_none());
+    }
 }
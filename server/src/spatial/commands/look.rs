@@ -6,18 +6,21 @@ use inflector::cases::titlecase::to_title_case;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
-    interact::components::Interactions,
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
+    interact::components::{Interactions, Openable},
     items::components::{Item, Surface},
-    npc::components::Npc,
+    npc::components::{Activity, Npc},
     paint,
     player::{
         components::{Character, Client, Online},
         events::Prompt,
     },
     spatial::{
-        components::{Position, Tile, Transition, Zone},
-        utils::offset_for_direction,
+        components::{Doorway, Exits, Position, Tile, Transition, Unlit, Zone},
+        utils::{offset_for_direction, passage, Passage},
     },
     value_or_continue,
     visual::{
@@ -25,6 +28,7 @@ use crate::{
         paint::Color,
         utils::name_list,
     },
+    weather::utils::Sky,
     world::resources::WorldTime,
 };
 
@@ -37,9 +41,7 @@ pub fn handle_look(content: &str) -> Result<Command, ParseError> {
     match regex.captures(content) {
         None => Err(ParseError::WrongCommand),
         Some(captures) => {
-            let target = captures
-                .name("target")
-                .map(|m| m.as_str().trim().to_lowercase());
+            let target = captures.name("target").map(|m| Target::parse(m.as_str()));
 
             Ok(Command::Look(target))
         }
@@ -49,48 +51,78 @@ pub fn handle_look(content: &str) -> Result<Command, ParseError> {
 pub fn look(
     items: Query<(Entity, &Depiction, Option<&Surface>, Option<&Children>), With<Item>>,
     mut commands: EventReader<ParsedCommand>,
+    doorways: Query<(&Doorway, &Depiction, Option<&Openable>)>,
+    doors: Query<(Entity, &Depiction, &Doorway)>,
+    exits: Query<&Exits>,
     mut outbox: EventWriter<Outbox>,
     mut prompts: EventWriter<Prompt>,
-    npcs: Query<(Entity, &Depiction, Option<&Interactions>), With<Npc>>,
+    npcs: Query<(Entity, &Depiction, Option<&Interactions>, Option<&Activity>), With<Npc>>,
     players: Query<(&Client, &Character, &Parent), With<Online>>,
+    sky: Sky,
     tiles: Query<(&Tile, &Sprite, &Position, Option<&Children>, &Parent)>,
     transitions: Query<(Entity, &Depiction), With<Transition>>,
+    unlit: Query<&Unlit>,
     world_time: Res<WorldTime>,
     zones: Query<(&Zone, &Children)>,
 ) {
     for command in commands.iter() {
         if let Command::Look(target) = &command.command {
-            let (client, character, tile) =
+            let (client, character, tile_entity) =
                 value_or_continue!(players.iter().find(|(c, _, _)| c.id == command.from));
+            let tile_entity = tile_entity.get();
             let (tile, sprite, position, siblings, zone) =
-                value_or_continue!(tiles.get(tile.get()).ok());
+                value_or_continue!(tiles.get(tile_entity).ok());
 
             let output: String;
+            let is_dark = unlit.contains(tile_entity);
 
-            if let Some(target) = target {
-                let matching_item = siblings
+            if target.is_some() && is_dark {
+                output = "It is too dark to see.".into();
+            } else if let Some(target) = target {
+                let candidates = siblings
                     .iter()
                     .flat_map(|siblings| siblings.iter())
-                    .filter_map(|sibling| items.get(*sibling).ok())
-                    .find(|(entity, depiction, _, _)| depiction.matches_query(entity, target));
+                    .filter_map(|sibling| {
+                        items
+                            .get(*sibling)
+                            .map(|(entity, depiction, _, _)| (entity, depiction))
+                            .or_else(|_| transitions.get(*sibling))
+                            .or_else(|_| {
+                                npcs.get(*sibling)
+                                    .map(|(entity, depiction, _, _)| (entity, depiction))
+                            })
+                            .ok()
+                    })
+                    .chain(
+                        doors
+                            .iter()
+                            .filter(|(_, _, doorway)| {
+                                doorway.from == tile_entity || doorway.to == tile_entity
+                            })
+                            .map(|(entity, depiction, _)| (entity, depiction)),
+                    )
+                    .collect::<Vec<_>>();
 
-                let matching_transition = siblings
-                    .iter()
-                    .flat_map(|siblings| siblings.iter())
-                    .filter_map(|sibling| transitions.get(*sibling).ok())
-                    .find(|(entity, depiction)| depiction.matches_query(entity, target));
+                let found = match target.resolve(&candidates) {
+                    Ok(found) => found.first().copied(),
+                    Err(TargetError::NotFound) => None,
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
 
-                let matching_npc = siblings
-                    .iter()
-                    .flat_map(|siblings| siblings.iter())
-                    .filter_map(|sibling| npcs.get(*sibling).ok())
-                    .find(|(entity, depiction, _)| depiction.matches_query(entity, target));
+                        continue;
+                    }
+                };
+
+                let matching_item = found.and_then(|entity| items.get(entity).ok());
+                let matching_transition = found.and_then(|entity| transitions.get(entity).ok());
+                let matching_npc = found.and_then(|entity| npcs.get(entity).ok());
+                let matching_door = found.and_then(|entity| doors.get(entity).ok());
 
                 let matching_player = siblings
                     .iter()
                     .flat_map(|siblings| siblings.iter())
                     .filter_map(|sibling| players.get(*sibling).ok())
-                    .find(|(_, c, _)| &c.name.to_lowercase() == target);
+                    .find(|(_, c, _)| c.name.to_lowercase() == target.query);
 
                 if let Some((_, depiction, surface, children)) = matching_item {
                     let surface_line = surface
@@ -115,7 +147,9 @@ pub fn look(
                     output = paint!("{}{}", depiction.description, surface_line);
                 } else if let Some((_, depiction)) = matching_transition {
                     output = paint!("{}", depiction.description,);
-                } else if let Some((_, depiction, _)) = matching_npc {
+                } else if let Some((_, depiction, _, _)) = matching_npc {
+                    output = paint!("{}", depiction.description,);
+                } else if let Some((_, depiction, _)) = matching_door {
                     output = paint!("{}", depiction.description,);
                 } else if let Some((_, character, _)) = matching_player {
                     output = character.description.clone().unwrap_or(format!(
@@ -128,9 +162,21 @@ pub fn look(
             } else {
                 let (zone, zone_tiles) = value_or_continue!(zones.get(zone.get()).ok());
 
-                let exits = get_exits(position, zone_tiles, &tiles);
-                let items_line = get_items_line(siblings, &items);
-                let npcs_line = get_npcs_line(siblings, &npcs);
+                let exits = get_exits(tile_entity, position, zone_tiles, &tiles, &exits, &doorways);
+                let (description, items_line, npcs_line) = if is_dark {
+                    ("It is too dark to see.".into(), "".into(), "".into())
+                } else {
+                    let description = match sky.weather(tile_entity) {
+                        Some(weather) => format!("{} {}", tile.description, weather.description()),
+                        None => tile.description.clone(),
+                    };
+
+                    (
+                        description,
+                        get_items_line(siblings, &items),
+                        get_npcs_line(siblings, &npcs),
+                    )
+                };
                 let players_line = get_players_line(client, siblings, &players);
 
                 output = if character.config.brief {
@@ -143,7 +189,7 @@ pub fn look(
                         exits,
                         zone.name,
                         world_time.time_string(),
-                        tile.description,
+                        description,
                         items_line,
                         npcs_line,
                         players_line,
@@ -158,29 +204,41 @@ pub fn look(
     }
 }
 
+/// Lists the ways out of a tile, marking the ones behind a closed door.
 fn get_exits(
+    tile: Entity,
     position: &Position,
     zone_tiles: &Children,
     tiles: &Query<(&Tile, &Sprite, &Position, Option<&Children>, &Parent)>,
+    exits: &Query<&Exits>,
+    doorways: &Query<(&Doorway, &Depiction, Option<&Openable>)>,
 ) -> String {
     let directions = vec!["n", "ne", "e", "se", "s", "sw", "w", "nw", "u", "d"];
-    let mut exits: Vec<String> = vec![];
-
-    for tile in zone_tiles.iter() {
-        if let Ok((_, _, p, _, _)) = tiles.get(*tile) {
-            directions.iter().for_each(|direction| {
-                if p.0 == position.0 + offset_for_direction(direction).unwrap() {
-                    exits.push(direction.to_uppercase());
-                }
-            });
+    let mut found: Vec<String> = vec![];
+
+    for direction in directions {
+        let offset = offset_for_direction(direction).unwrap();
+
+        let Some(neighbour) = zone_tiles.iter().find(|child| {
+            tiles
+                .get(**child)
+                .is_ok_and(|(_, _, p, _, _)| p.0 == position.0 + offset)
+        }) else {
+            continue;
+        };
+
+        match passage(tile, *neighbour, offset, exits, doorways) {
+            Passage::Open => found.push(direction.to_uppercase()),
+            Passage::Closed(_) => found.push(format!("{} (closed)", direction.to_uppercase())),
+            Passage::Blocked => {}
         }
     }
 
-    if exits.is_empty() {
+    if found.is_empty() {
         return "".into();
     }
 
-    format!(" [{}]", exits.join(", "))
+    format!(" [{}]", found.join(", "))
 }
 
 fn get_players_line(
@@ -215,29 +273,50 @@ fn get_players_line(
 
 fn get_npcs_line(
     siblings: Option<&Children>,
-    npcs: &Query<(Entity, &Depiction, Option<&Interactions>), With<Npc>>,
+    npcs: &Query<(Entity, &Depiction, Option<&Interactions>, Option<&Activity>), With<Npc>>,
 ) -> String {
-    let npcs_found = siblings
+    let (busy, idle): (Vec<_>, Vec<_>) = siblings
         .iter()
         .flat_map(|children| children.iter())
         .filter_map(|sibling| npcs.get(*sibling).ok())
-        .filter(|(_, depiction, _)| depiction.visible)
-        .map(|(_, depiction, _)| depiction.short_name.clone())
+        .filter(|(_, depiction, _, _)| depiction.visible)
+        .partition(|(_, _, _, activity)| activity.is_some());
+
+    let mut lines = busy
+        .iter()
+        .filter_map(|(_, depiction, _, activity)| {
+            activity.map(|activity| {
+                let name = name_list(
+                    std::slice::from_ref(&depiction.short_name),
+                    Some(Color::Npc),
+                    true,
+                );
+
+                format!("\n\n{} {}.", capitalize(&name), activity.0)
+            })
+        })
+        .collect::<String>();
+
+    let npcs_found = idle
+        .iter()
+        .map(|(_, depiction, _, _)| depiction.short_name.clone())
         .collect::<Vec<String>>();
 
-    if npcs_found.is_empty() {
-        return "".into();
+    if !npcs_found.is_empty() {
+        let npc_names = name_list(&npcs_found, Some(Color::Npc), true);
+
+        lines.push_str(&format!("\n\n{} are here.", capitalize(&npc_names)));
     }
 
-    let npc_names = name_list(&npcs_found, Some(Color::Npc), true);
+    lines
+}
 
-    let formatted = format!(
+fn capitalize(text: &str) -> String {
+    format!(
         "{}{}",
-        npc_names.chars().next().unwrap_or_default().to_uppercase(),
-        &npc_names[1..]
-    );
-
-    format!("\n\n{} are here.", formatted,)
+        text.chars().next().unwrap_or_default().to_uppercase(),
+        text.chars().skip(1).collect::<String>()
+    )
 }
 
 fn get_items_line(
@@ -297,13 +376,23 @@ fn items_on_surface(
 mod tests {
     use crate::{
         items::components::SurfaceKind,
+        spatial::{
+            components::Lighting,
+            systems::{link_doors, update_lighting},
+        },
         test::{
             app_builder::AppBuilder,
+            door_builder::DoorBuilder,
             item_builder::ItemBuilder,
+            npc_builder::NpcBuilder,
             player_builder::PlayerBuilder,
             tile_builder::{TileBuilder, ZoneBuilder},
             utils::{get_message_content, send_message},
         },
+        weather::{
+            components::{Climate, Weather},
+            resources::WeatherState,
+        },
     };
 
     use super::*;
@@ -330,6 +419,44 @@ mod tests {
         assert_eq!(content, "x The Void - V (00:00am)\nA vast, empty void.");
     }
 
+    #[test]
+    fn describes_weather_outdoors() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(look);
+
+        let zone = ZoneBuilder::new()
+            .name("V")
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        app.world.entity_mut(zone).insert(Climate {
+            region: "void".into(),
+            initial: Weather::Clear,
+            changes: vec![],
+        });
+        app.world
+            .resource_mut::<WeatherState>()
+            .0
+            .insert("void".into(), Weather::Rain);
+
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Void")
+            .description("A vast, empty void.")
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "x The Void - V (00:00am)\nA vast, empty void. It's raining."
+        );
+    }
+
     #[test]
     fn sends_item_info() {
         let mut app = AppBuilder::new().build();
@@ -444,6 +571,226 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hides_blocked_exits_and_marks_closed_doors() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((link_doors, look));
+
+        let zone = ZoneBuilder::new().name("V").build(&mut app);
+
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Void")
+            .description("A vast, empty void.")
+            .position(IVec3::new(0, 0, 0))
+            .blocked("n")
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, -1, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        DoorBuilder::new()
+            .direction("e")
+            .open(false)
+            .build(&mut app, tile);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "x The Void [E (closed), S] - V (00:00am)\nA vast, empty void."
+        );
+    }
+
+    #[test]
+    fn too_dark_to_see() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((update_lighting, look));
+
+        let zone = ZoneBuilder::new().name("V").build(&mut app);
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Cellar")
+            .description("A damp cellar.")
+            .lighting(Lighting::Dark)
+            .build(&mut app, zone);
+
+        ItemBuilder::new()
+            .short_name("rock")
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "x The Cellar - V (00:00am)\nIt is too dark to see."
+        );
+    }
+
+    #[test]
+    fn carried_light() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((update_lighting, look));
+
+        let zone = ZoneBuilder::new().name("V").build(&mut app);
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Cellar")
+            .description("A damp cellar.")
+            .lighting(Lighting::Dark)
+            .build(&mut app, zone);
+
+        let torch = ItemBuilder::new()
+            .short_name("torch")
+            .light_source(Some(60.0))
+            .build(&mut app);
+
+        let (_, client_id, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        app.world.entity_mut(inventory.unwrap()).add_child(torch);
+
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "x The Cellar - V (00:00am)\nA damp cellar.");
+    }
+
+    #[test]
+    fn light_taken_away() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((update_lighting, look));
+
+        let zone = ZoneBuilder::new().name("V").build(&mut app);
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Cellar")
+            .description("A damp cellar.")
+            .lighting(Lighting::Dark)
+            .build(&mut app, zone);
+
+        let torch = ItemBuilder::new()
+            .short_name("torch")
+            .light_source(None)
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+
+        app.world.entity_mut(torch).despawn_recursive();
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "x The Cellar - V (00:00am)\nIt is too dark to see."
+        );
+    }
+
+    #[test]
+    fn outdoor_daylight() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((update_lighting, look));
+
+        let zone = ZoneBuilder::new()
+            .name("V")
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Field")
+            .description("An open field.")
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "x The Field - V (00:00am)\nIt is too dark to see.");
+
+        app.world.resource_mut::<WorldTime>().hour = 12;
+        app.update();
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "x The Field - V (12:00pm)\nAn open field.");
+    }
+
+    #[test]
+    fn npc_activity() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(look);
+
+        let zone = ZoneBuilder::new().name("V").build(&mut app);
+        let tile = TileBuilder::new()
+            .sprite("x")
+            .name("The Roaring Lion")
+            .description("A busy tavern.")
+            .build(&mut app, zone);
+
+        let innkeeper = NpcBuilder::new()
+            .short_name("innkeeper")
+            .tile(tile)
+            .build(&mut app);
+
+        app.world
+            .entity_mut(innkeeper)
+            .insert(Activity("is polishing glasses behind the bar".into()));
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "look");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "x The Roaring Lion - V (00:00am)\nA busy tavern.\n\nAn innkeeper is polishing glasses behind the bar."
+        );
+    }
+
     #[test]
     fn other_player() {
         let mut app = AppBuilder::new().build();
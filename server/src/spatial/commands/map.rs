@@ -10,6 +10,7 @@ use crate::{
     spatial::components::{Position, Tile, Zone},
     value_or_continue,
     visual::components::Sprite,
+    wilderness::{components::Wilderness, utils::biome_at},
 };
 
 static REGEX: OnceLock<Regex> = OnceLock::new();
@@ -28,14 +29,14 @@ pub fn map(
     mut outbox: EventWriter<Outbox>,
     players: Query<(&Client, &Parent), With<Online>>,
     tiles: Query<(&Position, &Sprite, &Parent), With<Tile>>,
-    zones: Query<(&Zone, &Children)>,
+    zones: Query<(&Zone, &Children, Option<&Wilderness>)>,
 ) {
     for command in commands.iter() {
         if let Command::Map = &command.command {
             let (client, tile) =
                 value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
             let (position, _, zone) = value_or_continue!(tiles.get(tile.get()).ok());
-            let (zone, zone_tiles) = value_or_continue!(zones.get(zone.get()).ok());
+            let (zone, zone_tiles, wilderness) = value_or_continue!(zones.get(zone.get()).ok());
 
             let height = 24;
             let width = if client.width % 2 == 1 {
@@ -55,15 +56,26 @@ pub fn map(
                 for y in start_y..=end_y {
                     if x == position.0.x && y == position.0.y {
                         map[(y - start_y) as usize][(x - start_x) as usize] = '@';
-                    } else if let Some(sprite) = zone_tiles.iter().find_map(|child| {
-                        tiles
-                            .get(*child)
-                            .ok()
-                            .filter(|(p, _, _)| p.0 == IVec3::new(x, y, position.0.z))
-                            .map(|(_, s, _)| s)
-                    }) {
+                    } else if let Some(sprite) = zone_tiles
+                        .iter()
+                        .find_map(|child| {
+                            tiles
+                                .get(*child)
+                                .ok()
+                                .filter(|(p, _, _)| p.0 == IVec3::new(x, y, position.0.z))
+                                .map(|(_, s, _)| &s.character)
+                        })
+                        // Wilderness away from players has no tiles spawned, but still shows.
+                        .or_else(|| {
+                            wilderness
+                                .and_then(|wilderness| {
+                                    biome_at(wilderness, IVec3::new(x, y, position.0.z))
+                                })
+                                .map(|biome| &biome.sprite)
+                        })
+                    {
                         map[(y - start_y) as usize][(x - start_x) as usize] =
-                            sprite.character.chars().next().unwrap_or(' ');
+                            sprite.chars().next().unwrap_or(' ');
                     }
                 }
             }
@@ -2,5 +2,9 @@ pub mod enter;
 pub mod look;
 pub mod map;
 pub mod movement;
+pub mod path;
 pub mod scan;
+pub mod speedwalk;
+pub mod stop;
 pub mod teleport;
+pub mod travel;
@@ -6,12 +6,15 @@ use regex::Regex;
 
 use crate::{
     input::events::{Command, ParseError, ParsedCommand, ProxyCommand},
+    interact::components::Openable,
     player::components::{Character, Client, Online},
     spatial::{
-        components::{Position, Tile, Zone},
-        utils::offset_for_direction,
+        components::{Doorway, Exits, Position, Tile, Zone},
+        events::{EntityMoved, MoveMethod},
+        utils::{full_direction, offset_for_direction, passage, Passage},
     },
     value_or_continue,
+    visual::components::Depiction,
 };
 
 static REGEX: OnceLock<Regex> = OnceLock::new();
@@ -30,6 +33,9 @@ pub fn handle_movement(content: &str) -> Result<Command, ParseError> {
 pub fn movement(
     mut bevy: Commands,
     mut commands: EventReader<ParsedCommand>,
+    doorways: Query<(&Doorway, &Depiction, Option<&Openable>)>,
+    exits: Query<&Exits>,
+    mut moved: EventWriter<EntityMoved>,
     mut proxy: EventWriter<ProxyCommand>,
     mut outbox: EventWriter<Outbox>,
     mut players: Query<(Entity, &Client, &Character, &Parent), With<Online>>,
@@ -55,7 +61,8 @@ pub fn movement(
             };
 
             let Some(target) = zone_tiles.iter().find_map(|child| {
-                tiles.get(*child)
+                tiles
+                    .get(*child)
                     .ok()
                     .filter(|(_, p, _)| p.0 == position.0 + offset)
                     .map(|(e, _, _)| e)
@@ -65,8 +72,30 @@ pub fn movement(
                 continue;
             };
 
+            match passage(tile.get(), target, offset, &exits, &doorways) {
+                Passage::Open => {}
+                Passage::Closed(door) => {
+                    outbox.send_text(client.id, format!("The {door} is closed."));
+
+                    continue;
+                }
+                Passage::Blocked => {
+                    outbox.send_text(client.id, "You can't go that way.");
+
+                    continue;
+                }
+            }
+
             bevy.entity(player).set_parent(target);
 
+            moved.send(EntityMoved {
+                entity: player,
+                from: tile.get(),
+                to: target,
+                direction: full_direction(direction).map(String::from),
+                method: MoveMethod::Walk,
+            });
+
             proxy.send(ProxyCommand(ParsedCommand {
                 from: client.id,
                 command: Command::Look(None),
@@ -77,11 +106,15 @@ pub fn movement(
 
 #[cfg(test)]
 mod tests {
-    use crate::test::{
-        app_builder::AppBuilder,
-        player_builder::PlayerBuilder,
-        tile_builder::{TileBuilder, ZoneBuilder},
-        utils::{get_message_content, send_message},
+    use crate::{
+        spatial::systems::{announce_movement, link_doors},
+        test::{
+            app_builder::AppBuilder,
+            door_builder::DoorBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
     };
 
     use super::*;
@@ -110,6 +143,43 @@ mod tests {
         assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
     }
 
+    #[test]
+    fn announces_movement() {
+        let mut app = AppBuilder::new().build();
+
+        app.add_systems((movement, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .name("Ashur")
+            .tile(start)
+            .build(&mut app);
+        let (_, watcher, _) = PlayerBuilder::new().tile(start).build(&mut app);
+        let (_, greeter, _) = PlayerBuilder::new().tile(destination).build(&mut app);
+
+        send_message(&mut app, client_id, "s");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, watcher).unwrap(),
+            "Ashur leaves south."
+        );
+        assert_eq!(
+            get_message_content(&mut app, greeter).unwrap(),
+            "Ashur arrives from the north."
+        );
+        assert!(get_message_content(&mut app, client_id).is_none());
+    }
+
     #[test]
     fn no_exit() {
         let mut app = AppBuilder::new().build();
@@ -130,4 +200,137 @@ mod tests {
 
         assert_eq!(content, "You can't go that way.");
     }
+
+    #[test]
+    fn blocked_exit() {
+        let mut app = AppBuilder::new().build();
+
+        app.add_system(movement);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .blocked("north")
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "south");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You can't go that way.");
+        assert_ne!(app.world.get::<Parent>(player).unwrap().get(), destination);
+    }
+
+    #[test]
+    fn one_way_exit() {
+        let mut app = AppBuilder::new().build();
+
+        app.add_system(movement);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .one_way("s")
+            .build(&mut app, zone);
+
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "south");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
+
+        send_message(&mut app, client_id, "north");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You can't go that way.");
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
+    }
+
+    #[test]
+    fn closed_door() {
+        let mut app = AppBuilder::new().build();
+
+        app.add_systems((link_doors, movement));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        DoorBuilder::new()
+            .name("oak door")
+            .direction("n")
+            .open(false)
+            .build(&mut app, destination);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "south");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The oak door is closed.");
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), start);
+    }
+
+    #[test]
+    fn door_links_once_its_tile_exists() {
+        let mut app = AppBuilder::new().build();
+
+        app.add_systems((link_doors, movement));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        DoorBuilder::new()
+            .name("oak door")
+            .direction("s")
+            .open(false)
+            .build(&mut app, start);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        app.update();
+
+        TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        app.update();
+
+        send_message(&mut app, client_id, "south");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "The oak door is closed.");
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), start);
+    }
 }
@@ -0,0 +1,249 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    player::components::{Client, Online},
+    spatial::{
+        components::{Landmark, Tile},
+        pathfinding::{describe_path, Pathfinder},
+    },
+    value_or_continue,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_path(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^path( (?P<place>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let place = captures
+                .name("place")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Path to where?".into()))?;
+
+            Ok(Command::Path(place.to_lowercase()))
+        }
+    }
+}
+
+pub fn path(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    pathfinder: Pathfinder,
+    places: Query<(Entity, &Tile, Option<&Landmark>)>,
+    players: Query<(&Client, &Parent), With<Online>>,
+) {
+    for command in commands.iter() {
+        if let Command::Path(place) = &command.command {
+            let (client, tile) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            let Some((destination, name)) = find_place(place, &places) else {
+                outbox.send_text(
+                    client.id,
+                    format!("You don't know of a place called {place}."),
+                );
+
+                continue;
+            };
+
+            if destination == tile.get() {
+                outbox.send_text(client.id, "You are already there.");
+
+                continue;
+            }
+
+            let Some(steps) = pathfinder.find(tile.get(), destination) else {
+                outbox.send_text(client.id, format!("You can't find a way to {name}."));
+
+                continue;
+            };
+
+            outbox.send_text(
+                client.id,
+                format!("To reach {name}: {}.", describe_path(&steps)),
+            );
+        }
+    }
+}
+
+/// Finds a tile by landmark name, then by the tile's own name, then by part of its name.
+pub fn find_place(
+    place: &str,
+    places: &Query<(Entity, &Tile, Option<&Landmark>)>,
+) -> Option<(Entity, String)> {
+    let place = place.to_lowercase();
+
+    places
+        .iter()
+        .find(|(_, _, landmark)| {
+            landmark.is_some_and(|landmark| landmark.name.to_lowercase() == place)
+        })
+        .or_else(|| {
+            places
+                .iter()
+                .find(|(_, tile, _)| tile.name.to_lowercase() == place)
+        })
+        .or_else(|| {
+            places
+                .iter()
+                .find(|(_, tile, _)| tile.name.to_lowercase().contains(&place))
+        })
+        .map(|(entity, tile, _)| (entity, tile.name.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        spatial::systems::link_doors,
+        test::{
+            app_builder::AppBuilder,
+            door_builder::DoorBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            transition_builder::TransitionBuilder,
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn directions_within_zone() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(path);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, -1, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, -2, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .name("Market Square")
+            .position(IVec3::new(1, -3, 0))
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "path market");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "To reach Market Square: 2 north, northeast.");
+    }
+
+    #[test]
+    fn avoids_closed_doors() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((link_doors, path));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .name("Vault")
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+
+        DoorBuilder::new()
+            .direction("e")
+            .open(false)
+            .build(&mut app, start);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        app.update();
+
+        send_message(&mut app, client_id, "path vault");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You can't find a way to Vault.");
+    }
+
+    #[test]
+    fn through_transitions() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(path);
+
+        let outside = ZoneBuilder::new().build(&mut app);
+        let inside = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, outside);
+        let hall = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, inside);
+        TileBuilder::new()
+            .name("Cellar")
+            .position(IVec3::new(0, 0, -1))
+            .build(&mut app, inside);
+
+        TransitionBuilder::new()
+            .tags(&vec!["door"])
+            .build(&mut app, start, hall);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "path cellar");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "To reach Cellar: enter door, down.");
+    }
+
+    #[test]
+    fn shortcut_through_transition() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(path);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let alcove = TileBuilder::new()
+            .position(IVec3::new(-1, 0, 0))
+            .build(&mut app, zone);
+
+        for x in 1..4 {
+            TileBuilder::new()
+                .position(IVec3::new(x, 0, 0))
+                .build(&mut app, zone);
+        }
+
+        let tower = TileBuilder::new()
+            .name("Tower")
+            .position(IVec3::new(4, 0, 0))
+            .build(&mut app, zone);
+
+        TransitionBuilder::new()
+            .tags(&vec!["portal"])
+            .build(&mut app, alcove, tower);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "path tower");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "To reach Tower: west, enter portal.");
+    }
+}
@@ -5,12 +5,16 @@ use bevy_nest::prelude::*;
 use regex::Regex;
 
 use crate::{
-    input::events::{Command, ParseError, ParsedCommand},
+    input::{
+        events::{Command, ParseError, ParsedCommand},
+        target::{Target, TargetError},
+    },
     items::components::{Inventory, Item, Surface},
     player::components::{Character, Client, Online},
     spatial::components::Tile,
     value_or_continue,
     visual::components::Depiction,
+    weather::utils::Sky,
 };
 
 static REGEX: OnceLock<Regex> = OnceLock::new();
@@ -25,9 +29,7 @@ pub fn handle_scan(content: &str) -> Result<Command, ParseError> {
         Some(captures) => {
             let inventory = captures.name("inventory").is_some();
 
-            let target = captures
-                .name("target")
-                .map(|m| m.as_str().trim().to_lowercase());
+            let target = captures.name("target").map(|m| Target::parse(m.as_str()));
 
             Ok(Command::Scan((inventory, target)))
         }
@@ -46,6 +48,7 @@ pub fn scan(
     mut commands: EventReader<ParsedCommand>,
     mut outbox: EventWriter<Outbox>,
     players: Query<(Entity, &Client, &Character, &Parent, &Children), With<Online>>,
+    sky: Sky,
     tiles: Query<&Children, With<Tile>>,
 ) {
     for command in commands.iter() {
@@ -60,14 +63,37 @@ pub fn scan(
                     .map(|(_, inventory)| inventory.iter().collect())
                     .unwrap_or_else(Vec::new)
             } else if let Some(target) = target {
-                items
+                let surfaces = tiles
+                    .get(tile.get())
+                    .ok()
                     .iter()
+                    .flat_map(|siblings| siblings.iter())
+                    .filter_map(|sibling| items.get(*sibling).ok())
                     .filter(|(_, _, _, surface, _)| surface.is_some())
-                    .find(|(entity, _, depiction, _, _)| depiction.matches_query(entity, target))
-                    .and_then(|(_, _, _, _, children)| children)
-                    .map(|children| children.iter().collect())
-                    .unwrap_or_else(Vec::new)
+                    .map(|(entity, _, depiction, _, _)| (entity, depiction))
+                    .collect::<Vec<_>>();
+
+                match target.resolve(&surfaces) {
+                    Ok(found) => items
+                        .get(found[0])
+                        .ok()
+                        .and_then(|(_, _, _, _, children)| children)
+                        .map(|children| children.iter().collect())
+                        .unwrap_or_default(),
+                    Err(TargetError::NotFound) => Vec::new(),
+                    Err(TargetError::Ambiguous(question)) => {
+                        outbox.send_text(client.id, question);
+
+                        continue;
+                    }
+                }
             } else {
+                if let Some(obscured) = sky.weather(tile.get()).and_then(|w| w.obscured()) {
+                    outbox.send_text(client.id, obscured);
+
+                    continue;
+                }
+
                 tiles
                     .get(tile.get())
                     .ok()
@@ -101,6 +127,7 @@ pub fn scan(
 mod tests {
     use crate::{
         items::components::SurfaceKind,
+        spatial::components::Lighting,
         test::{
             app_builder::AppBuilder,
             item_builder::ItemBuilder,
@@ -108,6 +135,10 @@ mod tests {
             tile_builder::{TileBuilder, ZoneBuilder},
             utils::{get_message_content, send_message},
         },
+        weather::{
+            components::{Climate, Weather},
+            resources::WeatherState,
+        },
     };
 
     use super::*;
@@ -195,4 +226,42 @@ mod tests {
 
         assert_eq!(content, format!("#{}: rock", rock.index()));
     }
+
+    #[test]
+    fn fog_hides_tile() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(scan);
+
+        let zone = ZoneBuilder::new()
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        app.world.entity_mut(zone).insert(Climate {
+            region: "moor".into(),
+            initial: Weather::Fog,
+            changes: vec![],
+        });
+        app.world
+            .resource_mut::<WeatherState>()
+            .0
+            .insert("moor".into(), Weather::Fog);
+
+        let tile = TileBuilder::new().build(&mut app, zone);
+        ItemBuilder::new()
+            .short_name("stick")
+            .tile(tile)
+            .build(&mut app);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "scan");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "The fog is too thick to make anything out."
+        );
+    }
 }
@@ -0,0 +1,258 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::input::events::{Command, ParseError};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+static STEP_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// The most steps a single speedwalk may take.
+const MAX_STEPS: usize = 50;
+
+/// Expands a speedwalk like `run 3n2e` or `.3n2e` into the movement commands it stands for.
+pub fn handle_speedwalk(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^(run(\s+|$)|\.)(?P<path>.*)$").unwrap());
+    let step_regex = STEP_REGEX.get_or_init(|| {
+        Regex::new(r"(?P<count>\d*)(?P<direction>ne|nw|se|sw|n|e|s|w|u|d)").unwrap()
+    });
+
+    let Some(captures) = regex.captures(content) else {
+        return Err(ParseError::WrongCommand);
+    };
+
+    let path = captures
+        .name("path")
+        .map(|m| m.as_str().replace(' ', "").to_lowercase())
+        .unwrap_or_default();
+
+    let invalid =
+        || ParseError::InvalidArguments("Run where? Try something like `run 3n2e`.".into());
+
+    if path.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut commands = vec![];
+    let mut end = 0;
+
+    for step in step_regex.captures_iter(&path) {
+        let whole = step.get(0).unwrap();
+
+        if whole.start() != end {
+            return Err(invalid());
+        }
+
+        end = whole.end();
+
+        let count = match step.name("count").map(|m| m.as_str()) {
+            None | Some("") => 1,
+            Some(count) => count.parse::<usize>().map_err(|_| invalid())?,
+        };
+
+        if commands.len() + count > MAX_STEPS {
+            return Err(ParseError::InvalidArguments(
+                "You can't run that far in one go.".into(),
+            ));
+        }
+
+        let direction = step.name("direction").unwrap().as_str();
+
+        commands.extend((0..count).map(|_| Command::Movement(direction.into())));
+    }
+
+    if end != path.len() || commands.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(Command::Speedwalk(commands))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        input::{components::CommandQueue, events::Command},
+        items::commands::inventory::inventory,
+        player::components::{Character, CharacterState},
+        spatial::commands::movement::movement,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    fn next_step(app: &mut App, player: Entity) {
+        app.world
+            .get_mut::<CommandQueue>(player)
+            .unwrap()
+            .timer
+            .set_elapsed(Duration::from_secs_f32(0.5));
+        app.update();
+    }
+
+    #[test]
+    fn runs_one_step_at_a_time() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(movement);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let middle = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+        let end = TileBuilder::new()
+            .position(IVec3::new(1, 1, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "run s1e");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), middle);
+
+        next_step(&mut app, player);
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), end);
+        assert!(app.world.get::<CommandQueue>(player).is_none());
+    }
+
+    #[test]
+    fn queues_commands_sent_together() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(movement);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let middle = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+        let end = TileBuilder::new()
+            .position(IVec3::new(0, 2, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "s\r\ns");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), middle);
+
+        next_step(&mut app, player);
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), end);
+    }
+
+    #[test]
+    fn runs_other_commands_right_away() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((movement, inventory));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let middle = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, 2, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .tile(start)
+            .has_inventory()
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "s\r\ns\r\ninventory");
+        app.update();
+
+        let queue = app.world.get::<CommandQueue>(player).unwrap();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), middle);
+        assert_eq!(queue.commands.len(), 1);
+        assert!(matches!(&queue.commands[0], Command::Movement(direction) if direction == "s"));
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You are not carrying anything."
+        );
+    }
+
+    #[test]
+    fn cancelled_by_combat() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(movement);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let middle = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(0, 2, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, ".2s");
+        app.update();
+
+        app.world.get_mut::<Character>(player).unwrap().state = CharacterState::Combat(zone);
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), middle);
+        assert!(app.world.get::<CommandQueue>(player).is_none());
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You abandon your queued commands."
+        );
+    }
+
+    #[test]
+    fn invalid_path() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "run 3x");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Run where? Try something like `run 3n2e`."
+        );
+    }
+
+    #[test]
+    fn too_far() {
+        let mut app = AppBuilder::new().build();
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "run 99n");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You can't run that far in one go."
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        components::CommandQueue,
+        events::{Command, ParseError, ParsedCommand},
+    },
+    player::components::{Client, Online},
+    value_or_continue,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_stop(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^stop$").unwrap());
+
+    match regex.is_match(content) {
+        false => Err(ParseError::WrongCommand),
+        true => Ok(Command::Stop),
+    }
+}
+
+/// Ends what players had queued up. The queue is emptied as the command is parsed, so that
+/// anything sent after `stop` is kept and still runs.
+pub fn stop(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(Entity, &Client, Option<&CommandQueue>), With<Online>>,
+) {
+    for command in commands.iter() {
+        if let Command::Stop = &command.command {
+            let (player, client, queue) =
+                value_or_continue!(players.iter().find(|(_, c, _)| c.id == command.from));
+
+            let Some(queue) = queue else {
+                outbox.send_text(client.id, "You aren't going anywhere.");
+
+                continue;
+            };
+
+            if queue.commands.is_empty() {
+                bevy.entity(player).remove::<CommandQueue>();
+            }
+
+            outbox.send_text(client.id, "You stop.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn stops_queued_commands() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(stop);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "run 3n");
+        app.update();
+
+        assert!(app.world.get::<CommandQueue>(player).is_some());
+
+        send_message(&mut app, client_id, "stop");
+        app.update();
+
+        assert!(app.world.get::<CommandQueue>(player).is_none());
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You stop."
+        );
+    }
+
+    #[test]
+    fn nothing_to_stop() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(stop);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "stop");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You aren't going anywhere."
+        );
+    }
+
+    #[test]
+    fn keeps_commands_after_stop() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(stop);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "run 3n");
+        app.update();
+
+        send_message(&mut app, client_id, "stop\nnorth");
+        app.update();
+
+        let queue = app.world.get::<CommandQueue>(player).unwrap();
+
+        assert_eq!(queue.commands.len(), 1);
+        assert!(matches!(&queue.commands[0], Command::Movement(direction) if direction == "north"));
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You stop."
+        );
+    }
+}
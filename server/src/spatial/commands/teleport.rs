@@ -8,7 +8,10 @@ use crate::{
     input::events::{Command, ParseError, ParsedCommand, ProxyCommand},
     keycard::{Keycard, TELEPORT},
     player::components::{Character, Client, Online},
-    spatial::components::{Position, Tile, Zone},
+    spatial::{
+        components::{Instance, Position, Tile, Zone},
+        events::{EntityMoved, MoveMethod},
+    },
     value_or_continue,
 };
 
@@ -56,11 +59,12 @@ pub fn handle_teleport(content: &str) -> Result<Command, ParseError> {
 pub fn teleport(
     mut bevy: Commands,
     mut commands: EventReader<ParsedCommand>,
+    mut moved: EventWriter<EntityMoved>,
     mut proxy: EventWriter<ProxyCommand>,
     mut outbox: EventWriter<Outbox>,
     mut players: Query<(Entity, &Client, &Keycard, &Character, &Parent), With<Online>>,
     tiles: Query<(Entity, &Position, &Parent), With<Tile>>,
-    zones: Query<(&Zone, &Children)>,
+    zones: Query<(Entity, &Zone, &Children, Option<&Instance>)>,
 ) {
     for command in commands.iter() {
         if let Command::Teleport((zone, (x, y, z))) = &command.command {
@@ -73,25 +77,30 @@ pub fn teleport(
             }
 
             let (_, _, here) = value_or_continue!(tiles.get(tile.get()).ok());
-            let (here, _) = value_or_continue!(zones.get(here.get()).ok());
+            let here = here.get();
 
             let position = IVec3::new(*x, *y, *z);
 
-            let Some((zone, zone_tiles)) = zones.iter().find(|(z, _)| {
-                match zone {
-                    name if name == "here" => z.name == here.name,
+            // Instanced zones can have several copies, so prefer the one we're in, then
+            // one that isn't a copy.
+            let Some((_, zone, zone_tiles, _)) = zones
+                .iter()
+                .filter(|(e, z, _, _)| match zone {
+                    name if name == "here" => *e == here,
                     name => z.name.to_lowercase() == *name,
-                }
-            }) else  {
+                })
+                .min_by_key(|(e, _, _, instance)| (*e != here, instance.is_some()))
+            else {
                 outbox.send_text(client.id, format!("Zone \"{}\" not found.", zone));
 
                 continue;
             };
 
             let Some(target) = zone_tiles.iter().find_map(|child| {
-                tiles.get(*child)
+                tiles
+                    .get(*child)
                     .ok()
-                    .filter(|( _, p, _)| p.0 == position)
+                    .filter(|(_, p, _)| p.0 == position)
                     .map(|(e, _, _)| e)
             }) else {
                 outbox.send_text(client.id, format!("No tile found at {}", position));
@@ -106,6 +115,14 @@ pub fn teleport(
 
             bevy.entity(player).set_parent(target);
 
+            moved.send(EntityMoved {
+                entity: player,
+                from: tile.get(),
+                to: target,
+                direction: None,
+                method: MoveMethod::Teleport,
+            });
+
             proxy.send(ProxyCommand(ParsedCommand {
                 from: client.id,
                 command: Command::Look(None),
@@ -116,11 +133,14 @@ pub fn teleport(
 
 #[cfg(test)]
 mod tests {
-    use crate::test::{
-        app_builder::AppBuilder,
-        player_builder::PlayerBuilder,
-        tile_builder::{TileBuilder, ZoneBuilder},
-        utils::{get_message_content, send_message},
+    use crate::{
+        spatial::systems::announce_movement,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
     };
 
     use super::*;
@@ -151,6 +171,42 @@ mod tests {
         assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
     }
 
+    #[test]
+    fn announces_teleport() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((teleport, announce_movement).chain());
+
+        let zone = ZoneBuilder::new().build(&mut app);
+
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        let destination = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .name("Ashur")
+            .role(Keycard::admin())
+            .tile(start)
+            .build(&mut app);
+        let (_, watcher, _) = PlayerBuilder::new().tile(start).build(&mut app);
+        let (_, greeter, _) = PlayerBuilder::new().tile(destination).build(&mut app);
+
+        send_message(&mut app, client_id, "teleport here (0 1 0)");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, watcher).unwrap(),
+            "Ashur vanishes."
+        );
+        assert_eq!(
+            get_message_content(&mut app, greeter).unwrap(),
+            "Ashur appears out of thin air."
+        );
+    }
+
     #[test]
     fn teleports_in_zone() {
         let mut app = AppBuilder::new().build();
@@ -177,6 +233,40 @@ mod tests {
         assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
     }
 
+    #[test]
+    fn prefers_current_instance() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(teleport);
+
+        let copy = |app: &mut App| {
+            let zone = ZoneBuilder::new().name("Crypt").build(app);
+            app.world.entity_mut(zone).insert(Instance {
+                members: vec![],
+                closing: None,
+            });
+
+            let start = TileBuilder::new().position(IVec3::ZERO).build(app, zone);
+            let destination = TileBuilder::new()
+                .position(IVec3::new(0, 1, 0))
+                .build(app, zone);
+
+            (start, destination)
+        };
+
+        copy(&mut app);
+        let (start, destination) = copy(&mut app);
+
+        let (player, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::admin())
+            .tile(start)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "teleport crypt (0 1 0)");
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), destination);
+    }
+
     #[test]
     fn invalid_zone() {
         let mut app = AppBuilder::new().build();
@@ -0,0 +1,184 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::{
+        components::CommandQueue,
+        events::{Command, ParseError, ParsedCommand},
+    },
+    player::components::{Client, Online},
+    spatial::{
+        components::{Landmark, Tile},
+        pathfinding::{Pathfinder, Step},
+    },
+    value_or_continue,
+};
+
+use super::path::find_place;
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_travel(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^travel( (?P<place>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let place = captures
+                .name("place")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Travel where?".into()))?;
+
+            Ok(Command::Travel(place.to_lowercase()))
+        }
+    }
+}
+
+pub fn travel(
+    mut bevy: Commands,
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    pathfinder: Pathfinder,
+    places: Query<(Entity, &Tile, Option<&Landmark>)>,
+    players: Query<(Entity, &Client, &Parent), With<Online>>,
+) {
+    for command in commands.iter() {
+        if let Command::Travel(place) = &command.command {
+            let (player, client, tile) =
+                value_or_continue!(players.iter().find(|(_, c, _)| c.id == command.from));
+
+            let Some((destination, name)) = find_place(place, &places) else {
+                outbox.send_text(
+                    client.id,
+                    format!("You don't know of a place called {place}."),
+                );
+
+                continue;
+            };
+
+            if destination == tile.get() {
+                outbox.send_text(client.id, "You are already there.");
+
+                continue;
+            }
+
+            let Some(steps) = pathfinder.find(tile.get(), destination) else {
+                outbox.send_text(client.id, format!("You can't find a way to {name}."));
+
+                continue;
+            };
+
+            // The steps are queued like a speedwalk, so `stop` and combat end the journey.
+            let steps = steps
+                .into_iter()
+                .map(|path_step| match path_step.step {
+                    Step::Direction(direction) => Command::Movement(direction.into()),
+                    Step::Enter(tag) => Command::Enter(tag),
+                })
+                .collect();
+
+            bevy.entity(player).insert(CommandQueue::new(steps));
+
+            outbox.send_text(client.id, format!("You set off towards {name}."));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        input::components::QUEUE_STEP_SECONDS,
+        player::components::{Character, CharacterState},
+        spatial::commands::movement::movement,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+    };
+
+    use super::*;
+
+    fn take_step(app: &mut App, player: Entity) {
+        if let Some(mut queue) = app.world.get_mut::<CommandQueue>(player) {
+            queue
+                .timer
+                .set_elapsed(Duration::from_secs_f32(QUEUE_STEP_SECONDS));
+        }
+
+        app.update();
+    }
+
+    #[test]
+    fn walks_to_destination() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((travel, movement));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+        let well = TileBuilder::new()
+            .position(IVec3::new(2, 0, 0))
+            .build(&mut app, zone);
+
+        app.world.entity_mut(well).insert(Landmark {
+            name: "Old Well".into(),
+        });
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "travel old well");
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert!(content.starts_with("You set off towards"));
+
+        for _ in 0..2 {
+            take_step(&mut app, player);
+        }
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), well);
+        assert!(app.world.get::<CommandQueue>(player).is_none());
+    }
+
+    #[test]
+    fn cancelled_by_combat() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((travel, movement));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let start = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        TileBuilder::new()
+            .name("Gatehouse")
+            .position(IVec3::new(1, 0, 0))
+            .build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(start).build(&mut app);
+
+        send_message(&mut app, client_id, "travel gatehouse");
+        app.update();
+        app.update();
+
+        app.world.get_mut::<Character>(player).unwrap().state = CharacterState::Combat(zone);
+        take_step(&mut app, player);
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(content, "You abandon your queued commands.");
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), start);
+    }
+}
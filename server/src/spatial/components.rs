@@ -20,9 +20,89 @@ pub struct Tile {
 #[reflect(Schematic)]
 pub struct Spawn;
 
+/// Marks a zone players get a private copy of when they enter it, instead of sharing one.
+/// Nothing is spawned for it until then.
+#[derive(Debug, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Instanced {
+    /// Seconds a copy is kept around once everyone has left it.
+    pub grace: f32,
+}
+
+/// A live copy of an `Instanced` zone.
+#[derive(Component)]
+pub struct Instance {
+    /// Characters, by ID, who are taken back to this copy when they enter the zone again.
+    pub members: Vec<i64>,
+    /// Ticks while nobody is in the copy, which is despawned once it finishes.
+    pub closing: Option<Timer>,
+}
+
+/// Waiting for a copy of the instanced zone named `zone` to be spawned, to be put at
+/// `position` in it.
+#[derive(Component)]
+pub struct Arriving {
+    pub zone: String,
+    pub position: IVec3,
+    pub instance: Option<Entity>,
+}
+
+/// How a tile or zone is lit when nobody brings a light. A tile's own lighting wins over
+/// its zone's, and places with neither are always lit.
+#[derive(Debug, Clone, Copy, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub enum Lighting {
+    Lit,
+    Dark,
+    /// Lit by the sun during the in-game day.
+    Outdoor,
+}
+
+/// Kept on tiles that are too dark to see in right now.
+#[derive(Component)]
+pub struct Unlit;
+
 #[derive(Debug, Component, Schematic, Reflect, FromReflect)]
 #[reflect(Schematic)]
 pub struct Transition {
     pub zone: String,
     pub position: IVec3,
 }
+
+/// Restricts which ways lead out of a tile. Without it, every adjacent tile in the zone
+/// can be walked to.
+#[derive(Debug, Default, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Exits {
+    /// Directions walled off, in both directions.
+    pub blocked: Vec<String>,
+    /// Directions that lead out of this tile but can't be walked back through.
+    pub one_way: Vec<String>,
+}
+
+/// A door between the tile it's in and the adjacent tile in `direction`. Doors only need
+/// to be declared on one side, and block the way both ways while their `Openable` is closed.
+#[derive(Debug, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Door {
+    pub direction: String,
+}
+
+/// The two tiles a door connects, filled in once both have spawned.
+#[derive(Component)]
+pub struct Doorway {
+    pub from: Entity,
+    pub to: Entity,
+}
+
+/// Keeps sound from getting into or out of a tile.
+#[derive(Debug, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Soundproof;
+
+/// A well-known place players can find their way to with `path` and `travel`.
+#[derive(Debug, Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Landmark {
+    pub name: String,
+}
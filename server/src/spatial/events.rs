@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+/// Sent whenever a player or NPC moves from one tile to another, however they got there.
+pub struct EntityMoved {
+    pub entity: Entity,
+    pub from: Entity,
+    pub to: Entity,
+    /// The full name of the direction walked in, for moves between adjacent tiles.
+    pub direction: Option<String>,
+    pub method: MoveMethod,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveMethod {
+    Walk,
+    /// Through a transition into another zone.
+    Enter,
+    Teleport,
+    /// Moved by something else, like a script or an NPC's schedule.
+    Other,
+}
@@ -1,5 +1,8 @@
 pub mod bundles;
 pub mod commands;
 pub mod components;
+pub mod events;
+pub mod pathfinding;
 pub mod plugin;
-mod utils;
+pub mod systems;
+pub mod utils;
@@ -0,0 +1,276 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    interact::components::{Lock, Openable},
+    visual::components::Depiction,
+};
+
+use super::{
+    components::{Doorway, Exits, Position, Tile, Transition, Zone},
+    utils::{offset_for_direction, passage, Passage, DIRECTIONS},
+};
+
+/// How to get from one tile to the next.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Direction(&'static str),
+    /// Go through a transition, identified by its first tag when it has one.
+    Enter(Option<String>),
+}
+
+#[derive(Clone, Debug)]
+pub struct PathStep {
+    pub step: Step,
+    /// The tile this step leads to.
+    pub to: Entity,
+}
+
+/// Tiles by where they are, gathered once per search.
+#[derive(Default)]
+struct TileIndex<'a> {
+    by_position: HashMap<(Entity, IVec3), Entity>,
+    /// Where transitions lead, by the name of the zone and the position in it.
+    by_zone_name: HashMap<(&'a str, IVec3), Entity>,
+}
+
+/// Finds routes between tiles with A*, over adjacent tiles within a zone and through
+/// transitions into other zones. Blocked exits, closed doors and locked or closed
+/// transitions are never used.
+#[derive(SystemParam)]
+pub struct Pathfinder<'w, 's> {
+    doorways: Query<
+        'w,
+        's,
+        (
+            &'static Doorway,
+            &'static Depiction,
+            Option<&'static Openable>,
+        ),
+    >,
+    exits: Query<'w, 's, &'static Exits>,
+    tiles: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Position,
+            &'static Parent,
+            Option<&'static Children>,
+        ),
+        With<Tile>,
+    >,
+    transitions: Query<
+        'w,
+        's,
+        (
+            &'static Transition,
+            &'static Depiction,
+            Option<&'static Lock>,
+            Option<&'static Openable>,
+        ),
+    >,
+    zones: Query<'w, 's, &'static Zone>,
+}
+
+impl<'w, 's> Pathfinder<'w, 's> {
+    pub fn find(&self, from: Entity, to: Entity) -> Option<Vec<PathStep>> {
+        let (_, goal_position, goal_zone, _) = self.tiles.get(to).ok()?;
+
+        let index = self.index();
+
+        // Walking takes at least as many steps as the widest axis apart, diagonals costing
+        // the same as straight steps. A transition could drop the traveller anywhere, so the
+        // only other way to arrive is through one into the goal's zone and on from where it
+        // leads. Tiles in other zones have to take that way.
+        let goal_zone_name = self.zones.get(goal_zone.get()).ok().map(|zone| &zone.name);
+        let distance = |position: IVec3| (position - goal_position.0).abs().max_element();
+
+        let through_transition = self
+            .transitions
+            .iter()
+            .filter(|(transition, _, _, _)| Some(&transition.zone) == goal_zone_name)
+            .map(|(transition, _, _, _)| 1 + distance(transition.position))
+            .min();
+
+        let estimate = |tile: Entity| {
+            let walking = self
+                .tiles
+                .get(tile)
+                .ok()
+                .filter(|(_, _, zone, _)| zone.get() == goal_zone.get())
+                .map(|(_, position, _, _)| distance(position.0));
+
+            walking
+                .into_iter()
+                .chain(through_transition)
+                .min()
+                .unwrap_or(0)
+        };
+
+        let mut open = BinaryHeap::from([(Reverse(estimate(from)), Reverse(0), from)]);
+        let mut costs = HashMap::from([(from, 0)]);
+        let mut came_from: HashMap<Entity, (Entity, Step)> = HashMap::new();
+
+        while let Some((_, Reverse(cost), tile)) = open.pop() {
+            if tile == to {
+                let mut path = vec![];
+                let mut current = to;
+
+                while let Some((previous, step)) = came_from.get(&current) {
+                    path.push(PathStep {
+                        step: step.clone(),
+                        to: current,
+                    });
+
+                    current = *previous;
+                }
+
+                path.reverse();
+
+                return Some(path);
+            }
+
+            if costs.get(&tile).is_some_and(|best| cost > *best) {
+                continue;
+            }
+
+            for (neighbour, step) in self.neighbours(tile, &index, false) {
+                let next_cost = cost + 1;
+
+                if costs.get(&neighbour).is_some_and(|best| next_cost >= *best) {
+                    continue;
+                }
+
+                costs.insert(neighbour, next_cost);
+                came_from.insert(neighbour, (tile, step));
+                open.push((
+                    Reverse(next_cost + estimate(neighbour)),
+                    Reverse(next_cost),
+                    neighbour,
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Every tile that can be reached from any of `from`, including those tiles. Closed
+    /// doors and locked transitions count as open, since players can get through them.
+    pub fn reachable(&self, from: impl IntoIterator<Item = Entity>) -> HashSet<Entity> {
+        let index = self.index();
+
+        let mut reached = HashSet::new();
+        let mut queue = from.into_iter().collect::<Vec<_>>();
+
+        while let Some(tile) = queue.pop() {
+            if !reached.insert(tile) {
+                continue;
+            }
+
+            queue.extend(
+                self.neighbours(tile, &index, true)
+                    .into_iter()
+                    .map(|(neighbour, _)| neighbour)
+                    .filter(|neighbour| !reached.contains(neighbour)),
+            );
+        }
+
+        reached
+    }
+
+    fn index(&self) -> TileIndex<'_> {
+        let mut index = TileIndex::default();
+
+        for (entity, position, zone, _) in self.tiles.iter() {
+            index.by_position.insert((zone.get(), position.0), entity);
+
+            if let Ok(zone) = self.zones.get(zone.get()) {
+                index
+                    .by_zone_name
+                    .entry((zone.name.as_str(), position.0))
+                    .or_insert(entity);
+            }
+        }
+
+        index
+    }
+
+    fn neighbours(
+        &self,
+        tile: Entity,
+        index: &TileIndex,
+        through_doors: bool,
+    ) -> Vec<(Entity, Step)> {
+        let Ok((_, position, zone, children)) = self.tiles.get(tile) else {
+            return vec![];
+        };
+
+        let mut neighbours = DIRECTIONS
+            .iter()
+            .filter_map(|direction| {
+                let offset = offset_for_direction(direction)?;
+                let neighbour = *index.by_position.get(&(zone.get(), position.0 + offset))?;
+
+                match passage(tile, neighbour, offset, &self.exits, &self.doorways) {
+                    Passage::Open => Some((neighbour, Step::Direction(direction))),
+                    Passage::Closed(_) if through_doors => {
+                        Some((neighbour, Step::Direction(direction)))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let transitions = children
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| self.transitions.get(*child).ok())
+            .filter(|(_, _, lock, openable)| {
+                through_doors
+                    || (!lock.is_some_and(|lock| lock.locked)
+                        && !openable.is_some_and(|openable| !openable.open))
+            });
+
+        for (transition, depiction, _, _) in transitions {
+            let target = index
+                .by_zone_name
+                .get(&(transition.zone.as_str(), transition.position));
+
+            if let Some(target) = target {
+                neighbours.push((*target, Step::Enter(depiction.tags.first().cloned())));
+            }
+        }
+
+        neighbours
+    }
+}
+
+/// Turns a path into directions a player can follow, e.g. "2 north, east, enter gate".
+pub fn describe_path(path: &[PathStep]) -> String {
+    let mut parts: Vec<(usize, &Step)> = vec![];
+
+    for step in path.iter().map(|path_step| &path_step.step) {
+        match parts.last_mut() {
+            Some((count, last)) if *last == step && matches!(step, Step::Direction(_)) => {
+                *count += 1;
+            }
+            _ => parts.push((1, step)),
+        }
+    }
+
+    parts
+        .iter()
+        .map(|(count, step)| match step {
+            Step::Direction(direction) if *count > 1 => format!("{count} {direction}"),
+            Step::Direction(direction) => direction.to_string(),
+            Step::Enter(Some(tag)) => format!("enter {tag}"),
+            Step::Enter(None) => "enter".into(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
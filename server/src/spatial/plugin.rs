@@ -2,8 +2,12 @@ use bevy::prelude::*;
 
 use super::{
     bundles::{TileBundle, TransitionBundle},
-    commands::{enter::*, look::*, map::*, movement::*, scan::*, teleport::*},
+    commands::{
+        enter::*, look::*, map::*, movement::*, path::*, scan::*, stop::*, teleport::*, travel::*,
+    },
     components::*,
+    events::EntityMoved,
+    systems::*,
 };
 
 pub struct SpatialPlugin;
@@ -11,14 +15,30 @@ pub struct SpatialPlugin;
 impl Plugin for SpatialPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Vec<String>>()
+            .register_type::<Door>()
+            .register_type::<Exits>()
+            .register_type::<Instanced>()
+            .register_type::<Landmark>()
+            .register_type::<Lighting>()
             .register_type::<Position>()
             .register_type::<Tile>()
+            .register_type::<Soundproof>()
             .register_type::<Spawn>()
             .register_type::<Transition>()
             .register_type::<Zone>()
             .register_type::<TileBundle>()
             .register_type::<TransitionBundle>();
 
-        app.add_systems((look, scan, map, movement, enter, teleport));
+        app.add_event::<EntityMoved>();
+
+        app.add_systems((
+            look, scan, map, movement, enter, teleport, path, travel, stop,
+        ));
+        app.add_systems((
+            link_doors,
+            update_lighting,
+            announce_movement,
+            arrive_in_instances,
+        ));
     }
 }
@@ -0,0 +1,235 @@
+use bevy::prelude::*;
+
+use bevy_nest::prelude::*;
+
+use crate::{
+    input::events::{Command, ParsedCommand, ProxyCommand},
+    items::components::{Inventory, LightSource},
+    npc::components::Wander,
+    player::components::{Character, Client, Online},
+    visual::components::Depiction,
+    world::resources::WorldTime,
+};
+
+use super::{
+    components::{Arriving, Door, Doorway, Lighting, Position, Tile, Unlit, Zone},
+    events::{EntityMoved, MoveMethod},
+    utils::{is_naturally_lit, offset_for_direction, opposite_direction},
+};
+
+/// Links each door to the tile it leads to. Doors stay unlinked until that tile exists, so
+/// they're retried every frame and only warned about when they first appear.
+pub fn link_doors(
+    mut bevy: Commands,
+    doors: Query<(Entity, Ref<Door>, &Parent), Without<Doorway>>,
+    tiles: Query<(Entity, &Position, &Parent), With<Tile>>,
+    zones: Query<&Children, With<Zone>>,
+) {
+    for (entity, door, tile) in doors.iter() {
+        let Some(offset) = offset_for_direction(&door.direction) else {
+            if door.is_added() {
+                warn!(
+                    "Door {:?} has an invalid direction: {}",
+                    entity, door.direction
+                );
+            }
+
+            continue;
+        };
+
+        let Ok((from, position, zone)) = tiles.get(tile.get()) else {
+            continue;
+        };
+
+        let neighbour = zones.get(zone.get()).ok().and_then(|zone_tiles| {
+            zone_tiles.iter().find_map(|child| {
+                tiles
+                    .get(*child)
+                    .ok()
+                    .filter(|(_, p, _)| p.0 == position.0 + offset)
+                    .map(|(e, _, _)| e)
+            })
+        });
+
+        let Some(to) = neighbour else {
+            if door.is_added() {
+                warn!("Door {:?} does not lead to a tile yet", entity);
+            }
+
+            continue;
+        };
+
+        bevy.entity(entity).insert(Doorway { from, to });
+    }
+}
+
+/// Works out which tiles are too dark to see in, whenever a light, the lighting, something's
+/// whereabouts or the time of day has changed.
+pub fn update_lighting(
+    mut bevy: Commands,
+    changed_lights: Query<(), Or<(Changed<LightSource>, Changed<Lighting>)>>,
+    contents: Query<&Children>,
+    mut daytime: Local<Option<bool>>,
+    inventories: Query<&Children, With<Inventory>>,
+    lights: Query<&LightSource>,
+    moved: Query<(), Changed<Parent>>,
+    mut removed_lights: RemovedComponents<LightSource>,
+    tiles: Query<
+        (
+            Entity,
+            Option<&Lighting>,
+            Option<&Children>,
+            &Parent,
+            Option<&Unlit>,
+        ),
+        With<Tile>,
+    >,
+    world_time: Res<WorldTime>,
+    zones: Query<Option<&Lighting>, With<Zone>>,
+) {
+    let is_daytime = world_time.is_daytime();
+    let daybreak = daytime.replace(is_daytime) != Some(is_daytime);
+    let light_removed = removed_lights.iter().count() > 0;
+
+    if !daybreak && !light_removed && changed_lights.is_empty() && moved.is_empty() {
+        return;
+    }
+
+    let is_light = |entity: &Entity| lights.get(*entity).is_ok_and(|light| light.is_lit());
+
+    for (tile, lighting, siblings, zone, unlit) in tiles.iter() {
+        let zone_lighting = zones.get(zone.get()).ok().flatten();
+
+        let lit = is_naturally_lit(lighting, zone_lighting, &world_time)
+            || siblings.iter().flat_map(|s| s.iter()).any(|sibling| {
+                is_light(sibling)
+                    || contents
+                        .get(*sibling)
+                        .iter()
+                        .flat_map(|children| children.iter())
+                        .filter_map(|child| inventories.get(*child).ok())
+                        .flat_map(|items| items.iter())
+                        .any(is_light)
+            });
+
+        if lit && unlit.is_some() {
+            bevy.entity(tile).remove::<Unlit>();
+        } else if !lit && unlit.is_none() {
+            bevy.entity(tile).insert(Unlit);
+        }
+    }
+}
+
+pub fn announce_movement(
+    characters: Query<&Character>,
+    mut moves: EventReader<EntityMoved>,
+    npcs: Query<(&Depiction, Option<&Wander>)>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(Entity, &Client, &Parent), With<Online>>,
+) {
+    for moved in moves.iter() {
+        let (name, verb) = if let Ok(character) = characters.get(moved.entity) {
+            (character.name.clone(), None)
+        } else if let Ok((depiction, wander)) = npcs.get(moved.entity) {
+            (
+                format!("The {}", depiction.short_name),
+                wander.map(|wander| wander.verb.as_str()),
+            )
+        } else {
+            continue;
+        };
+
+        let (departure, arrival) = movement_messages(&name, verb, moved);
+
+        for (player, client, tile) in players.iter() {
+            if player == moved.entity {
+                continue;
+            }
+
+            if tile.get() == moved.from {
+                outbox.send_text(client.id, departure.clone());
+            } else if tile.get() == moved.to {
+                outbox.send_text(client.id, arrival.clone());
+            }
+        }
+    }
+}
+
+/// What others see when someone leaves their tile, and when they arrive at the next.
+fn movement_messages(name: &str, verb: Option<&str>, moved: &EntityMoved) -> (String, String) {
+    let arrival = format!("{name} arrives.");
+
+    match (moved.method, moved.direction.as_deref()) {
+        (MoveMethod::Teleport, _) => (
+            format!("{name} vanishes."),
+            format!("{name} appears out of thin air."),
+        ),
+        (MoveMethod::Walk, Some(direction)) => (
+            format!("{name} {} {direction}.", verb.unwrap_or("leaves")),
+            match opposite_direction(direction) {
+                Some("up") => format!("{name} arrives from above."),
+                Some("down") => format!("{name} arrives from below."),
+                Some(from) => format!("{name} arrives from the {from}."),
+                None => arrival,
+            },
+        ),
+        _ => match verb {
+            Some(verb) => (format!("{name} {verb} away."), arrival),
+            None => (format!("{name} leaves."), arrival),
+        },
+    }
+}
+
+/// Puts players at their spot in the instance spawned for them, once its tiles are there.
+pub fn arrive_in_instances(
+    mut bevy: Commands,
+    mut moved: EventWriter<EntityMoved>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(Entity, &Client, &Parent, &Arriving), With<Online>>,
+    mut proxy: EventWriter<ProxyCommand>,
+    tiles: Query<(Entity, &Position), With<Tile>>,
+    zones: Query<Option<&Children>, With<Zone>>,
+) {
+    for (player, client, tile, arriving) in players.iter() {
+        let Some(zone_tiles) = arriving
+            .instance
+            .and_then(|instance| zones.get(instance).ok())
+            .flatten()
+        else {
+            continue;
+        };
+
+        bevy.entity(player).remove::<Arriving>();
+
+        let Some(target) = zone_tiles
+            .iter()
+            .filter_map(|child| tiles.get(*child).ok())
+            .find(|(_, position)| position.0 == arriving.position)
+            .map(|(target, _)| target)
+        else {
+            warn!(
+                "No tile at {} in instance of {}",
+                arriving.position, arriving.zone
+            );
+
+            outbox.send_text(client.id, "The way in leads nowhere.");
+
+            continue;
+        };
+
+        bevy.entity(player).set_parent(target);
+
+        moved.send(EntityMoved {
+            entity: player,
+            from: tile.get(),
+            to: target,
+            direction: None,
+            method: MoveMethod::Enter,
+        });
+
+        proxy.send(ProxyCommand(ParsedCommand {
+            from: client.id,
+            command: Command::Look(None),
+        }));
+    }
+}
@@ -1,5 +1,33 @@
 use bevy::prelude::*;
 
+use crate::{
+    interact::components::Openable, visual::components::Depiction, world::resources::WorldTime,
+};
+
+use super::components::{Doorway, Exits, Lighting};
+
+/// Whether a way between two adjacent tiles can be walked through.
+pub enum Passage {
+    Open,
+    /// A closed door is in the way, with the door's name.
+    Closed(String),
+    Blocked,
+}
+
+/// Every direction that can be walked in, in the order exits are listed.
+pub const DIRECTIONS: [&str; 10] = [
+    "north",
+    "northeast",
+    "east",
+    "southeast",
+    "south",
+    "southwest",
+    "west",
+    "northwest",
+    "up",
+    "down",
+];
+
 pub fn offset_for_direction(direction: &str) -> Option<IVec3> {
     match direction {
         "north" | "n" => Some(IVec3::new(0, -1, 0)),
@@ -15,3 +43,75 @@ pub fn offset_for_direction(direction: &str) -> Option<IVec3> {
         _ => None,
     }
 }
+
+/// The full name of a direction that may have been abbreviated, e.g. "north" for "n".
+pub fn full_direction(direction: &str) -> Option<&'static str> {
+    let offset = offset_for_direction(direction)?;
+
+    DIRECTIONS
+        .iter()
+        .find(|other| offset_for_direction(other) == Some(offset))
+        .copied()
+}
+
+/// The full name of the direction leading back the way `direction` came.
+pub fn opposite_direction(direction: &str) -> Option<&'static str> {
+    let offset = offset_for_direction(direction)?;
+
+    DIRECTIONS
+        .iter()
+        .find(|other| offset_for_direction(other) == Some(-offset))
+        .copied()
+}
+
+/// Checks the way from `from` to the adjacent tile `to`, which lies at `offset`.
+pub fn passage(
+    from: Entity,
+    to: Entity,
+    offset: IVec3,
+    exits: &Query<&Exits>,
+    doorways: &Query<(&Doorway, &Depiction, Option<&Openable>)>,
+) -> Passage {
+    let lists = |tile: Entity, list: fn(&Exits) -> &Vec<String>, offset: IVec3| {
+        exits.get(tile).is_ok_and(|exits| {
+            list(exits)
+                .iter()
+                .any(|direction| offset_for_direction(direction) == Some(offset))
+        })
+    };
+
+    if lists(from, |e| &e.blocked, offset)
+        || lists(to, |e| &e.blocked, -offset)
+        || lists(to, |e| &e.one_way, -offset)
+    {
+        return Passage::Blocked;
+    }
+
+    let closed_door = doorways.iter().find(|(doorway, _, openable)| {
+        ((doorway.from == from && doorway.to == to) || (doorway.from == to && doorway.to == from))
+            && openable.is_some_and(|openable| !openable.open)
+    });
+
+    match closed_door {
+        Some((_, depiction, _)) => Passage::Closed(depiction.name.clone()),
+        None => Passage::Open,
+    }
+}
+
+/// Whether a tile can be seen in without a light source.
+pub fn is_naturally_lit(
+    tile: Option<&Lighting>,
+    zone: Option<&Lighting>,
+    world_time: &WorldTime,
+) -> bool {
+    match tile.or(zone) {
+        None | Some(Lighting::Lit) => true,
+        Some(Lighting::Dark) => false,
+        Some(Lighting::Outdoor) => world_time.is_daytime(),
+    }
+}
+
+/// Whether a tile is out under the sky.
+pub fn is_outdoors(tile: Option<&Lighting>, zone: Option<&Lighting>) -> bool {
+    matches!(tile.or(zone), Some(Lighting::Outdoor))
+}
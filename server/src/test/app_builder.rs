@@ -3,14 +3,23 @@ use bevy_nest::prelude::*;
 use sqlx::PgPool;
 
 use crate::{
+    combat::events::Killed,
     db::pool::DatabasePool,
     input::{
         events::{ParsedCommand, ProxyCommand},
-        systems::{handle_proxy_command, parse_command},
+        systems::{handle_proxy_command, parse_command, run_command_queues},
     },
     player::events::Prompt,
+    quest::events::{QuestCompleted, QuestEvent},
+    scheduler::{events::TriggerEvent, resources::Scheduler},
+    script::events::ScriptSpawn,
+    spatial::events::EntityMoved,
     visual::paint,
-    world::resources::{WorldState, WorldTime},
+    weather::resources::WeatherState,
+    world::{
+        events::{ReloadZone, ResetZone},
+        resources::{InstancedZones, PendingZones, WorldState, WorldTime},
+    },
     Set,
 };
 
@@ -38,12 +47,26 @@ impl AppBuilder {
             .add_plugin(NestPlugin)
             .insert_resource(WorldState::default())
             .insert_resource(WorldTime::default())
+            .init_resource::<InstancedZones>()
+            .init_resource::<PendingZones>()
+            .init_resource::<WeatherState>()
+            .init_resource::<Scheduler>()
             .add_event::<Inbox>()
             .add_event::<Outbox>()
             .add_event::<ParsedCommand>()
             .add_event::<ProxyCommand>()
             .add_event::<Prompt>()
-            .add_systems((parse_command, handle_proxy_command).in_base_set(Set::Input));
+            .add_event::<EntityMoved>()
+            .add_event::<ReloadZone>()
+            .add_event::<ResetZone>()
+            .add_event::<QuestEvent>()
+            .add_event::<Killed>()
+            .add_event::<QuestCompleted>()
+            .add_event::<ScriptSpawn>()
+            .add_event::<TriggerEvent>()
+            .add_systems(
+                (parse_command, run_command_queues, handle_proxy_command).in_base_set(Set::Input),
+            );
 
         if let Some(database) = self.database {
             app.insert_resource(DatabasePool(database));
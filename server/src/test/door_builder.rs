@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use fake::{Dummy, Fake, Faker};
+
+use crate::{
+    interact::components::Openable, spatial::components::Door, visual::components::Depiction,
+};
+
+#[derive(Dummy)]
+pub struct DoorBuilder {
+    #[dummy(expr = "\"door\".into()")]
+    name: String,
+    #[dummy(expr = "\"n\".into()")]
+    direction: String,
+    #[dummy(expr = "true")]
+    open: bool,
+}
+
+#[allow(dead_code)]
+impl DoorBuilder {
+    pub fn new() -> Self {
+        Faker.fake::<Self>()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn direction(mut self, direction: &str) -> Self {
+        self.direction = direction.into();
+        self
+    }
+
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    pub fn build(self, app: &mut App, tile: Entity) -> Entity {
+        let mut entity = app.world.spawn((
+            Door {
+                direction: self.direction,
+            },
+            Depiction {
+                name: self.name.clone(),
+                short_name: self.name,
+                description: "A door.".into(),
+                tags: vec!["door".into()],
+                visible: false,
+            },
+            Openable { open: self.open },
+        ));
+
+        entity.set_parent(tile);
+
+        entity.id()
+    }
+}
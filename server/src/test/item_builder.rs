@@ -2,10 +2,10 @@ use bevy::prelude::*;
 use fake::{Dummy, Fake, Faker};
 
 use crate::{
-    interact::components::{Interaction, Interactions},
+    interact::components::{Interaction, Interactions, Lock, Openable},
     items::{
         bundles::ItemBundle,
-        components::{Item, Size, Surface, SurfaceKind},
+        components::{Item, LightSource, Readable, Size, Surface, SurfaceKind},
     },
     visual::components::Depiction,
 };
@@ -27,6 +27,16 @@ pub struct ItemBuilder {
     #[dummy(expr = "Size::Small")]
     size: Size,
     #[dummy(expr = "None")]
+    prototype: Option<String>,
+    #[dummy(expr = "None")]
+    lock: Option<(String, bool)>,
+    #[dummy(expr = "None")]
+    open: Option<bool>,
+    #[dummy(expr = "None")]
+    readable: Option<(Vec<String>, bool)>,
+    #[dummy(expr = "None")]
+    light_source: Option<Option<f32>>,
+    #[dummy(expr = "None")]
     tile: Option<Entity>,
 }
 
@@ -73,6 +83,31 @@ impl ItemBuilder {
         self
     }
 
+    pub fn prototype(mut self, prototype: &str) -> Self {
+        self.prototype = Some(prototype.into());
+        self
+    }
+
+    pub fn lock(mut self, key: &str, locked: bool) -> Self {
+        self.lock = Some((key.into(), locked));
+        self
+    }
+
+    pub fn openable(mut self, open: bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    pub fn readable(mut self, pages: Vec<&str>, writable: bool) -> Self {
+        self.readable = Some((pages.iter().map(|p| p.to_string()).collect(), writable));
+        self
+    }
+
+    pub fn light_source(mut self, burn_time: Option<f32>) -> Self {
+        self.light_source = Some(burn_time);
+        self
+    }
+
     pub fn tile(mut self, tile: Entity) -> Self {
         self.tile = Some(tile);
         self
@@ -105,6 +140,30 @@ impl ItemBuilder {
             });
         }
 
+        if let Some(prototype) = self.prototype {
+            entity.insert(Name::new(format!("{prototype} (Prototype)")));
+        }
+
+        if let Some((key, locked)) = self.lock {
+            entity.insert(Lock {
+                key,
+                locked,
+                difficulty: 0,
+            });
+        }
+
+        if let Some(open) = self.open {
+            entity.insert(Openable { open });
+        }
+
+        if let Some((pages, writable)) = self.readable {
+            entity.insert(Readable { pages, writable });
+        }
+
+        if let Some(burn_time) = self.light_source {
+            entity.insert(LightSource { burn_time });
+        }
+
         entity.id()
     }
 }
@@ -1,12 +1,16 @@
 #[cfg(test)]
 pub mod app_builder;
 #[cfg(test)]
+pub mod door_builder;
+#[cfg(test)]
 pub mod item_builder;
 #[cfg(test)]
 pub mod npc_builder;
 #[cfg(test)]
 pub mod player_builder;
 #[cfg(test)]
+pub mod quest_builder;
+#[cfg(test)]
 pub mod tile_builder;
 #[cfg(test)]
 pub mod transition_builder;
@@ -3,7 +3,10 @@ use fake::{Dummy, Fake, Faker};
 
 use crate::{
     interact::components::{Interaction, Interactions},
-    npc::{bundles::NpcBundle, components::Npc},
+    npc::{
+        bundles::NpcBundle,
+        components::{Dialogue, Npc, Schedule, Wander},
+    },
     visual::components::Depiction,
 };
 
@@ -16,6 +19,14 @@ pub struct NpcBuilder {
     #[dummy(expr = "None")]
     interactions: Option<Vec<Interaction>>,
     #[dummy(expr = "None")]
+    dialogue: Option<Dialogue>,
+    #[dummy(expr = "None")]
+    wander: Option<Wander>,
+    #[dummy(expr = "None")]
+    schedule: Option<Schedule>,
+    #[dummy(expr = "None")]
+    prototype: Option<String>,
+    #[dummy(expr = "None")]
     tile: Option<Entity>,
 }
 
@@ -50,6 +61,26 @@ impl NpcBuilder {
         self
     }
 
+    pub fn dialogue(mut self, dialogue: Dialogue) -> Self {
+        self.dialogue = Some(dialogue);
+        self
+    }
+
+    pub fn wander(mut self, wander: Wander) -> Self {
+        self.wander = Some(wander);
+        self
+    }
+
+    pub fn schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    pub fn prototype(mut self, prototype: &str) -> Self {
+        self.prototype = Some(prototype.into());
+        self
+    }
+
     pub fn tile(mut self, tile: Entity) -> Self {
         self.tile = Some(tile);
         self
@@ -75,6 +106,22 @@ impl NpcBuilder {
             entity.insert(Interactions(interactions));
         }
 
+        if let Some(dialogue) = self.dialogue {
+            entity.insert(dialogue);
+        }
+
+        if let Some(wander) = self.wander {
+            entity.insert(wander);
+        }
+
+        if let Some(schedule) = self.schedule {
+            entity.insert(schedule);
+        }
+
+        if let Some(prototype) = self.prototype {
+            entity.insert(Name::new(format!("{prototype} (Prototype)")));
+        }
+
         entity.id()
     }
 }
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use fake::{Dummy, Fake, Faker};
+
+use crate::quest::components::{Goal, Objective, Quest, QuestReward, QuestStage};
+
+#[derive(Dummy)]
+pub struct QuestBuilder {
+    id: String,
+    name: String,
+    description: String,
+    #[dummy(expr = "vec![]")]
+    stages: Vec<(String, Vec<(String, Goal)>)>,
+    #[dummy(expr = "vec![]")]
+    rewards: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl QuestBuilder {
+    pub fn new() -> Self {
+        Faker.fake::<Self>()
+    }
+
+    /// The quest's prototype name.
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn stage(mut self, description: &str, objectives: Vec<(&str, Goal)>) -> Self {
+        self.stages.push((
+            description.into(),
+            objectives
+                .into_iter()
+                .map(|(description, goal)| (description.into(), goal))
+                .collect(),
+        ));
+        self
+    }
+
+    pub fn reward(mut self, item: &str) -> Self {
+        self.rewards.push(item.into());
+        self
+    }
+
+    pub fn build(self, app: &mut App) -> Entity {
+        app.world
+            .spawn((
+                Name::new(format!("{} (Prototype)", self.id)),
+                Quest {
+                    name: self.name,
+                    description: self.description,
+                    stages: self
+                        .stages
+                        .into_iter()
+                        .map(|(description, objectives)| QuestStage {
+                            description,
+                            objectives: objectives
+                                .into_iter()
+                                .map(|(description, goal)| Objective { description, goal })
+                                .collect(),
+                        })
+                        .collect(),
+                    rewards: self.rewards.into_iter().map(QuestReward::Item).collect(),
+                },
+            ))
+            .id()
+    }
+}
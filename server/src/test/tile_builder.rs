@@ -8,7 +8,7 @@ use fake::{
 use crate::{
     spatial::{
         bundles::TileBundle,
-        components::{Position, Spawn, Tile, Zone},
+        components::{Exits, Lighting, Position, Spawn, Tile, Zone},
     },
     visual::components::Sprite,
 };
@@ -17,6 +17,10 @@ use crate::{
 pub struct ZoneBuilder {
     #[dummy(faker = "CityName()")]
     name: String,
+    #[dummy(expr = "None")]
+    lighting: Option<Lighting>,
+    #[dummy(expr = "None")]
+    prototype: Option<String>,
 }
 
 impl ZoneBuilder {
@@ -29,8 +33,28 @@ impl ZoneBuilder {
         self
     }
 
+    pub fn lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    pub fn prototype(mut self, prototype: &str) -> Self {
+        self.prototype = Some(prototype.into());
+        self
+    }
+
     pub fn build(self, app: &mut App) -> Entity {
-        app.world.spawn(Zone { name: self.name }).id()
+        let mut entity = app.world.spawn(Zone { name: self.name });
+
+        if let Some(lighting) = self.lighting {
+            entity.insert(lighting);
+        }
+
+        if let Some(prototype) = self.prototype {
+            entity.insert(Name::new(format!("{prototype} (Prototype)")));
+        }
+
+        entity.id()
     }
 }
 
@@ -46,6 +70,12 @@ pub struct TileBuilder {
     is_spawn: bool,
     #[dummy(expr = "\" \".into()")]
     sprite: String,
+    #[dummy(expr = "None")]
+    exits: Option<Exits>,
+    #[dummy(expr = "None")]
+    lighting: Option<Lighting>,
+    #[dummy(expr = "None")]
+    prototype: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -79,6 +109,32 @@ impl TileBuilder {
         self
     }
 
+    pub fn blocked(mut self, direction: &str) -> Self {
+        self.exits
+            .get_or_insert_with(Exits::default)
+            .blocked
+            .push(direction.into());
+        self
+    }
+
+    pub fn one_way(mut self, direction: &str) -> Self {
+        self.exits
+            .get_or_insert_with(Exits::default)
+            .one_way
+            .push(direction.into());
+        self
+    }
+
+    pub fn lighting(mut self, lighting: Lighting) -> Self {
+        self.lighting = Some(lighting);
+        self
+    }
+
+    pub fn prototype(mut self, prototype: &str) -> Self {
+        self.prototype = Some(prototype.into());
+        self
+    }
+
     pub fn build(self, app: &mut App, zone: Entity) -> Entity {
         let mut entity = app.world.spawn(TileBundle {
             tile: Tile {
@@ -97,6 +153,18 @@ impl TileBuilder {
             entity.insert(Spawn);
         }
 
+        if let Some(exits) = self.exits {
+            entity.insert(exits);
+        }
+
+        if let Some(lighting) = self.lighting {
+            entity.insert(lighting);
+        }
+
+        if let Some(prototype) = self.prototype {
+            entity.insert(Name::new(format!("{prototype} (Prototype)")));
+        }
+
         entity.id()
     }
 }
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use fake::{Dummy, Fake, Faker};
 
 use crate::{
+    interact::components::{Lock, Openable},
     spatial::{
         bundles::TransitionBundle,
         components::{Position, Transition, Zone},
@@ -12,6 +13,10 @@ use crate::{
 #[derive(Dummy)]
 pub struct TransitionBuilder {
     tags: Vec<String>,
+    #[dummy(expr = "None")]
+    lock: Option<(String, bool)>,
+    #[dummy(expr = "None")]
+    open: Option<bool>,
 }
 
 #[allow(dead_code)]
@@ -25,6 +30,16 @@ impl TransitionBuilder {
         self
     }
 
+    pub fn lock(mut self, key: &str, locked: bool) -> Self {
+        self.lock = Some((key.into(), locked));
+        self
+    }
+
+    pub fn openable(mut self, open: bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
     pub fn build(self, app: &mut App, tile: Entity, target: Entity) -> Entity {
         let target_parent = app.world.get::<Parent>(target).expect("Tile has no parent");
 
@@ -38,21 +53,34 @@ impl TransitionBuilder {
             .get::<Position>(target)
             .expect("Target has no position");
 
-        app.world
-            .spawn(TransitionBundle {
-                transition: Transition {
-                    zone: zone.name.clone(),
-                    position: position.0,
-                },
-                depiction: Depiction {
-                    tags: self.tags,
-                    name: "Transition".into(),
-                    short_name: "transition".into(),
-                    description: "A transition".into(),
-                    visible: false,
-                },
-            })
-            .set_parent(tile)
-            .id()
+        let mut entity = app.world.spawn(TransitionBundle {
+            transition: Transition {
+                zone: zone.name.clone(),
+                position: position.0,
+            },
+            depiction: Depiction {
+                tags: self.tags,
+                name: "Transition".into(),
+                short_name: "transition".into(),
+                description: "A transition".into(),
+                visible: false,
+            },
+        });
+
+        entity.set_parent(tile);
+
+        if let Some((key, locked)) = self.lock {
+            entity.insert(Lock {
+                key,
+                locked,
+                difficulty: 0,
+            });
+        }
+
+        if let Some(open) = self.open {
+            entity.insert(Openable { open });
+        }
+
+        entity.id()
     }
 }
@@ -14,3 +14,9 @@ macro_rules! value_or_continue {
         }
     };
 }
+
+/// The id of the prototype an entity was spawned from, which bevy_proto names it after,
+/// e.g. `world.trinus.square` for `world.trinus.square (Prototype)`.
+pub fn prototype_id(name: &str) -> &str {
+    name.trim_end_matches(" (Prototype)")
+}
@@ -30,4 +30,21 @@ impl Depiction {
                 || self.tags.contains(&query.to_lowercase())
         }
     }
+
+    pub fn matches_partial(&self, query: &str) -> bool {
+        if query.is_empty() || query.starts_with('#') {
+            return false;
+        }
+
+        let query = query.to_lowercase();
+
+        self.name
+            .to_lowercase()
+            .split_whitespace()
+            .chain(self.short_name.to_lowercase().split_whitespace())
+            .any(|word| word.starts_with(&query))
+            || self.name.to_lowercase().starts_with(&query)
+            || self.short_name.to_lowercase().starts_with(&query)
+            || self.tags.iter().any(|tag| tag.starts_with(&query))
+    }
 }
@@ -0,0 +1,121 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    player::components::{Client, Online},
+    value_or_continue,
+    weather::{components::Weather, utils::Sky},
+    world::resources::WorldTime,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_weather(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^weather$").unwrap());
+
+    match regex.is_match(content) {
+        false => Err(ParseError::WrongCommand),
+        true => Ok(Command::Weather),
+    }
+}
+
+pub fn weather(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Parent), With<Online>>,
+    sky: Sky,
+    world_time: Res<WorldTime>,
+) {
+    for command in commands.iter() {
+        if let Command::Weather = &command.command {
+            let (client, tile) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            if !sky.is_outdoors(tile.get()) {
+                outbox.send_text(client.id, "You can't see the sky from here.");
+
+                continue;
+            }
+
+            // Places without a climate never see anything but fair weather.
+            let weather = sky.weather(tile.get()).unwrap_or(Weather::Clear);
+
+            outbox.send_text(
+                client.id,
+                format!("{} It's {}.", weather.description(), world_time.season),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        spatial::components::Lighting,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, send_message},
+        },
+        weather::{components::Climate, resources::WeatherState},
+        world::resources::Season,
+    };
+
+    use super::*;
+
+    #[test]
+    fn describes_weather() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(weather);
+
+        let zone = ZoneBuilder::new()
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        app.world.entity_mut(zone).insert(Climate {
+            region: "coast".into(),
+            initial: Weather::Clear,
+            changes: vec![],
+        });
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        app.world
+            .resource_mut::<WeatherState>()
+            .0
+            .insert("coast".into(), Weather::Snow);
+        app.world.resource_mut::<WorldTime>().season = Season::Winter;
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "weather");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Snow is falling. It's winter."
+        );
+    }
+
+    #[test]
+    fn indoors() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(weather);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "weather");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "You can't see the sky from here."
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+use crate::world::resources::Season;
+
+/// How a zone's weather changes over time. Zones with the same `region` share their weather,
+/// which follows the table of whichever of them is found first.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Climate {
+    pub region: String,
+    /// The weather the region starts out with.
+    pub initial: Weather,
+    pub changes: Vec<WeatherChange>,
+}
+
+/// A way the weather can go each time it's rolled, picked by weight among those that
+/// apply. The weather stays as it is when none do.
+#[derive(Reflect, FromReflect)]
+pub struct WeatherChange {
+    pub from: Weather,
+    pub to: Weather,
+    pub weight: u32,
+    /// Seasons it can happen in, or any season if empty.
+    pub seasons: Vec<Season>,
+    /// Hours of the day it can happen in, or any hour if empty.
+    pub hours: Vec<u32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+pub enum Weather {
+    Clear,
+    Cloudy,
+    Fog,
+    Rain,
+    Storm,
+    Snow,
+}
+
+impl Weather {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Clear => "The sky is clear.",
+            Self::Cloudy => "Clouds cover the sky.",
+            Self::Fog => "A thick fog hangs in the air.",
+            Self::Rain => "It's raining.",
+            Self::Storm => "A storm rages overhead.",
+            Self::Snow => "Snow is falling.",
+        }
+    }
+
+    /// What players outdoors are told when the weather turns from this to `to`.
+    pub fn change_message(&self, to: Weather) -> &'static str {
+        match (self, to) {
+            (Self::Cloudy, Self::Clear) => "The clouds part.",
+            (Self::Fog, Self::Clear | Self::Cloudy) => "The fog lifts.",
+            (Self::Rain, Self::Clear | Self::Cloudy) => "The rain stops.",
+            (Self::Storm, Self::Clear | Self::Cloudy) => "The storm passes.",
+            (Self::Snow, Self::Clear | Self::Cloudy) => "It stops snowing.",
+            (_, Self::Clear) => "The sky clears.",
+            (_, Self::Cloudy) => "Clouds gather overhead.",
+            (_, Self::Fog) => "A fog rolls in.",
+            (_, Self::Rain) => "It begins to rain.",
+            (_, Self::Storm) => "Thunder rolls as a storm breaks.",
+            (_, Self::Snow) => "It begins to snow.",
+        }
+    }
+
+    /// Why nothing around can be made out in this weather, if it's bad enough.
+    pub fn obscured(&self) -> Option<&'static str> {
+        match self {
+            Self::Fog => Some("The fog is too thick to make anything out."),
+            Self::Storm => Some("The storm is too fierce to make anything out."),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::world::resources::Season;
+
+use super::{
+    commands::weather::*,
+    components::*,
+    resources::{WeatherState, WeatherTimer},
+    systems::*,
+};
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Climate>()
+            .register_type::<Weather>()
+            .register_type::<WeatherChange>()
+            .register_type::<Vec<WeatherChange>>()
+            .register_type::<Season>()
+            .register_type::<Vec<Season>>()
+            .register_type::<Vec<u32>>();
+
+        app.init_resource::<WeatherState>()
+            .insert_resource(WeatherTimer(Timer::from_seconds(
+                WEATHER_ROLL_SECONDS,
+                TimerMode::Repeating,
+            )));
+
+        app.add_systems((
+            weather,
+            change_weather,
+            announce_weather.after(change_weather),
+        ));
+    }
+}
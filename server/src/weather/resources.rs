@@ -0,0 +1,13 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::components::Weather;
+
+/// The weather in each climate region.
+#[derive(Default, Resource)]
+pub struct WeatherState(pub HashMap<String, Weather>);
+
+/// The weather is rolled again every time it finishes.
+#[derive(Resource)]
+pub struct WeatherTimer(pub Timer);
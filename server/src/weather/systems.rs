@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::{
+    player::components::{Client, Online},
+    value_or_continue,
+    world::resources::WorldTime,
+};
+
+use super::{
+    components::{Climate, Weather},
+    resources::{WeatherState, WeatherTimer},
+    utils::Sky,
+};
+
+/// How often, in real seconds, each region's weather gets a chance to change.
+pub const WEATHER_ROLL_SECONDS: f32 = 300.0;
+
+/// Rolls each climate region's weather on its table whenever the timer finishes. Regions
+/// start out with their initial weather as soon as a zone in them is found.
+pub fn change_weather(
+    climates: Query<&Climate>,
+    mut state: ResMut<WeatherState>,
+    time: Res<Time>,
+    mut timer: ResMut<WeatherTimer>,
+    world_time: Res<WorldTime>,
+) {
+    for climate in climates.iter() {
+        if !state.0.contains_key(&climate.region) {
+            state.0.insert(climate.region.clone(), climate.initial);
+        }
+    }
+
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let season = world_time.season;
+    let mut rng = thread_rng();
+    let mut rolled = vec![];
+
+    for climate in climates.iter() {
+        if rolled.contains(&&climate.region) {
+            continue;
+        }
+
+        rolled.push(&climate.region);
+
+        let current = value_or_continue!(state.0.get(&climate.region).copied());
+
+        let changes = climate
+            .changes
+            .iter()
+            .filter(|change| change.from == current)
+            .filter(|change| change.seasons.is_empty() || change.seasons.contains(&season))
+            .filter(|change| change.hours.is_empty() || change.hours.contains(&world_time.hour))
+            .collect::<Vec<_>>();
+
+        let total = changes.iter().map(|change| change.weight).sum::<u32>();
+
+        if total == 0 {
+            continue;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+
+        let next = changes.iter().find(|change| {
+            if roll < change.weight {
+                true
+            } else {
+                roll -= change.weight;
+
+                false
+            }
+        });
+
+        if let Some(next) = next {
+            state.0.insert(climate.region.clone(), next.to);
+        }
+    }
+}
+
+/// Tells players outdoors when the weather over them changes.
+pub fn announce_weather(
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Parent), With<Online>>,
+    mut seen: Local<HashMap<String, Weather>>,
+    sky: Sky,
+    state: Res<WeatherState>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    let changed = state
+        .0
+        .iter()
+        .filter_map(|(region, weather)| {
+            seen.insert(region.clone(), *weather)
+                .filter(|previous| previous != weather)
+                .map(|previous| (region, previous.change_message(*weather)))
+        })
+        .collect::<HashMap<_, _>>();
+
+    for (client, tile) in players.iter() {
+        if let Some(message) = sky
+            .climate(tile.get())
+            .and_then(|climate| changed.get(&climate.region))
+        {
+            outbox.send_text(client.id, *message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{
+        spatial::components::Lighting,
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::get_message_content,
+        },
+        weather::components::WeatherChange,
+        world::resources::Season,
+    };
+
+    use super::*;
+
+    fn drizzly() -> Climate {
+        Climate {
+            region: "coast".into(),
+            initial: Weather::Clear,
+            changes: vec![WeatherChange {
+                from: Weather::Clear,
+                to: Weather::Rain,
+                weight: 1,
+                seasons: vec![],
+                hours: vec![],
+            }],
+        }
+    }
+
+    fn roll_now(app: &mut App) {
+        app.world
+            .resource_mut::<WeatherTimer>()
+            .0
+            .set_elapsed(Duration::from_secs_f32(WEATHER_ROLL_SECONDS));
+    }
+
+    fn setup() -> App {
+        let mut app = AppBuilder::new().build();
+        app.insert_resource(WeatherTimer(Timer::from_seconds(
+            WEATHER_ROLL_SECONDS,
+            TimerMode::Repeating,
+        )))
+        .add_systems((change_weather, announce_weather.after(change_weather)));
+
+        app
+    }
+
+    #[test]
+    fn changes_weather() {
+        let mut app = setup();
+
+        let zone = ZoneBuilder::new()
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        app.world.entity_mut(zone).insert(drizzly());
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<WeatherState>().0.get("coast"),
+            Some(&Weather::Clear)
+        );
+
+        roll_now(&mut app);
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<WeatherState>().0.get("coast"),
+            Some(&Weather::Rain)
+        );
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "It begins to rain."
+        );
+    }
+
+    #[test]
+    fn indoors_stays_quiet() {
+        let mut app = setup();
+
+        let zone = ZoneBuilder::new()
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        app.world.entity_mut(zone).insert(drizzly());
+        let tile = TileBuilder::new()
+            .lighting(Lighting::Lit)
+            .build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+        app.update();
+
+        roll_now(&mut app);
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+    }
+
+    #[test]
+    fn follows_season() {
+        let mut app = setup();
+
+        let zone = ZoneBuilder::new()
+            .lighting(Lighting::Outdoor)
+            .build(&mut app);
+        let mut climate = drizzly();
+        climate.changes[0].seasons = vec![Season::Summer];
+        app.world.entity_mut(zone).insert(climate);
+
+        app.world.resource_mut::<WorldTime>().season = Season::Winter;
+        app.update();
+
+        roll_now(&mut app);
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<WeatherState>().0.get("coast"),
+            Some(&Weather::Clear)
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::spatial::{
+    components::{Lighting, Tile},
+    utils::is_outdoors,
+};
+
+use super::{
+    components::{Climate, Weather},
+    resources::WeatherState,
+};
+
+/// Finds the weather over tiles that are out under the sky.
+#[derive(SystemParam)]
+pub struct Sky<'w, 's> {
+    climates: Query<'w, 's, &'static Climate>,
+    lighting: Query<'w, 's, &'static Lighting>,
+    state: Res<'w, WeatherState>,
+    tiles: Query<'w, 's, &'static Parent, With<Tile>>,
+}
+
+impl<'w, 's> Sky<'w, 's> {
+    pub fn is_outdoors(&self, tile: Entity) -> bool {
+        self.tiles.get(tile).is_ok_and(|zone| {
+            is_outdoors(
+                self.lighting.get(tile).ok(),
+                self.lighting.get(zone.get()).ok(),
+            )
+        })
+    }
+
+    /// The climate over an outdoor tile, if its zone has one.
+    pub fn climate(&self, tile: Entity) -> Option<&Climate> {
+        if !self.is_outdoors(tile) {
+            return None;
+        }
+
+        self.climates.get(self.tiles.get(tile).ok()?.get()).ok()
+    }
+
+    /// The weather over an outdoor tile, if its zone has a climate.
+    pub fn weather(&self, tile: Entity) -> Option<Weather> {
+        self.climate(tile)
+            .and_then(|climate| self.state.0.get(&climate.region))
+            .copied()
+    }
+}
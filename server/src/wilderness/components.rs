@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+/// A zone whose tiles are generated from a seed instead of being built by hand. It covers
+/// level 0 from (0, 0) to its size, and any tiles the zone's prototype does have win over
+/// the generated ones.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Wilderness {
+    pub seed: u64,
+    pub width: u32,
+    pub height: u32,
+    pub biomes: Vec<Biome>,
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct Biome {
+    /// The name of every tile in the biome.
+    pub name: String,
+    pub sprite: String,
+    /// How much of the wilderness the biome covers, relative to the others.
+    pub weight: u32,
+    /// Descriptions picked from for each tile, with `{feature}` replaced by one of `features`.
+    pub descriptions: Vec<String>,
+    pub features: Vec<String>,
+}
+
+/// A tile generated for a wilderness, despawned again once nobody is near it.
+#[derive(Component)]
+pub struct Generated;
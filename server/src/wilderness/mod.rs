@@ -0,0 +1,4 @@
+pub mod components;
+pub mod plugin;
+pub mod systems;
+pub mod utils;
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use super::{components::*, systems::*};
+
+pub struct WildernessPlugin;
+
+impl Plugin for WildernessPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Wilderness>()
+            .register_type::<Biome>()
+            .register_type::<Vec<Biome>>();
+
+        app.add_systems((
+            generate_wilderness,
+            prune_wilderness.after(generate_wilderness),
+        ));
+    }
+}
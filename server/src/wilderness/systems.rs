@@ -0,0 +1,242 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+use crate::{
+    player::components::Online,
+    spatial::components::{Position, Tile, Transition, Zone},
+    utils::prototype_id,
+    value_or_continue,
+};
+
+use super::{
+    components::{Generated, Wilderness},
+    utils::generate_tile,
+};
+
+/// How far around players wilderness tiles are generated.
+const GENERATE_RADIUS: i32 = 4;
+
+/// How far players have to get from a generated tile before it's despawned. It's further
+/// out than `GENERATE_RADIUS` so walking back and forth doesn't keep respawning tiles.
+const KEEP_RADIUS: i32 = 8;
+
+/// The positions of the online players in each zone.
+fn player_positions(
+    players: &Query<&Parent, With<Online>>,
+    tiles: &Query<(&Position, &Parent), With<Tile>>,
+) -> HashMap<Entity, Vec<IVec3>> {
+    let mut positions: HashMap<Entity, Vec<IVec3>> = HashMap::new();
+
+    for tile in players.iter() {
+        if let Ok((position, zone)) = tiles.get(tile.get()) {
+            positions.entry(zone.get()).or_default().push(position.0);
+        }
+    }
+
+    positions
+}
+
+/// Spawns the tiles around players in a wilderness, and those transitions lead to, wherever
+/// the zone doesn't already have one.
+pub fn generate_wilderness(
+    mut bevy: Commands,
+    players: Query<&Parent, With<Online>>,
+    tiles: Query<(&Position, &Parent), With<Tile>>,
+    transitions: Query<&Transition>,
+    zones: Query<(Entity, &Zone, &Wilderness, Option<&Name>, Option<&Children>)>,
+) {
+    let players = player_positions(&players, &tiles);
+
+    for (zone, Zone { name }, wilderness, prototype, children) in zones.iter() {
+        let existing = children
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| tiles.get(*child).ok())
+            .map(|(position, _)| position.0)
+            .collect::<HashSet<_>>();
+
+        let around_players = players.get(&zone).into_iter().flatten().flat_map(|center| {
+            (-GENERATE_RADIUS..=GENERATE_RADIUS).flat_map(move |x| {
+                (-GENERATE_RADIUS..=GENERATE_RADIUS).map(move |y| *center + IVec3::new(x, y, 0))
+            })
+        });
+
+        let entrances = transitions
+            .iter()
+            .filter(|transition| transition.zone == *name)
+            .map(|transition| transition.position);
+
+        let wanted = around_players
+            .chain(entrances)
+            .filter(|position| !existing.contains(position))
+            .collect::<HashSet<_>>();
+
+        let id = prototype.map_or(name.as_str(), |prototype| prototype_id(prototype));
+
+        for position in wanted {
+            let tile = value_or_continue!(generate_tile(wilderness, position));
+            let IVec3 { x, y, z } = position;
+
+            // Named like a prototype, so players standing here are saved like anywhere else.
+            bevy.spawn((tile, Generated, Name::new(format!("{id}.{x}_{y}_{z}"))))
+                .set_parent(zone);
+        }
+    }
+}
+
+/// Despawns generated tiles nobody is near anymore, unless something was left on them or a
+/// transition leads there.
+pub fn prune_wilderness(
+    mut bevy: Commands,
+    generated: Query<(Entity, &Position, &Parent, Option<&Children>), With<Generated>>,
+    players: Query<&Parent, With<Online>>,
+    tiles: Query<(&Position, &Parent), With<Tile>>,
+    transitions: Query<&Transition>,
+    zones: Query<&Zone>,
+) {
+    let players = player_positions(&players, &tiles);
+
+    for (tile, position, zone, children) in generated.iter() {
+        if children.is_some_and(|children| !children.is_empty()) {
+            continue;
+        }
+
+        let near_player = players.get(&zone.get()).is_some_and(|players| {
+            players.iter().any(|player| {
+                let distance = (*player - position.0).abs();
+
+                distance.z == 0 && distance.x <= KEEP_RADIUS && distance.y <= KEEP_RADIUS
+            })
+        });
+
+        let entrance = zones.get(zone.get()).is_ok_and(|zone| {
+            transitions
+                .iter()
+                .any(|transition| transition.zone == zone.name && transition.position == position.0)
+        });
+
+        if !near_player && !entrance {
+            bevy.entity(tile).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test::{
+            app_builder::AppBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+        },
+        wilderness::components::Biome,
+    };
+
+    use super::*;
+
+    fn wilderness() -> Wilderness {
+        Wilderness {
+            seed: 42,
+            width: 100,
+            height: 100,
+            biomes: vec![Biome {
+                name: "Heath".into(),
+                sprite: ",".into(),
+                weight: 1,
+                descriptions: vec!["Heather stretches in every direction.".into()],
+                features: vec![],
+            }],
+        }
+    }
+
+    fn generated(app: &mut App) -> Vec<IVec3> {
+        app.world
+            .query_filtered::<&Position, With<Generated>>()
+            .iter(&app.world)
+            .map(|position| position.0)
+            .collect()
+    }
+
+    #[test]
+    fn generates_around_players() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((generate_wilderness, prune_wilderness));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(wilderness());
+
+        let camp = TileBuilder::new()
+            .position(IVec3::new(50, 50, 0))
+            .build(&mut app, zone);
+
+        PlayerBuilder::new().tile(camp).build(&mut app);
+        app.update();
+
+        let positions = generated(&mut app);
+
+        assert_eq!(positions.len(), 80);
+        assert!(positions.contains(&IVec3::new(46, 54, 0)));
+        assert!(!positions.contains(&IVec3::new(50, 50, 0)));
+    }
+
+    #[test]
+    fn stops_at_the_edge() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(generate_wilderness);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(wilderness());
+
+        let corner = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+
+        PlayerBuilder::new().tile(corner).build(&mut app);
+        app.update();
+
+        assert_eq!(generated(&mut app).len(), 24);
+    }
+
+    #[test]
+    fn prunes_once_players_leave() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((generate_wilderness, prune_wilderness));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(wilderness());
+
+        let camp = TileBuilder::new()
+            .position(IVec3::new(50, 50, 0))
+            .build(&mut app, zone);
+
+        let town = ZoneBuilder::new().build(&mut app);
+        let square = TileBuilder::new().build(&mut app, town);
+
+        let (player, _, _) = PlayerBuilder::new().tile(camp).build(&mut app);
+        app.update();
+
+        app.world.entity_mut(player).set_parent(square);
+        app.update();
+
+        assert!(generated(&mut app).is_empty());
+    }
+
+    #[test]
+    fn keeps_entrances() {
+        let mut app = AppBuilder::new().build();
+        app.add_systems((generate_wilderness, prune_wilderness));
+
+        let zone = ZoneBuilder::new().name("The Heath").build(&mut app);
+        app.world.entity_mut(zone).insert(wilderness());
+
+        app.world.spawn(Transition {
+            zone: "The Heath".into(),
+            position: IVec3::new(10, 10, 0),
+        });
+        app.update();
+        app.update();
+
+        assert_eq!(generated(&mut app), vec![IVec3::new(10, 10, 0)]);
+    }
+}
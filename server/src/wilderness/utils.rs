@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+
+use crate::{
+    spatial::{
+        bundles::TileBundle,
+        components::{Position, Tile},
+    },
+    visual::components::Sprite,
+};
+
+use super::components::{Biome, Wilderness};
+
+/// The rough size of the patches each biome is laid out in.
+const REGION_SIZE: i32 = 12;
+
+/// Mixes `values` into a well-spread number, so the same seed always builds the same land.
+fn hash(values: &[i64]) -> u64 {
+    values
+        .iter()
+        .fold(0x9E37_79B9_7F4A_7C15, |hash: u64, value| {
+            let mut z = (hash ^ *value as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+            z ^ (z >> 31)
+        })
+}
+
+fn pick<T>(list: &[T], roll: u64) -> Option<&T> {
+    (!list.is_empty()).then(|| &list[(roll % list.len() as u64) as usize])
+}
+
+/// Whether `position` is inside the wilderness, which only has level 0.
+pub fn in_bounds(wilderness: &Wilderness, position: IVec3) -> bool {
+    position.z == 0
+        && position.x >= 0
+        && position.y >= 0
+        && (position.x as u32) < wilderness.width
+        && (position.y as u32) < wilderness.height
+}
+
+/// The biome at `position`. The wilderness is split into regions around scattered points,
+/// each with a biome picked by weight, so biomes come in patches rather than noise.
+pub fn biome_at(wilderness: &Wilderness, position: IVec3) -> Option<&Biome> {
+    if !in_bounds(wilderness, position) {
+        return None;
+    }
+
+    let seed = wilderness.seed as i64;
+    let cell = IVec2::new(
+        position.x.div_euclid(REGION_SIZE),
+        position.y.div_euclid(REGION_SIZE),
+    );
+
+    let region = (-1..=1)
+        .flat_map(|x| (-1..=1).map(move |y| cell + IVec2::new(x, y)))
+        .min_by_key(|cell| {
+            let offset = |salt| {
+                (hash(&[seed, cell.x.into(), cell.y.into(), salt]) % REGION_SIZE as u64) as i32
+            };
+            let point = *cell * REGION_SIZE + IVec2::new(offset(0), offset(1));
+
+            let distance = point - position.truncate();
+
+            distance.x * distance.x + distance.y * distance.y
+        })?;
+
+    let total = wilderness
+        .biomes
+        .iter()
+        .map(|biome| u64::from(biome.weight))
+        .sum::<u64>();
+
+    if total == 0 {
+        return None;
+    }
+
+    let mut roll = hash(&[seed, region.x.into(), region.y.into(), 2]) % total;
+
+    wilderness.biomes.iter().find(|biome| {
+        let weight = u64::from(biome.weight);
+
+        if roll < weight {
+            true
+        } else {
+            roll -= weight;
+
+            false
+        }
+    })
+}
+
+/// The tile a wilderness has at `position`, if it's inside it.
+pub fn generate_tile(wilderness: &Wilderness, position: IVec3) -> Option<TileBundle> {
+    let biome = biome_at(wilderness, position)?;
+    let roll = |salt| {
+        hash(&[
+            wilderness.seed as i64,
+            position.x.into(),
+            position.y.into(),
+            salt,
+        ])
+    };
+
+    let feature = pick(&biome.features, roll(3)).map_or("", String::as_str);
+    let description = pick(&biome.descriptions, roll(4))
+        .map(|template| template.replace("{feature}", feature))
+        .unwrap_or_default();
+
+    Some(TileBundle {
+        tile: Tile {
+            name: biome.name.clone(),
+            description,
+        },
+        sprite: Sprite {
+            character: biome.sprite.clone(),
+        },
+        position: Position(position),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn biome(name: &str, sprite: &str, weight: u32) -> Biome {
+        Biome {
+            name: name.into(),
+            sprite: sprite.into(),
+            weight,
+            descriptions: vec!["Wind moves through the {feature}.".into()],
+            features: vec!["grass".into(), "reeds".into()],
+        }
+    }
+
+    fn wilderness(seed: u64, biomes: Vec<Biome>) -> Wilderness {
+        Wilderness {
+            seed,
+            width: 64,
+            height: 64,
+            biomes,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_land() {
+        let biomes = || vec![biome("Plains", ".", 1), biome("Forest", "T", 1)];
+        let first = wilderness(7, biomes());
+        let second = wilderness(7, biomes());
+
+        for x in 0..64 {
+            for y in 0..64 {
+                let position = IVec3::new(x, y, 0);
+                let first = generate_tile(&first, position).unwrap();
+                let second = generate_tile(&second, position).unwrap();
+
+                assert_eq!(first.tile.name, second.tile.name);
+                assert_eq!(first.tile.description, second.tile.description);
+            }
+        }
+    }
+
+    #[test]
+    fn uses_every_weighted_biome() {
+        let wilderness = wilderness(
+            3,
+            vec![
+                biome("Plains", ".", 1),
+                biome("Forest", "T", 1),
+                biome("Lake", "~", 0),
+            ],
+        );
+
+        let names = (0..64)
+            .flat_map(|x| (0..64).map(move |y| IVec3::new(x, y, 0)))
+            .filter_map(|position| biome_at(&wilderness, position))
+            .map(|biome| biome.name.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(names.contains(&"Plains"));
+        assert!(names.contains(&"Forest"));
+        assert!(!names.contains(&"Lake"));
+    }
+
+    #[test]
+    fn fills_in_description() {
+        let wilderness = wilderness(1, vec![biome("Plains", ".", 1)]);
+
+        let tile = generate_tile(&wilderness, IVec3::new(5, 5, 0)).unwrap();
+
+        assert!([
+            "Wind moves through the grass.",
+            "Wind moves through the reeds."
+        ]
+        .contains(&tile.tile.description.as_str()));
+        assert_eq!(tile.sprite.character, ".");
+    }
+
+    #[test]
+    fn nothing_out_of_bounds() {
+        let wilderness = wilderness(1, vec![biome("Plains", ".", 1)]);
+
+        assert!(generate_tile(&wilderness, IVec3::new(-1, 0, 0)).is_none());
+        assert!(generate_tile(&wilderness, IVec3::new(0, 64, 0)).is_none());
+        assert!(generate_tile(&wilderness, IVec3::new(0, 0, 1)).is_none());
+    }
+}
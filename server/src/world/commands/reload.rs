@@ -0,0 +1,161 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use bevy_nest::prelude::*;
+use regex::Regex;
+
+use crate::{
+    input::events::{Command, ParseError, ParsedCommand},
+    keycard::{Keycard, RELOAD},
+    player::components::{Client, Online},
+    spatial::components::Zone,
+    utils::prototype_id,
+    value_or_continue,
+    world::events::ReloadZone,
+};
+
+static REGEX: OnceLock<Regex> = OnceLock::new();
+
+pub fn handle_reload(content: &str) -> Result<Command, ParseError> {
+    let regex = REGEX.get_or_init(|| Regex::new(r"^reload zone( (?P<zone>.*))?$").unwrap());
+
+    match regex.captures(content) {
+        None => Err(ParseError::WrongCommand),
+        Some(captures) => {
+            let zone = captures
+                .name("zone")
+                .map(|m| m.as_str().trim())
+                .filter(|m| !m.is_empty())
+                .ok_or(ParseError::InvalidArguments("Reload which zone?".into()))?;
+
+            Ok(Command::Reload(zone.to_lowercase()))
+        }
+    }
+}
+
+pub fn reload(
+    mut commands: EventReader<ParsedCommand>,
+    mut outbox: EventWriter<Outbox>,
+    mut reloads: EventWriter<ReloadZone>,
+    players: Query<(&Client, &Keycard), With<Online>>,
+    zones: Query<(&Zone, Option<&Name>)>,
+) {
+    for command in commands.iter() {
+        if let Command::Reload(name) = &command.command {
+            let (client, keycard) =
+                value_or_continue!(players.iter().find(|(c, _)| c.id == command.from));
+
+            if !keycard.can(RELOAD) {
+                continue;
+            }
+
+            let Some((zone, prototype)) = zones
+                .iter()
+                .find(|(zone, _)| zone.name.to_lowercase() == *name)
+            else {
+                outbox.send_text(client.id, format!("Zone \"{name}\" not found."));
+
+                continue;
+            };
+
+            let Some(prototype) = prototype else {
+                outbox.send_text(
+                    client.id,
+                    format!("{} wasn't spawned from a prototype.", zone.name),
+                );
+
+                continue;
+            };
+
+            info!("Reloading zone {}", zone.name);
+
+            reloads.send(ReloadZone {
+                prototype: prototype_id(prototype).into(),
+            });
+
+            outbox.send_text(client.id, format!("Reloading {}.", zone.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{
+        app_builder::AppBuilder,
+        player_builder::PlayerBuilder,
+        tile_builder::{TileBuilder, ZoneBuilder},
+        utils::{get_message_content, send_message},
+    };
+
+    use super::*;
+
+    #[test]
+    fn reloads_zone() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(reload);
+
+        let zone = ZoneBuilder::new()
+            .name("Trinus Castra")
+            .prototype("world.trinus.trinus-castra")
+            .build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::admin())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "reload zone trinus castra");
+        app.update();
+
+        let events = app.world.resource::<Events<ReloadZone>>();
+        let mut reader = events.get_reader();
+
+        assert_eq!(
+            reader.iter(events).next().unwrap().prototype,
+            "world.trinus.trinus-castra"
+        );
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Reloading Trinus Castra."
+        );
+    }
+
+    #[test]
+    fn zone_not_found() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(reload);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new()
+            .role(Keycard::admin())
+            .tile(tile)
+            .build(&mut app);
+
+        send_message(&mut app, client_id, "reload zone atlantis");
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Zone \"atlantis\" not found."
+        );
+    }
+
+    #[test]
+    fn forbidden() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(reload);
+
+        let zone = ZoneBuilder::new().name("Trinus Castra").build(&mut app);
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+
+        send_message(&mut app, client_id, "reload zone trinus castra");
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+    }
+}
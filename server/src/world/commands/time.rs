@@ -32,7 +32,19 @@ pub fn time(
         if let Command::Time = &command.command {
             let client = value_or_continue!(players.iter().find(|c| c.id == command.from));
 
-            outbox.send_text(client.id, world_time.to_string());
+            let mut lines = vec![
+                format!("{}.", *world_time),
+                format!("The moon is {}.", world_time.moon),
+            ];
+
+            lines.extend(
+                world_time
+                    .festivals
+                    .iter()
+                    .map(|festival| format!("Today is {festival}.")),
+            );
+
+            outbox.send_text(client.id, lines.join("\n"));
         }
     }
 }
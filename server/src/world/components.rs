@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_proto::prelude::*;
+
+/// A player taken out of a zone while it's respawned, waiting to be put back on the tile at
+/// the same position once the zone is there again.
+#[derive(Component)]
+pub struct Evacuated {
+    /// Prototype name of the zone.
+    pub zone: String,
+    pub position: IVec3,
+    /// The tile they were taken from, which went with the zone.
+    pub tile: Entity,
+}
+
+/// The tile whose prototype spawned this, so respawning the tile only replaces what it put
+/// there and leaves alone what's been dropped on it since.
+#[derive(Component)]
+pub struct SpawnedOn(pub Entity);
+
+/// Brings a zone back to how it was designed every `interval` seconds. Missing prototype
+/// children are spawned again, locks and doors restored, and items left lying around cleared.
+#[derive(Component, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+pub struct Reset {
+    pub interval: f32,
+    pub policy: ResetPolicy,
+    /// Told to players in the zone shortly before it resets.
+    pub message: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect, FromReflect)]
+pub enum ResetPolicy {
+    /// Only while nobody is in the zone, waiting for it to empty once a reset is due.
+    Empty,
+    Always,
+    Never,
+}
+
+/// Counts down to a zone's next reset.
+#[derive(Component)]
+pub struct ResetTimer {
+    pub timer: Timer,
+    /// Whether players in the zone have been told the reset is coming.
+    pub warned: bool,
+}
@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+/// Asks for a zone's prototype files to be read again, respawning the zone once they are.
+pub struct ReloadZone {
+    pub prototype: String,
+}
+
+/// Puts a zone back the way its prototype has it.
+pub struct ResetZone {
+    pub zone: Entity,
+}
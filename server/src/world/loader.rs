@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+use bevy_proto::{
+    backend::{
+        load::{Loader, ProtoLoadContext},
+        path::ProtoPathContext,
+    },
+    de::PrototypeDeserializer,
+    prelude::*,
+};
+use serde::de::DeserializeSeed;
+
+use super::resources::{LoadFailure, LoadFailures};
+
+/// Loads prototypes the same way bevy_proto's own loader does, but notes down which prototype
+/// a broken file was meant to be and what was wrong with it.
+#[derive(Clone)]
+pub struct WorldLoader {
+    failures: LoadFailures,
+}
+
+impl WorldLoader {
+    pub fn new(failures: LoadFailures) -> Self {
+        Self { failures }
+    }
+}
+
+impl FromWorld for WorldLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(
+            world
+                .get_resource_or_insert_with(LoadFailures::default)
+                .clone(),
+        )
+    }
+}
+
+impl Loader<Prototype> for WorldLoader {
+    type Error = PrototypeError;
+
+    fn deserialize(
+        bytes: &[u8],
+        ctx: &mut ProtoLoadContext<Prototype, Self>,
+    ) -> Result<Prototype, Self::Error> {
+        let path = ctx.base_path().to_path_buf();
+        let failures = ctx.loader().failures.clone();
+
+        let result = ron::Deserializer::from_bytes(bytes)
+            .map_err(|err| PrototypeError::SpannedRonError(path.clone(), err))
+            .and_then(|mut de| {
+                PrototypeDeserializer::new(ctx)
+                    .deserialize(&mut de)
+                    .map_err(|err| {
+                        PrototypeError::SpannedRonError(path.clone(), de.span_error(err))
+                    })
+            });
+
+        if let Err(error) = &result {
+            let path = path.display().to_string();
+
+            failures.0.lock().unwrap().insert(
+                path.clone(),
+                LoadFailure {
+                    id: prototype_name(bytes).unwrap_or(path),
+                    error: error.to_string(),
+                },
+            );
+        }
+
+        result
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["prototype.ron", "proto.ron"]
+    }
+}
+
+/// The `name` a prototype file gives its prototype, as long as the file is valid RON.
+fn prototype_name(bytes: &[u8]) -> Option<String> {
+    let ron::Value::Map(fields) = ron::de::from_bytes(bytes).ok()? else {
+        return None;
+    };
+
+    let name = fields.iter().find_map(|field| match field {
+        (ron::Value::String(key), ron::Value::String(name)) if key == "name" => Some(name.clone()),
+        _ => None,
+    });
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_broken_prototype() {
+        let bytes = br#"(
+            name: "world.trinus.trinus-castra",
+            schematics: {
+                "server::spatial::components::Zone": (nme: "Trinus Castra"),
+            },
+        )"#;
+
+        assert_eq!(
+            prototype_name(bytes),
+            Some("world.trinus.trinus-castra".into())
+        );
+    }
+
+    #[test]
+    fn unnamed_when_unreadable() {
+        assert_eq!(prototype_name(b"(name: \"world.trinus"), None);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod commands;
+pub mod components;
+pub mod events;
+pub mod loader;
 pub mod plugin;
 pub mod resources;
 mod systems;
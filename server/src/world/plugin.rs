@@ -1,13 +1,18 @@
-use std::time::Duration;
+use std::{collections::HashMap, env, time::Duration};
 
 use bevy::prelude::*;
 use bevy_proto::prelude::*;
 
-use crate::Set;
+use crate::{db::pool::DatabasePool, Set};
 
 use super::{
-    commands::time::*,
-    resources::{SaveTimer, WorldState, WorldTime},
+    commands::{reload::*, time::*},
+    components::{Reset, ResetPolicy},
+    events::{ReloadZone, ResetZone},
+    resources::{
+        Calendar, Festival, InstancedZones, Month, PendingZones, PrototypeWatcher, SaveTimer,
+        WorldFiles, WorldState, WorldTime, ZoneReloads,
+    },
     systems::*,
 };
 
@@ -15,25 +20,64 @@ pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
+        app.register_type::<Reset>().register_type::<ResetPolicy>();
+        app.register_type::<Calendar>()
+            .register_type::<Month>()
+            .register_type::<Vec<Month>>()
+            .register_type::<Festival>()
+            .register_type::<Vec<Festival>>();
+
         app.insert_resource(WorldState::default())
             .insert_resource(SaveTimer(Timer::new(
                 Duration::from_secs(60),
                 TimerMode::Repeating,
             )))
             .insert_resource(WorldTime::default())
+            .init_resource::<Calendar>()
+            .init_resource::<WorldFiles>()
+            .init_resource::<PendingZones>()
+            .init_resource::<InstancedZones>()
+            .init_resource::<ZoneReloads>()
+            .add_event::<ReloadZone>()
+            .add_event::<ResetZone>()
             .add_systems((
-                save_world_state.in_base_set(Set::WorldSave),
+                save_world_state
+                    .in_base_set(Set::WorldSave)
+                    .run_if(resource_exists::<DatabasePool>()),
                 handle_save_world_state_task,
                 handle_load_world_state_task,
             ))
-            .add_startup_systems((load_world_state,))
-            .add_systems((time, update_world_time));
+            .add_startup_systems((
+                load_world_state.run_if(resource_exists::<DatabasePool>()),
+                load_world,
+            ))
+            .add_systems((time, update_world_time, reload));
+
+        app.add_system(spawn_calendar.run_if(prototype_ready("calendar").and_then(run_once())));
 
         app.add_systems((
-            spawn_trinus_castra
-                .run_if(prototype_ready("world.trinus.trinus-castra").and_then(run_once())),
-            spawn_the_roaring_lion
-                .run_if(prototype_ready("world.trinus.the-roaring-lion").and_then(run_once())),
+            report_world_errors,
+            find_zones,
+            reload_zone_files,
+            respawn_zones,
+            respawn_tiles
+                .after(respawn_zones)
+                .run_if(resource_exists::<PrototypeWatcher>()),
+            spawn_zones.after(find_zones).after(respawn_zones),
+            mark_tile_spawns,
+            return_evacuated.before(spawn_zones),
+            open_instances,
+            close_instances,
+            tick_resets,
+            reset_zones.after(tick_resets),
+            watch_prototypes.run_if(resource_exists::<PrototypeWatcher>()),
         ));
+
+        if env::var("WATCH_PROTOTYPES").is_ok() {
+            app.insert_resource(PrototypeWatcher {
+                timer: Timer::new(Duration::from_secs(1), TimerMode::Repeating),
+                modified: HashMap::new(),
+            });
+        }
     }
 }
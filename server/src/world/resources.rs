@@ -1,31 +1,288 @@
-use std::fmt::Display;
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
 
 use bevy::prelude::*;
-use chrono::prelude::*;
+use bevy_proto::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Resource)]
 pub struct SaveTimer(pub Timer);
 
+/// Prototype files loaded from `world/`.
+#[derive(Default, Resource)]
+pub struct WorldFiles {
+    /// Files that haven't finished loading yet.
+    pub loading: Vec<HandleUntyped>,
+    /// What went wrong with each file that failed to load.
+    pub failed: Vec<String>,
+}
+
+/// Why a prototype file failed to load.
+pub struct LoadFailure {
+    /// The prototype the file was meant to be, or its path if that couldn't be read.
+    pub id: String,
+    pub error: String,
+}
+
+/// Failures noted by `WorldLoader` while files load in the background, by path.
+#[derive(Clone, Default, Resource)]
+pub struct LoadFailures(pub Arc<Mutex<HashMap<String, LoadFailure>>>);
+
+/// Zone prototypes that have been found but aren't ready to spawn yet.
+#[derive(Default, Resource)]
+pub struct PendingZones(pub Vec<String>);
+
+/// Prototypes of instanced zones by zone name, which are only spawned as copies when
+/// someone enters them.
+#[derive(Default, Resource)]
+pub struct InstancedZones(pub HashMap<String, String>);
+
+/// Zone prototypes being reloaded on request, to respawn once their files have been read.
+#[derive(Default, Resource)]
+pub struct ZoneReloads(pub Vec<String>);
+
+/// Present when `WATCH_PROTOTYPES` is set. Prototype files are checked for changes every
+/// tick of the timer. A changed zone is respawned, and a changed tile, or anything on one,
+/// only has the tiles it's part of respawned.
+#[derive(Resource)]
+pub struct PrototypeWatcher {
+    pub timer: Timer,
+    pub modified: HashMap<PathBuf, SystemTime>,
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Resource)]
 pub struct WorldState {
     pub characters: Vec<WorldStateCharacter>,
+    #[serde(default)]
+    pub locks: Vec<WorldStateLock>,
+    /// When the in-game clock started, as a real unix timestamp.
+    #[serde(default)]
+    pub epoch: Option<i64>,
 }
 
+/// Inserted once the latest world save has been read from the database.
+#[derive(Resource)]
+pub struct WorldStateLoaded;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldStateCharacter {
     pub id: i64,
     pub tile: String,
-    pub inventory: Vec<String>,
+    pub inventory: Vec<WorldStateItem>,
+}
+
+/// An item saved by prototype name, along with any state that belongs to this one item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "SavedItem")]
+pub struct WorldStateItem {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub written_pages: Vec<String>,
+}
+
+/// Older saves stored inventory items as just their prototype name.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedItem {
+    Name(String),
+    Item {
+        name: String,
+        #[serde(default)]
+        written_pages: Vec<String>,
+    },
+}
+
+impl From<SavedItem> for WorldStateItem {
+    fn from(saved: SavedItem) -> Self {
+        match saved {
+            SavedItem::Name(name) => Self {
+                name,
+                written_pages: vec![],
+            },
+            SavedItem::Item {
+                name,
+                written_pages,
+            } => Self {
+                name,
+                written_pages,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldStateLock {
+    pub tile: String,
+    pub name: String,
+    pub locked: bool,
+    pub open: Option<bool>,
+}
+
+/// The game's calendar, inserted when the `calendar` prototype is spawned. Until then the world
+/// runs on a plain Roman one.
+#[derive(Resource, Schematic, Reflect, FromReflect)]
+#[reflect(Schematic)]
+#[schematic(kind = "resource")]
+pub struct Calendar {
+    /// How many in-game minutes pass for every real one.
+    pub ratio: u32,
+    pub months: Vec<Month>,
+    pub weekdays: Vec<String>,
+    /// How many days the moon takes to go from new to new again.
+    pub lunar_cycle: u32,
+    pub festivals: Vec<Festival>,
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        let months = [
+            ("Ianuarius", Season::Winter),
+            ("Februarius", Season::Winter),
+            ("Martius", Season::Spring),
+            ("Aprilis", Season::Spring),
+            ("Maius", Season::Spring),
+            ("Iunius", Season::Summer),
+            ("Quintilis", Season::Summer),
+            ("Sextilis", Season::Summer),
+            ("September", Season::Autumn),
+            ("October", Season::Autumn),
+            ("November", Season::Autumn),
+            ("December", Season::Winter),
+        ];
+
+        let weekdays = [
+            "Dies Solis",
+            "Dies Lunae",
+            "Dies Martis",
+            "Dies Mercurii",
+            "Dies Iovis",
+            "Dies Veneris",
+            "Dies Saturni",
+        ];
+
+        Self {
+            ratio: 4,
+            months: months
+                .into_iter()
+                .map(|(name, season)| Month {
+                    name: name.into(),
+                    days: 30,
+                    season,
+                })
+                .collect(),
+            weekdays: weekdays.into_iter().map(String::from).collect(),
+            lunar_cycle: 28,
+            festivals: vec![],
+        }
+    }
+}
+
+#[derive(Reflect, FromReflect)]
+pub struct Month {
+    pub name: String,
+    pub days: u32,
+    pub season: Season,
+}
+
+/// A festival or holiday, held for `days` days from `day` of `month`, both counted from 1.
+/// It doesn't carry over into the next month.
+#[derive(Reflect, FromReflect)]
+pub struct Festival {
+    pub name: String,
+    pub month: u32,
+    pub day: u32,
+    pub days: u32,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect, FromReflect)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Display for Season {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Spring => write!(f, "spring"),
+            Self::Summer => write!(f, "summer"),
+            Self::Autumn => write!(f, "autumn"),
+            Self::Winter => write!(f, "winter"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum MoonPhase {
+    #[default]
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    /// The phase `days` days after a new moon.
+    pub fn on_day(days: u64, cycle: u32) -> Self {
+        let cycle = u64::from(cycle.max(1));
+        // Rounded, so each phase is centered on the day it's named for.
+        let phase = ((days % cycle) * 8 + cycle / 2) / cycle % 8;
+
+        match phase {
+            0 => Self::New,
+            1 => Self::WaxingCrescent,
+            2 => Self::FirstQuarter,
+            3 => Self::WaxingGibbous,
+            4 => Self::Full,
+            5 => Self::WaningGibbous,
+            6 => Self::LastQuarter,
+            _ => Self::WaningCrescent,
+        }
+    }
+}
+
+impl Display for MoonPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::New => write!(f, "new"),
+            Self::WaxingCrescent => write!(f, "a waxing crescent"),
+            Self::FirstQuarter => write!(f, "in its first quarter"),
+            Self::WaxingGibbous => write!(f, "waxing gibbous"),
+            Self::Full => write!(f, "full"),
+            Self::WaningGibbous => write!(f, "waning gibbous"),
+            Self::LastQuarter => write!(f, "in its last quarter"),
+            Self::WaningCrescent => write!(f, "a waning crescent"),
+        }
+    }
 }
 
 #[derive(Default, Resource)]
 pub struct WorldTime {
+    /// In-game minutes since the epoch.
+    pub minutes: u64,
     pub year: u32,
+    /// Counted from 1, like `day`.
     pub month: u32,
     pub day: u32,
     pub hour: u32,
     pub minute: u32,
+    pub month_name: String,
+    pub weekday: String,
+    pub season: Season,
+    pub moon: MoonPhase,
+    /// The festivals being held today.
+    pub festivals: Vec<String>,
 }
 
 impl Display for WorldTime {
@@ -35,30 +292,66 @@ impl Display for WorldTime {
 }
 
 impl WorldTime {
-    pub fn update(&mut self) {
-        let now = Utc::now();
-
-        let game_ratio = 4;
+    /// Works out the date `minutes` in-game minutes after the epoch.
+    pub fn update(&mut self, calendar: &Calendar, minutes: u64) {
         let minutes_per_day = 24 * 60;
+        let days = minutes / minutes_per_day;
+
+        self.minutes = minutes;
+        self.hour = (minutes % minutes_per_day / 60) as u32;
+        self.minute = (minutes % 60) as u32;
+
+        let year_length = calendar
+            .months
+            .iter()
+            .map(|month| u64::from(month.days))
+            .sum::<u64>()
+            .max(1);
+
+        self.year = (days / year_length) as u32 + 1;
+
+        let mut day_of_year = (days % year_length) as u32;
 
-        let total_minutes_passed = now.minute() + now.hour() * 60 + now.day() * minutes_per_day;
-        let total_game_minutes_passed = total_minutes_passed * game_ratio;
+        for (index, month) in calendar.months.iter().enumerate() {
+            if day_of_year < month.days {
+                self.month = index as u32 + 1;
+                self.day = day_of_year + 1;
+                self.month_name = month.name.clone();
+                self.season = month.season;
 
-        let game_day = total_game_minutes_passed / minutes_per_day;
-        let game_hour = (total_game_minutes_passed % minutes_per_day) / 60;
-        let game_minute = (total_game_minutes_passed % minutes_per_day) % 60;
+                break;
+            }
 
-        self.year = now.year() as u32 - 2020;
-        self.month = now.month();
-        self.day = game_day;
-        self.hour = game_hour;
-        self.minute = game_minute;
+            day_of_year -= month.days;
+        }
+
+        if !calendar.weekdays.is_empty() {
+            let weekday = days % calendar.weekdays.len() as u64;
+            self.weekday = calendar.weekdays[weekday as usize].clone();
+        }
+
+        self.moon = MoonPhase::on_day(days, calendar.lunar_cycle);
+
+        self.festivals = calendar
+            .festivals
+            .iter()
+            .filter(|festival| {
+                festival.month == self.month
+                    && (festival.day..festival.day + festival.days.max(1)).contains(&self.day)
+            })
+            .map(|festival| festival.name.clone())
+            .collect();
+    }
+
+    /// Whether the sun is up, from 6am until 8pm.
+    pub fn is_daytime(&self) -> bool {
+        (6..20).contains(&self.hour)
     }
 
     pub fn date_string(&self) -> String {
         format!(
-            "day {} of month {}, year {}",
-            self.day, self.month, self.year
+            "{}, day {} of {}, year {}",
+            self.weekday, self.day, self.month_name, self.year
         )
     }
 
@@ -71,3 +364,66 @@ impl WorldTime {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar() -> Calendar {
+        Calendar {
+            festivals: vec![Festival {
+                name: "Saturnalia".into(),
+                month: 12,
+                day: 17,
+                days: 3,
+            }],
+            ..default()
+        }
+    }
+
+    #[test]
+    fn starts_at_the_epoch() {
+        let mut time = WorldTime::default();
+        time.update(&calendar(), 0);
+
+        assert_eq!(
+            time.to_string(),
+            "It's Dies Solis, day 1 of Ianuarius, year 1, 00:00am"
+        );
+        assert_eq!(time.season, Season::Winter);
+        assert_eq!(time.moon, MoonPhase::New);
+    }
+
+    #[test]
+    fn walks_the_calendar() {
+        let mut time = WorldTime::default();
+        // A year, two months, three days, four hours and five minutes in.
+        let days = 360 + 60 + 3;
+        time.update(&calendar(), (days * 24 + 4) * 60 + 5);
+
+        assert_eq!(
+            time.to_string(),
+            "It's Dies Mercurii, day 4 of Martius, year 2, 04:05am"
+        );
+        assert_eq!(time.season, Season::Spring);
+    }
+
+    #[test]
+    fn follows_the_moon() {
+        assert_eq!(MoonPhase::on_day(14, 28), MoonPhase::Full);
+        assert_eq!(MoonPhase::on_day(7, 28), MoonPhase::FirstQuarter);
+        assert_eq!(MoonPhase::on_day(27, 28), MoonPhase::New);
+    }
+
+    #[test]
+    fn holds_festivals() {
+        let mut time = WorldTime::default();
+        let day = |day: u64| (330 + day - 1) * 24 * 60;
+
+        time.update(&calendar(), day(19));
+        assert_eq!(time.festivals, vec!["Saturnalia".to_string()]);
+
+        time.update(&calendar(), day(20));
+        assert!(time.festivals.is_empty());
+    }
+}
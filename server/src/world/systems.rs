@@ -1,27 +1,786 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
 use bevy::{
+    asset::{AssetPath, FileAssetIo, HandleId, LoadState},
     prelude::*,
     tasks::{AsyncComputeTaskPool, Task},
 };
-use bevy_proto::prelude::*;
+use bevy_nest::prelude::*;
+use bevy_proto::{
+    backend::{children::PrototypicalChild, proto::ProtoInstance},
+    prelude::*,
+};
+use chrono::Utc;
 use futures_lite::future;
 use sqlx::{Pool, Postgres};
 
 use crate::{
     db::{models::WorldSaveModel, pool::DatabasePool, utils::store_world_state},
-    items::components::{Inventory, Item},
-    player::components::{Character, Online},
-    spatial::components::Tile,
+    input::events::{Command, ParsedCommand, ProxyCommand},
+    interact::components::{Lock, Openable},
+    items::components::{Inventory, Item, WrittenPages},
+    npc::components::Npc,
+    player::components::{Character, Client, Online},
+    spatial::{
+        components::{Arriving, Instance, Instanced, Position, Spawn, Tile, Zone},
+        events::{EntityMoved, MoveMethod},
+    },
+    utils::prototype_id,
     value_or_continue,
 };
 
-use super::resources::{SaveTimer, WorldState, WorldStateCharacter, WorldTime};
+use super::{
+    components::{Evacuated, Reset, ResetPolicy, ResetTimer, SpawnedOn},
+    events::{ReloadZone, ResetZone},
+    resources::{
+        Calendar, InstancedZones, LoadFailure, LoadFailures, PendingZones, PrototypeWatcher,
+        SaveTimer, WorldFiles, WorldState, WorldStateCharacter, WorldStateItem, WorldStateLoaded,
+        WorldStateLock, WorldTime, ZoneReloads,
+    },
+};
+
+/// Seconds before a reset that players in the zone are told it's coming.
+const RESET_WARNING: f32 = 10.0;
+
+pub fn load_world(mut files: ResMut<WorldFiles>, mut prototypes: PrototypesMut) {
+    match prototypes.load_folder("world/") {
+        Ok(loaded) => files.loading = loaded,
+        Err(err) => error!("Failed to load world prototypes: {}", err),
+    }
+}
+
+/// Logs each file in `world/` that failed to load, with the prototype it was meant to be and
+/// why, so one broken zone doesn't hide behind the rest of the world spawning fine.
+pub fn report_world_errors(
+    asset_server: Res<AssetServer>,
+    failures: Res<LoadFailures>,
+    mut files: ResMut<WorldFiles>,
+    prototypes: Prototypes,
+) {
+    let WorldFiles { loading, failed } = &mut *files;
+
+    loading.retain(|handle| match prototypes.get_load_state(handle) {
+        LoadState::Loaded => false,
+        LoadState::Failed => {
+            let path = handle_path(&asset_server, handle.id());
+
+            let failure = match failures.0.lock().unwrap().remove(&path) {
+                Some(LoadFailure { id, error }) => format!("{id} ({path}): {error}"),
+                None => path,
+            };
+
+            error!("Failed to load {}", failure);
+
+            failed.push(failure);
+
+            false
+        }
+        _ => true,
+    });
+}
+
+fn handle_path(asset_server: &AssetServer, handle: HandleId) -> String {
+    asset_server
+        .get_handle_path(handle)
+        .map(|path| path.path().display().to_string())
+        .unwrap_or_else(|| format!("{:?}", handle))
+}
+
+/// Any prototype with a `Zone` schematic is a zone to spawn once everything in it is ready,
+/// unless it's instanced.
+pub fn find_zones(
+    assets: Res<Assets<Prototype>>,
+    mut events: EventReader<ProtoAssetEvent>,
+    mut instanced: ResMut<InstancedZones>,
+    mut pending: ResMut<PendingZones>,
+) {
+    for event in events.iter() {
+        let ProtoAssetEvent::Created { id, handle } = event else {
+            continue;
+        };
+
+        let Some(schematics) = assets
+            .get(handle)
+            .map(|prototype| prototype.schematics())
+            .filter(|schematics| schematics.contains::<Zone>())
+        else {
+            continue;
+        };
+
+        if schematics.contains::<Instanced>() {
+            let zone = schematics
+                .get::<Zone>()
+                .and_then(|zone| zone.input().downcast_ref::<Zone>());
+
+            if let Some(zone) = zone {
+                info!("Found instanced zone {}", id);
+
+                instanced.0.insert(zone.name.clone(), id.clone());
+            }
+
+            continue;
+        }
+
+        info!("Found zone {}", id);
+
+        pending.0.push(id.clone());
+    }
+}
+
+/// Spawns pending zones once they're ready. A zone that never will be, because something in
+/// it failed to load, is skipped so the rest of the world still spawns.
+pub fn spawn_zones(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Prototype>>,
+    mut commands: ProtoCommands,
+    mut pending: ResMut<PendingZones>,
+    prototypes: Prototypes,
+) {
+    pending.0.retain(|id| {
+        if prototypes.is_ready(id.as_str()) {
+            commands.spawn(id.as_str());
+
+            return false;
+        }
+
+        let Some(failed) = assets
+            .iter()
+            .find(|(_, prototype)| prototype.id() == id)
+            .and_then(|(_, zone)| failed_child(&assets, &prototypes, zone))
+        else {
+            return true;
+        };
+
+        error!(
+            "Skipping zone {}, {} failed to load",
+            id,
+            handle_path(&asset_server, failed)
+        );
+
+        false
+    });
+}
+
+/// The first of a prototype's children, or their children, that failed to load.
+fn failed_child(
+    assets: &Assets<Prototype>,
+    prototypes: &Prototypes,
+    prototype: &Prototype,
+) -> Option<HandleId> {
+    let mut queue = vec![prototype];
+
+    while let Some(prototype) = queue.pop() {
+        for child in prototype
+            .children()
+            .iter()
+            .flat_map(|children| children.iter())
+        {
+            let handle = child.handle().id();
+
+            if prototypes.get_load_state(handle) == LoadState::Failed {
+                return Some(handle);
+            }
+
+            queue.extend(assets.get(child.handle()));
+        }
+    }
+
+    None
+}
+
+/// Spawns a copy of an instanced zone for each player waiting to arrive in one.
+pub fn open_instances(
+    mut bevy: Commands,
+    instanced: Res<InstancedZones>,
+    mut players: Query<(&Character, &mut Arriving)>,
+    mut proto: ProtoCommands,
+    prototypes: Prototypes,
+) {
+    for (character, mut arriving) in players.iter_mut() {
+        if arriving.instance.is_some() {
+            continue;
+        }
+
+        let prototype = value_or_continue!(instanced.0.get(&arriving.zone));
+
+        if !prototypes.is_ready(prototype.as_str()) {
+            continue;
+        }
+
+        info!(
+            "Opening an instance of {} for {}",
+            prototype, character.name
+        );
+
+        let zone = proto.spawn(prototype.as_str()).id();
+
+        bevy.entity(zone).insert(Instance {
+            members: vec![character.id],
+            closing: None,
+        });
+
+        arriving.instance = Some(zone);
+    }
+}
+
+/// Despawns instances that have been empty for their zone's grace period.
+pub fn close_instances(
+    arriving: Query<&Arriving>,
+    mut bevy: Commands,
+    mut instances: Query<(Entity, &mut Instance, &Instanced, &Zone)>,
+    players: Query<&Parent, With<Online>>,
+    tiles: Query<&Parent, With<Tile>>,
+    time: Res<Time>,
+) {
+    let occupied = players
+        .iter()
+        .filter_map(|tile| tiles.get(tile.get()).ok())
+        .map(|zone| zone.get())
+        .chain(arriving.iter().filter_map(|arriving| arriving.instance))
+        .collect::<HashSet<_>>();
+
+    for (zone, mut instance, instanced, Zone { name }) in instances.iter_mut() {
+        if occupied.contains(&zone) {
+            instance.closing = None;
+
+            continue;
+        }
+
+        if instance
+            .closing
+            .get_or_insert_with(|| Timer::from_seconds(instanced.grace, TimerMode::Once))
+            .tick(time.delta())
+            .finished()
+        {
+            info!("Closing an instance of {}", name);
+
+            bevy.entity(zone).despawn_recursive();
+        }
+    }
+}
+
+/// Counts down to each zone's next reset, warning players inside shortly before and asking
+/// for the reset once it's due.
+pub fn tick_resets(
+    mut bevy: Commands,
+    mut outbox: EventWriter<Outbox>,
+    players: Query<(&Client, &Parent), With<Online>>,
+    mut resets: EventWriter<ResetZone>,
+    tiles: Query<&Parent, With<Tile>>,
+    time: Res<Time>,
+    mut zones: Query<(Entity, &Reset, Option<&mut ResetTimer>)>,
+) {
+    for (zone, reset, timer) in zones.iter_mut() {
+        if reset.policy == ResetPolicy::Never {
+            continue;
+        }
+
+        let Some(mut timer) = timer else {
+            bevy.entity(zone).insert(ResetTimer {
+                timer: Timer::from_seconds(reset.interval, TimerMode::Once),
+                warned: false,
+            });
+
+            continue;
+        };
+
+        timer.timer.tick(time.delta());
+
+        let inside = players
+            .iter()
+            .filter(|(_, tile)| {
+                tiles
+                    .get(tile.get())
+                    .is_ok_and(|parent| parent.get() == zone)
+            })
+            .map(|(client, _)| client.id)
+            .collect::<Vec<_>>();
+
+        // A zone that only resets while empty waits for everyone to leave, so nobody is
+        // there to warn.
+        if reset.policy == ResetPolicy::Empty && !inside.is_empty() {
+            continue;
+        }
+
+        if !timer.warned && timer.timer.remaining_secs() <= RESET_WARNING {
+            for client in inside.iter() {
+                outbox.send_text(*client, reset.message.clone());
+            }
+
+            timer.warned = true;
+        }
+
+        if timer.timer.finished() {
+            resets.send(ResetZone { zone });
+
+            timer.timer.reset();
+            timer.warned = false;
+        }
+    }
+}
+
+/// Puts a zone back the way its prototypes have it. Children missing from anything spawned
+/// from a prototype are spawned again, locks and doors are set back to their designed state,
+/// and items on its tiles that nothing in the zone was designed with are cleared away.
+pub fn reset_zones(
+    assets: Res<Assets<Prototype>>,
+    mut bevy: Commands,
+    children: Query<&Children>,
+    mut events: EventReader<ResetZone>,
+    items: Query<&Parent, With<Item>>,
+    mut locks: Query<(Option<&mut Lock>, Option<&mut Openable>)>,
+    names: Query<&Name>,
+    npcs: Query<(), With<Npc>>,
+    players: Query<(), With<Character>>,
+    mut proto: ProtoCommands,
+    tiles: Query<(), With<Tile>>,
+) {
+    let events = events.iter().collect::<Vec<_>>();
+
+    if events.is_empty() {
+        return;
+    }
+
+    let prototypes = assets
+        .iter()
+        .map(|(_, prototype)| (prototype.id().as_str(), prototype))
+        .collect::<HashMap<_, _>>();
+
+    let prototype_of = |entity: Entity| {
+        names
+            .get(entity)
+            .ok()
+            .and_then(|name| prototypes.get(prototype_id(name)))
+    };
+
+    let is_named =
+        |entity: Entity, id: &str| names.get(entity).is_ok_and(|name| prototype_id(name) == id);
+
+    for ResetZone { zone } in events {
+        info!("Resetting zone {:?}", zone);
+
+        // Everything in the zone, parents first, leaving out players and what they carry.
+        let mut entities = vec![*zone];
+        let mut next = 0;
+
+        while let Some(entity) = entities.get(next).copied() {
+            entities.extend(
+                children
+                    .get(entity)
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .filter(|child| !players.contains(**child)),
+            );
+
+            next += 1;
+        }
+
+        // NPCs walk around, so they count towards whatever was designed with them wherever
+        // they are in the zone.
+        let mut claimed = HashSet::new();
+
+        for parent in entities.iter() {
+            let Some(prototype) = prototype_of(*parent) else {
+                continue;
+            };
+
+            for child in prototype
+                .children()
+                .iter()
+                .flat_map(|children| children.iter())
+            {
+                let Some(id) = assets.get(child.handle()).map(|child| child.id().as_str()) else {
+                    continue;
+                };
+
+                let live = children
+                    .get(*parent)
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .copied()
+                    .chain(
+                        entities
+                            .iter()
+                            .copied()
+                            .filter(|entity| npcs.contains(*entity)),
+                    )
+                    .find(|entity| !claimed.contains(entity) && is_named(*entity, id));
+
+                match live {
+                    Some(entity) => {
+                        claimed.insert(entity);
+                    }
+                    None => {
+                        let entity = proto.spawn(id).id();
+
+                        bevy.entity(*parent).add_child(entity);
+                    }
+                }
+            }
+
+            if let Ok((lock, openable)) = locks.get_mut(*parent) {
+                let schematics = prototype.schematics();
+
+                if let (Some(mut lock), Some(designed)) = (
+                    lock,
+                    schematics
+                        .get::<Lock>()
+                        .and_then(|lock| lock.input().downcast_ref::<Lock>()),
+                ) {
+                    lock.locked = designed.locked;
+                }
+
+                if let (Some(mut openable), Some(designed)) = (
+                    openable,
+                    schematics
+                        .get::<Openable>()
+                        .and_then(|openable| openable.input().downcast_ref::<Openable>()),
+                ) {
+                    openable.open = designed.open;
+                }
+            }
+        }
+
+        for entity in entities.iter() {
+            if items
+                .get(*entity)
+                .is_ok_and(|parent| tiles.contains(parent.get()))
+                && !claimed.contains(entity)
+            {
+                bevy.entity(*entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Reads the files of a zone and everything spawned with it again. The zone is respawned by
+/// `respawn_zones` once they have been.
+pub fn reload_zone_files(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Prototype>>,
+    mut events: EventReader<ReloadZone>,
+    mut reloads: ResMut<ZoneReloads>,
+) {
+    for event in events.iter() {
+        let Some((_, zone)) = assets
+            .iter()
+            .find(|(_, prototype)| *prototype.id() == event.prototype)
+        else {
+            warn!("Can't reload unknown zone {}", event.prototype);
+
+            continue;
+        };
+
+        let mut paths = vec![];
+        let mut queue = vec![zone];
+
+        while let Some(prototype) = queue.pop() {
+            let path = AssetPath::from(prototype.path());
+
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+
+            queue.extend(
+                prototype
+                    .children()
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .filter_map(|child| assets.get(child.handle())),
+            );
+        }
+
+        for path in paths {
+            asset_server.reload_asset(path);
+        }
 
-pub fn spawn_trinus_castra(mut commands: ProtoCommands) {
-    commands.spawn("world.trinus.trinus-castra");
+        reloads.0.push(event.prototype.clone());
+    }
 }
 
-pub fn spawn_the_roaring_lion(mut commands: ProtoCommands) {
-    commands.spawn("world.trinus.the-roaring-lion");
+pub fn watch_prototypes(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Prototype>>,
+    time: Res<Time>,
+    mut watcher: ResMut<PrototypeWatcher>,
+) {
+    if !watcher.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let root = FileAssetIo::get_base_path().join("assets");
+
+    let paths = assets
+        .iter()
+        .map(|(_, prototype)| AsRef::<Path>::as_ref(prototype.path()).to_path_buf())
+        .collect::<HashSet<PathBuf>>();
+
+    for path in paths {
+        let Ok(modified) = fs::metadata(root.join(&path)).and_then(|meta| meta.modified()) else {
+            continue;
+        };
+
+        // The first time a file is seen is only to know when it changes later.
+        if watcher
+            .modified
+            .insert(path.clone(), modified)
+            .is_some_and(|previous| previous != modified)
+        {
+            info!("Reloading {}", path.display());
+
+            asset_server.reload_asset(path);
+        }
+    }
+}
+
+/// Despawns a zone whose prototype changed and queues it to be spawned again. Players in it
+/// are kept aside, along with everything they carry, until it's back.
+pub fn respawn_zones(
+    assets: Res<Assets<Prototype>>,
+    mut bevy: Commands,
+    mut events: EventReader<ProtoAssetEvent>,
+    mut files: EventReader<AssetEvent<Prototype>>,
+    mut modified: Local<HashSet<HandleId>>,
+    mut pending: ResMut<PendingZones>,
+    players: Query<(Entity, &Parent), With<Online>>,
+    mut reloads: ResMut<ZoneReloads>,
+    tiles: Query<(&Position, &Parent), With<Tile>>,
+    watcher: Option<Res<PrototypeWatcher>>,
+    zones: Query<(Entity, &Name), With<Zone>>,
+) {
+    // A zone is also modified whenever anything spawned with it is, which `respawn_tiles`
+    // takes care of, so only zones whose own file was read again are respawned here.
+    for file in files.iter() {
+        if let AssetEvent::Modified { handle } = file {
+            if assets
+                .get(handle)
+                .is_some_and(|prototype| prototype.schematics().contains::<Zone>())
+            {
+                modified.insert(handle.id());
+            }
+        }
+    }
+
+    for event in events.iter() {
+        let ProtoAssetEvent::Modified { id, handle } = event else {
+            continue;
+        };
+
+        if !modified.remove(&handle.id()) {
+            continue;
+        }
+
+        let Some(schematics) = assets
+            .get(handle)
+            .map(|prototype| prototype.schematics())
+            .filter(|schematics| schematics.contains::<Zone>())
+        else {
+            continue;
+        };
+
+        let requested = reloads.0.contains(id);
+
+        reloads.0.retain(|reload| reload != id);
+
+        // Live instances keep what they were spawned from, and new ones use the changes.
+        if schematics.contains::<Instanced>() {
+            continue;
+        }
+
+        if !requested && watcher.is_none() {
+            continue;
+        }
+
+        for (zone, _) in zones.iter().filter(|(_, name)| prototype_id(name) == id) {
+            for (player, tile) in players.iter() {
+                let Ok((position, tile_zone)) = tiles.get(tile.get()) else {
+                    continue;
+                };
+
+                if tile_zone.get() != zone {
+                    continue;
+                }
+
+                bevy.entity(player).remove_parent().insert(Evacuated {
+                    zone: id.clone(),
+                    position: position.0,
+                    tile: tile.get(),
+                });
+            }
+
+            bevy.entity(zone).despawn_recursive();
+        }
+
+        info!("Respawning zone {}", id);
+
+        if !pending.0.contains(id) {
+            pending.0.push(id.clone());
+        }
+    }
+}
+
+/// Respawns the tiles a changed prototype is part of in place, whether it's the tile's own or
+/// one spawned with it, rather than the whole zone. What the tile's prototype put there is
+/// spawned again, while players and whatever else has ended up on it since stay where they are.
+pub fn respawn_tiles(
+    assets: Res<Assets<Prototype>>,
+    mut bevy: Commands,
+    mut events: EventReader<ProtoAssetEvent>,
+    instances: Query<(), With<Instance>>,
+    names: Query<&Name>,
+    npcs: Query<(), With<Npc>>,
+    pending: Res<PendingZones>,
+    mut proto: ProtoCommands,
+    prototypes: Prototypes,
+    spawned: Query<(Entity, &SpawnedOn, &Parent)>,
+    tiles: Query<(Entity, &Parent), With<Tile>>,
+) {
+    let changed = events
+        .iter()
+        .filter_map(|event| match event {
+            ProtoAssetEvent::Modified { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if changed.is_empty() {
+        return;
+    }
+
+    let by_id = assets
+        .iter()
+        .map(|(_, prototype)| (prototype.id().as_str(), prototype))
+        .collect::<HashMap<_, _>>();
+
+    for (tile, zone) in tiles.iter() {
+        // Live instances keep what they were spawned from, and zones waiting to be spawned
+        // again will be from the changes.
+        if instances.contains(zone.get())
+            || names
+                .get(zone.get())
+                .is_ok_and(|name| pending.0.iter().any(|id| id == prototype_id(name)))
+        {
+            continue;
+        }
+
+        let Some((id, prototype)) = names
+            .get(tile)
+            .ok()
+            .and_then(|name| by_id.get_key_value(prototype_id(name)))
+        else {
+            continue;
+        };
+
+        if !spawned_with(&assets, prototype, &changed) || !prototypes.is_ready(*id) {
+            continue;
+        }
+
+        info!("Respawning tile {}", id);
+
+        // The tile's NPCs are replaced wherever they've wandered off to, so they aren't doubled.
+        for (entity, spawned_on, parent) in spawned.iter() {
+            if spawned_on.0 == tile && (parent.get() == tile || npcs.contains(entity)) {
+                bevy.entity(entity).despawn_recursive();
+            }
+        }
+
+        proto.entity(tile).insert(*id);
+    }
+}
+
+/// Notes which tile spawned each of the entities a tile's prototype puts on it.
+pub fn mark_tile_spawns(
+    mut bevy: Commands,
+    spawned: Query<(Entity, &Parent), Added<ProtoInstance>>,
+    tiles: Query<(), With<Tile>>,
+) {
+    for (entity, tile) in spawned.iter() {
+        if tiles.contains(tile.get()) {
+            bevy.entity(entity).insert(SpawnedOn(tile.get()));
+        }
+    }
+}
+
+/// Whether any of `ids` is the prototype or one of the prototypes spawned with it.
+fn spawned_with(assets: &Assets<Prototype>, prototype: &Prototype, ids: &[&str]) -> bool {
+    let mut queue = vec![prototype];
+
+    while let Some(prototype) = queue.pop() {
+        if ids.contains(&prototype.id().as_str()) {
+            return true;
+        }
+
+        queue.extend(
+            prototype
+                .children()
+                .iter()
+                .flat_map(|children| children.iter())
+                .filter_map(|child| assets.get(child.handle())),
+        );
+    }
+
+    false
+}
+
+/// Puts evacuated players back on the tile at their old position once their zone has been
+/// spawned again, or on a spawn tile if their tile, or the whole zone, is gone for good.
+pub fn return_evacuated(
+    mut bevy: Commands,
+    mut moved: EventWriter<EntityMoved>,
+    mut outbox: EventWriter<Outbox>,
+    pending: Res<PendingZones>,
+    players: Query<(Entity, &Client, &Evacuated), With<Online>>,
+    mut proxy: EventWriter<ProxyCommand>,
+    spawn_tiles: Query<Entity, (With<Tile>, With<Spawn>)>,
+    tiles: Query<(Entity, &Position), With<Tile>>,
+    zones: Query<(&Name, &Children), With<Zone>>,
+) {
+    for (player, client, evacuated) in players.iter() {
+        let zone_tiles = zones
+            .iter()
+            .find(|(name, _)| prototype_id(name) == evacuated.zone)
+            .map(|(_, children)| children);
+
+        if zone_tiles.is_none() && pending.0.contains(&evacuated.zone) {
+            continue;
+        }
+
+        let target = zone_tiles
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|child| tiles.get(*child).ok())
+            .find(|(_, position)| position.0 == evacuated.position)
+            .map(|(tile, _)| tile)
+            .or_else(|| spawn_tiles.iter().next());
+
+        let target = value_or_continue!(target);
+
+        if zone_tiles.is_none() {
+            outbox.send_text(
+                client.id,
+                "The place you were in is gone, and you find yourself somewhere else.",
+            );
+        }
+
+        bevy.entity(player).remove::<Evacuated>().set_parent(target);
+
+        moved.send(EntityMoved {
+            entity: player,
+            from: evacuated.tile,
+            to: target,
+            direction: None,
+            method: MoveMethod::Other,
+        });
+
+        proxy.send(ProxyCommand(ParsedCommand {
+            from: client.id,
+            command: Command::Look(None),
+        }));
+    }
 }
 
 #[derive(Component)]
@@ -30,12 +789,14 @@ pub struct SaveWorldTask(Task<Result<WorldState, sqlx::Error>>);
 pub fn save_world_state(
     database: Res<DatabasePool>,
     inventories: Query<Option<&Children>, With<Inventory>>,
-    items: Query<&Name, With<Item>>,
+    items: Query<(&Name, Option<&WrittenPages>), With<Item>>,
+    locks: Query<(&Name, &Lock, Option<&Openable>, &Parent)>,
     mut bevy: Commands,
     mut save_timer: ResMut<SaveTimer>,
     players: Query<(&Character, &Parent, &Children), With<Online>>,
     tiles: Query<&Name, With<Tile>>,
     time: Res<Time>,
+    world_state: Res<WorldState>,
 ) {
     if save_timer.0.tick(time.delta()).just_finished() {
         let mut characters: Vec<WorldStateCharacter> = Vec::new();
@@ -52,7 +813,10 @@ pub fn save_world_state(
                 .iter()
                 .flat_map(|children| children.iter())
                 .filter_map(|child| items.get(*child).ok())
-                .map(|name| name.to_string())
+                .map(|(name, written)| WorldStateItem {
+                    name: name.to_string(),
+                    written_pages: written.map(|w| w.0.clone()).unwrap_or_default(),
+                })
                 .collect();
 
             let character = WorldStateCharacter {
@@ -64,9 +828,25 @@ pub fn save_world_state(
             characters.push(character);
         }
 
+        let locks = locks
+            .iter()
+            .filter_map(|(name, lock, openable, parent)| {
+                tiles.get(parent.get()).ok().map(|tile| WorldStateLock {
+                    tile: tile.to_string(),
+                    name: name.to_string(),
+                    locked: lock.locked,
+                    open: openable.map(|openable| openable.open),
+                })
+            })
+            .collect();
+
         bevy.spawn(SaveWorldTask(spawn_save_world_state_task(
             database.0.clone(),
-            WorldState { characters },
+            WorldState {
+                characters,
+                locks,
+                epoch: world_state.epoch,
+            },
         )));
     }
 }
@@ -85,8 +865,11 @@ fn spawn_save_world_state_task(
         .await?;
 
         let mut characters = state.characters.clone();
+        let mut epoch = state.epoch;
 
         if let Some(save) = latest {
+            epoch = epoch.or(save.state.epoch);
+
             // To prevent offline characters from being removed from the world state,
             // we need to add them to the new state if they are not already present.
             for character in save.state.characters.iter() {
@@ -96,7 +879,11 @@ fn spawn_save_world_state_task(
             }
         }
 
-        let state = WorldState { characters };
+        let state = WorldState {
+            characters,
+            locks: state.locks,
+            epoch,
+        };
 
         store_world_state(&state, &mut transaction).await?;
 
@@ -151,14 +938,482 @@ pub fn handle_load_world_state_task(
     mut world_state: ResMut<WorldState>,
 ) {
     for (entity, mut task) in &mut tasks {
-        if let Some(Ok(state)) = future::block_on(future::poll_once(&mut task.0)) {
+        if let Some(Ok(mut state)) = future::block_on(future::poll_once(&mut task.0)) {
+            // The first save there is starts the clock.
+            state.epoch.get_or_insert_with(|| Utc::now().timestamp());
+
             *world_state = state;
 
+            bevy.insert_resource(WorldStateLoaded);
             bevy.entity(entity).remove::<LoadWorldStateTask>();
         }
     }
 }
 
-pub fn update_world_time(mut time: ResMut<WorldTime>) {
-    time.update();
+pub fn spawn_calendar(mut commands: ProtoCommands) {
+    commands.spawn("calendar");
+}
+
+/// Keeps the clock running from the saved epoch. It stands still until the save is loaded.
+pub fn update_world_time(
+    calendar: Res<Calendar>,
+    mut time: ResMut<WorldTime>,
+    world_state: Res<WorldState>,
+) {
+    let Some(epoch) = world_state.epoch else {
+        return;
+    };
+
+    let seconds = (Utc::now().timestamp() - epoch).max(0) as u64;
+
+    time.update(&calendar, seconds * u64::from(calendar.ratio) / 60);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, thread, time::Duration};
+
+    use bevy::asset::AssetPlugin;
+    use sqlx::PgPool;
+
+    use crate::{
+        spatial::bundles::TileBundle,
+        test::{
+            app_builder::AppBuilder,
+            item_builder::ItemBuilder,
+            player_builder::PlayerBuilder,
+            tile_builder::{TileBuilder, ZoneBuilder},
+            utils::{get_message_content, get_task, wait_for_task},
+        },
+        visual::components::{Depiction, Sprite},
+        world::{loader::WorldLoader, resources::LoadFailures},
+    };
+
+    use super::*;
+
+    #[sqlx::test]
+    async fn saves_written_pages_only_when_carried(pool: PgPool) -> sqlx::Result<()> {
+        let mut app = AppBuilder::new().database(&pool).build();
+        app.add_system(save_world_state);
+
+        let mut timer = Timer::from_seconds(60.0, TimerMode::Repeating);
+        timer.set_elapsed(Duration::from_secs(60));
+
+        app.insert_resource(SaveTimer(timer));
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let tile = TileBuilder::new()
+            .prototype("world.trinus.scriptorium")
+            .build(&mut app, zone);
+
+        let (_, _, inventory) = PlayerBuilder::new()
+            .tile(tile)
+            .has_inventory()
+            .build(&mut app);
+
+        let diary = ItemBuilder::new().prototype("items.diary").build(&mut app);
+        let ledger = ItemBuilder::new()
+            .prototype("items.ledger")
+            .tile(tile)
+            .build(&mut app);
+
+        app.world
+            .entity_mut(diary)
+            .set_parent(inventory.unwrap())
+            .insert(WrittenPages(vec!["Dear diary.".into()]));
+        app.world
+            .entity_mut(ledger)
+            .insert(WrittenPages(vec!["Paid in full.".into()]));
+
+        app.update();
+
+        wait_for_task(&get_task::<SaveWorldTask>(&mut app).unwrap().0);
+
+        let save = sqlx::query_as::<_, WorldSaveModel>(
+            "SELECT * FROM world_saves ORDER BY id DESC LIMIT 1",
+        )
+        .fetch_one(&pool)
+        .await?;
+
+        let saved = save
+            .state
+            .0
+            .characters
+            .iter()
+            .flat_map(|character| character.inventory.iter())
+            .map(|item| (prototype_id(&item.name), item.written_pages.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            saved,
+            vec![("items.diary", vec!["Dear diary.".to_string()])]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn returns_to_same_position() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(return_evacuated);
+
+        let zone = ZoneBuilder::new()
+            .prototype("world.trinus.trinus-castra")
+            .build(&mut app);
+        TileBuilder::new()
+            .position(IVec3::ZERO)
+            .build(&mut app, zone);
+        let gate = TileBuilder::new()
+            .position(IVec3::new(0, 1, 0))
+            .build(&mut app, zone);
+
+        let (player, _, _) = PlayerBuilder::new().build(&mut app);
+
+        app.world.entity_mut(player).insert(Evacuated {
+            zone: "world.trinus.trinus-castra".into(),
+            position: IVec3::new(0, 1, 0),
+            tile: Entity::PLACEHOLDER,
+        });
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), gate);
+        assert!(app.world.get::<Evacuated>(player).is_none());
+    }
+
+    #[test]
+    fn returns_to_spawn_when_tile_removed() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(return_evacuated);
+
+        let zone = ZoneBuilder::new()
+            .prototype("world.trinus.trinus-castra")
+            .build(&mut app);
+        let spawn = TileBuilder::new()
+            .position(IVec3::ZERO)
+            .is_spawn()
+            .build(&mut app, zone);
+
+        let (player, _, _) = PlayerBuilder::new().build(&mut app);
+
+        app.world.entity_mut(player).insert(Evacuated {
+            zone: "world.trinus.trinus-castra".into(),
+            position: IVec3::new(4, 4, 0),
+            tile: Entity::PLACEHOLDER,
+        });
+        app.update();
+
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), spawn);
+    }
+
+    #[test]
+    fn returns_to_spawn_when_zone_removed() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(return_evacuated);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        let spawn = TileBuilder::new().is_spawn().build(&mut app, zone);
+
+        let (player, client_id, _) = PlayerBuilder::new().build(&mut app);
+
+        app.world.entity_mut(player).insert(Evacuated {
+            zone: "world.trinus.trinus-castra".into(),
+            position: IVec3::ZERO,
+            tile: Entity::PLACEHOLDER,
+        });
+        app.update();
+
+        let content = get_message_content(&mut app, client_id).unwrap();
+
+        assert_eq!(
+            content,
+            "The place you were in is gone, and you find yourself somewhere else."
+        );
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), spawn);
+        assert_eq!(app.world.resource::<Events<EntityMoved>>().len(), 1);
+    }
+
+    #[test]
+    fn waits_for_zone() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(return_evacuated);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        TileBuilder::new().is_spawn().build(&mut app, zone);
+
+        let (player, _, _) = PlayerBuilder::new().build(&mut app);
+
+        app.world
+            .resource_mut::<PendingZones>()
+            .0
+            .push("world.trinus.trinus-castra".into());
+
+        app.world.entity_mut(player).insert(Evacuated {
+            zone: "world.trinus.trinus-castra".into(),
+            position: IVec3::ZERO,
+            tile: Entity::PLACEHOLDER,
+        });
+        app.update();
+
+        assert!(app.world.get::<Parent>(player).is_none());
+        assert!(app.world.get::<Evacuated>(player).is_some());
+    }
+
+    #[test]
+    fn closes_empty_instances() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(close_instances);
+
+        let crypt = |app: &mut App| {
+            let zone = ZoneBuilder::new().name("Crypt").build(app);
+            app.world.entity_mut(zone).insert((
+                Instanced { grace: 0.0 },
+                Instance {
+                    members: vec![],
+                    closing: None,
+                },
+            ));
+
+            (zone, TileBuilder::new().build(app, zone))
+        };
+
+        let (empty, _) = crypt(&mut app);
+        let (occupied, tile) = crypt(&mut app);
+
+        PlayerBuilder::new().tile(tile).build(&mut app);
+        app.update();
+
+        assert!(app.world.get_entity(empty).is_none());
+        assert!(app.world.get_entity(occupied).is_some());
+    }
+
+    fn resets(app: &mut App) -> usize {
+        let events = app.world.resource::<Events<ResetZone>>();
+
+        events.get_reader().iter(events).count()
+    }
+
+    #[test]
+    fn warns_before_reset() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(tick_resets);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(Reset {
+            interval: 0.0,
+            policy: ResetPolicy::Always,
+            message: "Dust settles over the crypt.".into(),
+        });
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let (_, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+        app.update();
+        app.update();
+
+        assert_eq!(
+            get_message_content(&mut app, client_id).unwrap(),
+            "Dust settles over the crypt."
+        );
+        assert_eq!(resets(&mut app), 1);
+    }
+
+    #[test]
+    fn waits_until_empty() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(tick_resets);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(Reset {
+            interval: 0.0,
+            policy: ResetPolicy::Empty,
+            message: "Dust settles over the crypt.".into(),
+        });
+        let tile = TileBuilder::new().build(&mut app, zone);
+
+        let elsewhere = ZoneBuilder::new().build(&mut app);
+        let away = TileBuilder::new().build(&mut app, elsewhere);
+
+        let (player, client_id, _) = PlayerBuilder::new().tile(tile).build(&mut app);
+        app.update();
+        app.update();
+
+        assert!(get_message_content(&mut app, client_id).is_none());
+        assert_eq!(resets(&mut app), 0);
+
+        app.world.entity_mut(player).set_parent(away);
+        app.update();
+
+        assert_eq!(resets(&mut app), 1);
+    }
+
+    #[test]
+    fn never_resets() {
+        let mut app = AppBuilder::new().build();
+        app.add_system(tick_resets);
+
+        let zone = ZoneBuilder::new().build(&mut app);
+        app.world.entity_mut(zone).insert(Reset {
+            interval: 0.0,
+            policy: ResetPolicy::Never,
+            message: "Dust settles over the crypt.".into(),
+        });
+        app.update();
+        app.update();
+
+        assert_eq!(resets(&mut app), 0);
+    }
+
+    fn tile_prototype(name: &str, description: &str, x: i32, children: &[&str]) -> String {
+        format!(
+            r#"(
+                name: "world.test.{name}",
+                schematics: {{
+                    "server::spatial::bundles::TileBundle": (
+                        tile: (name: "{name}", description: "{description}"),
+                        sprite: (character: "."),
+                        position: ((x: {x}, y: 0, z: 0)),
+                    ),
+                }},
+                children: {children:?},
+            )"#
+        )
+    }
+
+    /// Updates the app until `done`, giving files time to load in the background.
+    fn update_until(app: &mut App, done: impl Fn(&mut World) -> bool) {
+        for _ in 0..200 {
+            app.update();
+
+            if done(&mut app.world) {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("Gave up waiting on prototypes");
+    }
+
+    #[test]
+    fn respawns_edited_tile() {
+        let assets = env::temp_dir().join(format!("respawns-edited-tile-{}", std::process::id()));
+        let world = assets.join("world");
+
+        fs::create_dir_all(&world).unwrap();
+        fs::write(
+            world.join("test.proto.ron"),
+            r#"(
+                name: "world.test",
+                schematics: {
+                    "server::spatial::components::Zone": (name: "Test"),
+                },
+                children: ["gate.proto.ron", "yard.proto.ron"],
+            )"#,
+        )
+        .unwrap();
+        fs::write(
+            world.join("gate.proto.ron"),
+            tile_prototype("gate", "An old gate.", 0, &["sign.proto.ron"]),
+        )
+        .unwrap();
+        fs::write(
+            world.join("sign.proto.ron"),
+            r#"(
+                name: "world.test.sign",
+                schematics: {
+                    "server::visual::components::Depiction": (
+                        name: "a sign",
+                        short_name: "sign",
+                        description: "A weathered sign.",
+                        tags: [],
+                        visible: true,
+                    ),
+                },
+            )"#,
+        )
+        .unwrap();
+        fs::write(
+            world.join("yard.proto.ron"),
+            tile_prototype("yard", "A muddy yard.", 1, &[]),
+        )
+        .unwrap();
+
+        let mut app = AppBuilder::new().build();
+        app.add_plugin(AssetPlugin {
+            asset_folder: assets.display().to_string(),
+            watch_for_changes: false,
+        })
+        .add_plugin(ProtoPlugin::new_with_loader(WorldLoader::new(
+            LoadFailures::default(),
+        )))
+        .register_type::<Depiction>()
+        .register_type::<Position>()
+        .register_type::<Sprite>()
+        .register_type::<Tile>()
+        .register_type::<TileBundle>()
+        .register_type::<Vec<String>>()
+        .register_type::<Zone>()
+        .init_resource::<PendingZones>()
+        .init_resource::<ZoneReloads>()
+        .insert_resource(PrototypeWatcher {
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            modified: HashMap::new(),
+        })
+        .add_startup_system(|mut prototypes: PrototypesMut| {
+            prototypes.load("world/test.proto.ron");
+        })
+        .add_systems((
+            find_zones,
+            respawn_zones,
+            respawn_tiles.after(respawn_zones),
+            spawn_zones.after(find_zones).after(respawn_zones),
+            mark_tile_spawns,
+        ));
+
+        let tile_named = |world: &mut World, name: &str| {
+            world
+                .query::<(Entity, &Tile)>()
+                .iter(world)
+                .find(|(_, tile)| tile.name == name)
+                .map(|(entity, _)| entity)
+        };
+
+        update_until(&mut app, |world| tile_named(world, "yard").is_some());
+
+        let gate = tile_named(&mut app.world, "gate").unwrap();
+        let yard = tile_named(&mut app.world, "yard").unwrap();
+        let zone = app.world.get::<Parent>(gate).unwrap().get();
+
+        let (player, _, _) = PlayerBuilder::new().tile(gate).build(&mut app);
+        let dropped = ItemBuilder::new().tile(gate).build(&mut app);
+        let item = ItemBuilder::new().tile(yard).build(&mut app);
+
+        fs::write(
+            world.join("gate.proto.ron"),
+            tile_prototype("gate", "A new gate.", 0, &["sign.proto.ron"]),
+        )
+        .unwrap();
+
+        app.world
+            .resource::<AssetServer>()
+            .reload_asset("world/gate.proto.ron");
+
+        update_until(&mut app, |world| {
+            world.get::<Tile>(gate).unwrap().description == "A new gate."
+        });
+
+        fs::remove_dir_all(&assets).unwrap();
+
+        let signs = app
+            .world
+            .query::<(&Depiction, &Parent)>()
+            .iter(&app.world)
+            .filter(|(depiction, parent)| depiction.short_name == "sign" && parent.get() == gate)
+            .count();
+
+        assert!(app.world.get_entity(zone).is_some());
+        assert_eq!(app.world.get::<Parent>(player).unwrap().get(), gate);
+        assert_eq!(app.world.get::<Parent>(dropped).unwrap().get(), gate);
+        assert_eq!(app.world.get::<Parent>(item).unwrap().get(), yard);
+        assert_eq!(signs, 1);
+    }
 }